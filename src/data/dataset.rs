@@ -0,0 +1,113 @@
+use crate::linalg::{Array, Numeric};
+
+/// Collection of examples accessed by index.
+///
+/// An example consists of one or more arrays, e.g. features and a label. Arrays at the
+/// same position have equal shapes in all examples, so they can be stacked into batches.
+pub trait Dataset<T: Numeric> {
+    /// Returns the number of examples.
+    fn len(&self) -> usize;
+
+    /// Returns true if there are no examples.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns arrays of the example at a given index.
+    ///
+    /// * `index` - Index smaller than `len()`.
+    ///
+    /// **Panics** if `index` is out of bounds.
+    fn get(&self, index: usize) -> Vec<Array<T>>;
+}
+
+impl<T: Numeric, D: Dataset<T> + ?Sized> Dataset<T> for &D {
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    fn get(&self, index: usize) -> Vec<Array<T>> {
+        (**self).get(index)
+    }
+}
+
+/// Examples stored in memory as a list of arrays per example.
+impl<T: Numeric> Dataset<T> for Vec<Vec<Array<T>>> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn get(&self, index: usize) -> Vec<Array<T>> {
+        self[index].clone()
+    }
+}
+
+/// In-memory dataset made of arrays whose first axis indexes examples.
+///
+/// An example consists of sub-arrays at a given index of the first axis of every array.
+/// Examples of 1-dimensional arrays have shape `[1]`.
+///
+/// # Examples
+/// ```
+/// use neurust::data::{ArrayDataset, Dataset};
+/// use neurust::Array;
+///
+/// let features = Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![3, 2]);
+/// let labels = Array::from_vec(vec![0., 1., 0.], vec![3]);
+/// let dataset = ArrayDataset::new(vec![features, labels]);
+///
+/// assert_eq!(dataset.len(), 3);
+/// assert_eq!(
+///     dataset.get(1),
+///     vec![
+///         Array::from_vec(vec![3., 4.], vec![2]),
+///         Array::from_vec(vec![1.], vec![1])
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ArrayDataset<T: Numeric> {
+    arrays: Vec<Array<T>>,
+}
+
+impl<T: Numeric> ArrayDataset<T> {
+    /// Creates a new `ArrayDataset`.
+    ///
+    /// * `arrays` - Non-empty vector of arrays with equal sizes of the first axis.
+    ///
+    /// **Panics** if `arrays` is empty or sizes of their first axes differ.
+    pub fn new(arrays: Vec<Array<T>>) -> ArrayDataset<T> {
+        if arrays.is_empty()
+            || arrays
+                .iter()
+                .any(|array| array.shape[0] != arrays[0].shape[0])
+        {
+            panic!(
+                "Arrays have to have equal sizes of the first axis. Got shapes: {:?}",
+                arrays
+                    .iter()
+                    .map(|array| array.get_shape())
+                    .collect::<Vec<Vec<usize>>>()
+            )
+        }
+        ArrayDataset { arrays }
+    }
+
+    /// Returns arrays of the dataset.
+    pub fn arrays(&self) -> &[Array<T>] {
+        &self.arrays
+    }
+}
+
+impl<T: Numeric> Dataset<T> for ArrayDataset<T> {
+    fn len(&self) -> usize {
+        self.arrays[0].shape[0]
+    }
+
+    fn get(&self, index: usize) -> Vec<Array<T>> {
+        self.arrays
+            .iter()
+            .map(|array| array.select(0, index))
+            .collect()
+    }
+}
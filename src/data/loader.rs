@@ -0,0 +1,383 @@
+use crate::data::Dataset;
+use crate::linalg::{stack, Array, Numeric, Rng};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::mpsc::{sync_channel, Receiver};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+/// Batch of examples with arrays stacked along a new leading axis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Batch<T: Numeric> {
+    names: Vec<String>,
+    arrays: Vec<Array<T>>,
+}
+
+impl<T: Numeric> Batch<T> {
+    /// Returns the number of examples in the batch.
+    pub fn len(&self) -> usize {
+        self.arrays[0].shape[0]
+    }
+
+    /// Returns true if there are no examples in the batch, which never happens for
+    /// batches created by `DataLoader`.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns stacked arrays in the order of examples' arrays.
+    pub fn arrays(&self) -> &[Array<T>] {
+        &self.arrays
+    }
+
+    /// Returns the stacked array fed to a placeholder with a given name, if any.
+    ///
+    /// * `name` - Name of the placeholder.
+    pub fn get(&self, name: &str) -> Option<&Array<T>> {
+        self.names
+            .iter()
+            .position(|other| other == name)
+            .map(|i| &self.arrays[i])
+    }
+
+    /// Returns a feed dictionary mapping placeholder names to stacked arrays, ready to
+    /// be passed to `Tensor::eval`.
+    pub fn feed_dict(&self) -> HashMap<String, &Array<T>> {
+        self.names.iter().cloned().zip(self.arrays.iter()).collect()
+    }
+
+    /// Returns stacked arrays, consuming the batch.
+    pub fn into_arrays(self) -> Vec<Array<T>> {
+        self.arrays
+    }
+}
+
+/// Iterates over batches of a dataset.
+///
+/// Examples are optionally shuffled with a seed mixed with the epoch number, so every
+/// epoch has a different, but reproducible order. Arrays of examples are stacked along
+/// a new leading axis and exposed as feed dictionaries keyed by placeholder names.
+/// Batches can be prepared in background threads with `prefetch_epoch`, while the graph
+/// is evaluated on the calling thread.
+///
+/// # Examples
+/// ```
+/// use neurust::data::{ArrayDataset, DataLoader};
+/// use neurust::prelude::*;
+///
+/// let features = Array::from_vec((0..10).map(|x| x as f64).collect(), vec![5, 2]);
+/// let labels = Array::from_vec(vec![0., 1., 0., 1., 1.], vec![5]);
+/// let mut loader = DataLoader::new(
+///     ArrayDataset::new(vec![features, labels]),
+///     &["x", "y"],
+///     2,
+///     Some(42),
+///     false,
+/// );
+/// let x = Tensor::new_placeholder("x".to_owned(), vec![2, 2]);
+///
+/// let batches: Vec<_> = loader.epoch().collect();
+///
+/// assert_eq!(batches.len(), 3);
+/// assert_eq!(batches[0].get("y").unwrap().get_shape(), vec![2, 1]);
+/// assert_eq!(batches[2].len(), 1);
+/// assert_eq!(x.eval(Some(&batches[0].feed_dict())).get_shape(), vec![2, 2]);
+/// ```
+pub struct DataLoader<T: Numeric, D: Dataset<T>> {
+    dataset: Arc<D>,
+    names: Vec<String>,
+    batch_size: usize,
+    seed: Option<u64>,
+    drop_last: bool,
+    epoch: u64,
+    phantom: PhantomData<T>,
+}
+
+impl<T: Numeric, D: Dataset<T>> DataLoader<T, D> {
+    /// Creates a new `DataLoader`.
+    ///
+    /// * `dataset` - Non-empty dataset of examples.
+    /// * `names` - Names of placeholders fed with examples' arrays, in the same order.
+    /// * `batch_size` - Positive number of examples in a batch.
+    /// * `seed` - Seed of shuffling examples every epoch. If `None`, examples are
+    ///   iterated in order.
+    /// * `drop_last` - If true, the last batch is dropped when it's smaller than
+    ///   `batch_size`.
+    ///
+    /// **Panics** if `dataset` is empty, `batch_size` is zero or `names` has a different
+    /// length than examples.
+    pub fn new(
+        dataset: D,
+        names: &[&str],
+        batch_size: usize,
+        seed: Option<u64>,
+        drop_last: bool,
+    ) -> DataLoader<T, D> {
+        if dataset.is_empty() || batch_size == 0 {
+            panic!(
+                "Dataset and batch size have to be positive. Got dataset of length {} and batch size {}.",
+                dataset.len(),
+                batch_size
+            )
+        }
+        let example_len = dataset.get(0).len();
+        if names.len() != example_len {
+            panic!(
+                "Got {} names for examples consisting of {} arrays.",
+                names.len(),
+                example_len
+            )
+        }
+        DataLoader {
+            dataset: Arc::new(dataset),
+            names: names.iter().map(|&name| name.to_owned()).collect(),
+            batch_size,
+            seed,
+            drop_last,
+            epoch: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the dataset.
+    pub fn dataset(&self) -> &D {
+        &self.dataset
+    }
+
+    /// Returns the number of batches in an epoch.
+    pub fn len(&self) -> usize {
+        if self.drop_last {
+            self.dataset.len() / self.batch_size
+        } else {
+            self.dataset.len().div_ceil(self.batch_size)
+        }
+    }
+
+    /// Returns true if an epoch has no batches, i.e. `drop_last` is set and the dataset
+    /// is smaller than a batch.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of the next epoch, starting from 0.
+    pub fn current_epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Sets the number of the next epoch, e.g. to resume training with the same order
+    /// of examples.
+    ///
+    /// * `epoch` - Number of the epoch.
+    pub fn set_epoch(&mut self, epoch: u64) {
+        self.epoch = epoch;
+    }
+
+    /// Returns indices of examples in the order of the next epoch and moves to the
+    /// following one.
+    fn next_epoch_indices(&mut self) -> Vec<usize> {
+        let mut indices = match self.seed {
+            Some(seed) => epoch_rng(seed, self.epoch).permutation(self.dataset.len()),
+            None => (0..self.dataset.len()).collect(),
+        };
+        indices.truncate(self.len() * self.batch_size);
+        self.epoch += 1;
+        indices
+    }
+
+    /// Returns an iterator over batches of the next epoch and moves to the following one.
+    pub fn epoch(&mut self) -> Batches<'_, T, D> {
+        let indices = self.next_epoch_indices();
+        Batches {
+            loader: self,
+            indices,
+            position: 0,
+        }
+    }
+
+    /// Returns an iterator over batches of the next epoch prepared in background threads
+    /// and moves to the following one.
+    ///
+    /// Batches are assigned to workers in turns and every worker keeps at most
+    /// `queue_size` prepared batches, so memory usage is bounded. Batches are returned in
+    /// the same order as by `epoch()`. Dropping the iterator stops the workers.
+    ///
+    /// * `workers` - Positive number of worker threads.
+    /// * `queue_size` - Positive number of batches prepared in advance by every worker.
+    ///
+    /// **Panics** if `workers` or `queue_size` is zero. A panic of a worker, e.g. caused
+    /// by an invalid example, is propagated when its batch is requested.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::data::{ArrayDataset, DataLoader};
+    /// use neurust::Array;
+    ///
+    /// let features = Array::from_vec((0..20).map(|x| x as f64).collect(), vec![10, 2]);
+    /// let mut loader = DataLoader::new(ArrayDataset::new(vec![features]), &["x"], 3, None, false);
+    ///
+    /// let batches: Vec<_> = loader.prefetch_epoch(2, 1).collect();
+    ///
+    /// assert_eq!(batches.len(), 4);
+    /// assert_eq!(
+    ///     batches[1].get("x").unwrap(),
+    ///     &Array::from_vec((6..12).map(|x| x as f64).collect(), vec![3, 2])
+    /// );
+    /// ```
+    pub fn prefetch_epoch(&mut self, workers: usize, queue_size: usize) -> PrefetchedBatches<T>
+    where
+        T: Send + 'static,
+        D: Send + Sync + 'static,
+    {
+        if workers == 0 || queue_size == 0 {
+            panic!(
+                "Number of workers and queue size have to be positive. Got {} and {}.",
+                workers, queue_size
+            )
+        }
+        let indices = self.next_epoch_indices();
+        let batches: Vec<Vec<usize>> = indices
+            .chunks(self.batch_size)
+            .map(|batch| batch.to_vec())
+            .collect();
+
+        let workers = workers.min(batches.len());
+        let mut receivers = Vec::with_capacity(workers);
+        let mut handles = Vec::with_capacity(workers);
+        for worker in 0..workers {
+            let (sender, receiver) = sync_channel(queue_size);
+            let dataset = Arc::clone(&self.dataset);
+            let names = self.names.clone();
+            let assigned: Vec<Vec<usize>> = batches
+                .iter()
+                .skip(worker)
+                .step_by(workers)
+                .cloned()
+                .collect();
+            handles.push(thread::spawn(move || {
+                for indices in assigned {
+                    // the receiver is dropped when iteration stops early
+                    if sender
+                        .send(make_batch(&*dataset, &indices, names.clone()))
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            }));
+            receivers.push(receiver);
+        }
+        PrefetchedBatches {
+            receivers,
+            handles,
+            len: batches.len(),
+            position: 0,
+        }
+    }
+}
+
+// Returns the generator shuffling examples of an epoch. The seed is scrambled before
+// the epoch is added, so loaders with neighbouring seeds don't repeat each other's
+// orders shifted by an epoch.
+fn epoch_rng(seed: u64, epoch: u64) -> Rng {
+    Rng::new(Rng::new(seed).next_u64().wrapping_add(epoch))
+}
+
+// Stacks arrays of examples at given indices along a new leading axis.
+fn make_batch<T: Numeric, D: Dataset<T> + ?Sized>(
+    dataset: &D,
+    indices: &[usize],
+    names: Vec<String>,
+) -> Batch<T> {
+    let examples: Vec<Vec<Array<T>>> = indices.iter().map(|&i| dataset.get(i)).collect();
+    let arrays = (0..names.len())
+        .map(|i| {
+            let arrays: Vec<&Array<T>> = examples.iter().map(|example| &example[i]).collect();
+            stack(&arrays, 0)
+        })
+        .collect();
+    Batch { names, arrays }
+}
+
+/// Iterator over batches of an epoch, created by `DataLoader::epoch`.
+pub struct Batches<'a, T: Numeric, D: Dataset<T>> {
+    loader: &'a DataLoader<T, D>,
+    indices: Vec<usize>,
+    position: usize,
+}
+
+impl<'a, T: Numeric, D: Dataset<T>> Iterator for Batches<'a, T, D> {
+    type Item = Batch<T>;
+
+    fn next(&mut self) -> Option<Batch<T>> {
+        if self.position >= self.indices.len() {
+            return None;
+        }
+        let end = (self.position + self.loader.batch_size).min(self.indices.len());
+        let batch = make_batch(
+            &*self.loader.dataset,
+            &self.indices[self.position..end],
+            self.loader.names.clone(),
+        );
+        self.position = end;
+        Some(batch)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.indices.len() - self.position).div_ceil(self.loader.batch_size);
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: Numeric, D: Dataset<T>> ExactSizeIterator for Batches<'a, T, D> {}
+
+/// Iterator over batches of an epoch prepared in background threads, created by
+/// `DataLoader::prefetch_epoch`.
+pub struct PrefetchedBatches<T: Numeric> {
+    receivers: Vec<Receiver<Batch<T>>>,
+    handles: Vec<JoinHandle<()>>,
+    len: usize,
+    position: usize,
+}
+
+impl<T: Numeric> Iterator for PrefetchedBatches<T> {
+    type Item = Batch<T>;
+
+    fn next(&mut self) -> Option<Batch<T>> {
+        if self.position >= self.len {
+            return None;
+        }
+        let worker = self.position % self.receivers.len();
+        match self.receivers[worker].recv() {
+            Ok(batch) => {
+                self.position += 1;
+                Some(batch)
+            }
+            // the worker has stopped before sending all its batches, so it panicked
+            Err(_) => {
+                self.position = self.len;
+                let handle = self.handles.remove(worker);
+                match handle.join() {
+                    Err(payload) => std::panic::resume_unwind(payload),
+                    Ok(()) => panic!("Data loader worker stopped unexpectedly."),
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.position;
+        (len, Some(len))
+    }
+}
+
+impl<T: Numeric> ExactSizeIterator for PrefetchedBatches<T> {}
+
+impl<T: Numeric> Drop for PrefetchedBatches<T> {
+    fn drop(&mut self) {
+        // dropping receivers makes blocked workers stop
+        self.receivers.clear();
+        for handle in self.handles.drain(..) {
+            let _ = handle.join();
+        }
+    }
+}
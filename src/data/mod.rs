@@ -0,0 +1,7 @@
+//! Datasets and batching of examples for training.
+mod dataset;
+mod loader;
+pub mod transforms;
+
+pub use dataset::{ArrayDataset, Dataset};
+pub use loader::{Batch, Batches, DataLoader, PrefetchedBatches};
@@ -0,0 +1,700 @@
+//! Data augmentation transforms of images with associated bounding boxes.
+use crate::linalg::{resize_nchw, Array, Interpolation, Numeric, Rng};
+
+/// Axis-aligned bounding box in pixel coordinates of an image.
+///
+/// Coordinates are continuous, i.e. an image of width `W` spans `[0, W]` horizontally
+/// and a box covering the first column of pixels has `x_min = 0` and `x_max = 1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox<T: Numeric> {
+    pub x_min: T,
+    pub y_min: T,
+    pub x_max: T,
+    pub y_max: T,
+}
+
+impl<T: Numeric> BoundingBox<T> {
+    /// Creates a new `BoundingBox`.
+    ///
+    /// * `x_min` - Left edge of the box.
+    /// * `y_min` - Top edge of the box.
+    /// * `x_max` - Right edge of the box.
+    /// * `y_max` - Bottom edge of the box.
+    pub fn new(x_min: T, y_min: T, x_max: T, y_max: T) -> BoundingBox<T> {
+        BoundingBox {
+            x_min,
+            y_min,
+            x_max,
+            y_max,
+        }
+    }
+
+    /// Returns area of the box, zero if it's empty.
+    pub fn area(&self) -> T {
+        (self.x_max - self.x_min).max(T::zero()) * (self.y_max - self.y_min).max(T::zero())
+    }
+
+    /// Returns true if the box has no area, e.g. after it was cropped out of an image.
+    pub fn is_empty(&self) -> bool {
+        self.area() == T::zero()
+    }
+
+    // Returns the box clipped to an image of a given size.
+    fn clip(&self, height: usize, width: usize) -> BoundingBox<T> {
+        let clip = |x: T, size: usize| x.max(T::zero()).min(T::from(size).unwrap());
+        BoundingBox::new(
+            clip(self.x_min, width),
+            clip(self.y_min, height),
+            clip(self.x_max, width),
+            clip(self.y_max, height),
+        )
+    }
+
+    // Returns the box with coordinates mapped by a function of `(x, y)` points, as the
+    // smallest box containing mapped corners.
+    fn map_corners(&self, mapping: impl Fn(f64, f64) -> (f64, f64)) -> BoundingBox<T> {
+        let (x_min, y_min) = (self.x_min.to_f64().unwrap(), self.y_min.to_f64().unwrap());
+        let (x_max, y_max) = (self.x_max.to_f64().unwrap(), self.y_max.to_f64().unwrap());
+        let corners = [
+            mapping(x_min, y_min),
+            mapping(x_max, y_min),
+            mapping(x_min, y_max),
+            mapping(x_max, y_max),
+        ];
+        let fold = |select: fn(&(f64, f64)) -> f64, init: f64, f: fn(f64, f64) -> f64| {
+            T::from(corners.iter().map(select).fold(init, f)).unwrap()
+        };
+        BoundingBox::new(
+            fold(|corner| corner.0, f64::INFINITY, f64::min),
+            fold(|corner| corner.1, f64::INFINITY, f64::min),
+            fold(|corner| corner.0, f64::NEG_INFINITY, f64::max),
+            fold(|corner| corner.1, f64::NEG_INFINITY, f64::max),
+        )
+    }
+}
+
+/// Transformation of an image, optionally random, applied consistently to bounding
+/// boxes of objects in the image.
+///
+/// Images are arrays of shape `[channels, height, width]`. Random transforms draw their
+/// parameters from a given generator, so results are reproducible with a seed.
+pub trait Transform<T: Numeric> {
+    /// Transforms an image and bounding boxes in its pixel coordinates.
+    ///
+    /// Boxes are returned in the same order. Boxes moved out of the image are clipped to
+    /// its borders and become empty, so they can be filtered out together with their
+    /// labels.
+    ///
+    /// * `image` - Image of shape `[channels, height, width]`.
+    /// * `boxes` - Bounding boxes, possibly none.
+    /// * `rng` - Random number generator used to draw parameters of the transform.
+    ///
+    /// **Panics** if `image` isn't 3-dimensional or its shape isn't valid for the
+    /// transform.
+    fn apply(
+        &self,
+        image: &Array<T>,
+        boxes: &[BoundingBox<T>],
+        rng: &mut Rng,
+    ) -> (Array<T>, Vec<BoundingBox<T>>);
+
+    /// Transforms an image without bounding boxes.
+    ///
+    /// * `image` - Image of shape `[channels, height, width]`.
+    /// * `rng` - Random number generator used to draw parameters of the transform.
+    fn apply_image(&self, image: &Array<T>, rng: &mut Rng) -> Array<T> {
+        self.apply(image, &[], rng).0
+    }
+}
+
+// Returns channels, height and width of an image. Panics if it isn't 3-dimensional.
+fn get_image_shape<T: Numeric>(image: &Array<T>) -> (usize, usize, usize) {
+    if image.shape.len() != 3 {
+        panic!(
+            "Images have to be of shape [channels, height, width]. Got: {:?}",
+            image.shape
+        )
+    }
+    (image.shape[0], image.shape[1], image.shape[2])
+}
+
+// Checks if a probability is in `[0, 1]` interval. Panics if not.
+fn check_probability(p: f64) {
+    if !(0. ..=1.).contains(&p) {
+        panic!("Probability has to be in [0, 1] interval. Got: {}", p)
+    }
+}
+
+/// Applies transforms one after another.
+///
+/// # Examples
+/// ```
+/// use neurust::data::transforms::{
+///     BoundingBox, Compose, RandomCrop, RandomHorizontalFlip, Transform,
+/// };
+/// use neurust::linalg::Rng;
+/// use neurust::Array;
+///
+/// let mut transform = Compose::new();
+/// transform.add(RandomCrop::new(4, 4));
+/// transform.add(RandomHorizontalFlip::new(0.5));
+///
+/// let image = Array::new(0.5, vec![3, 6, 8]);
+/// let boxes = [BoundingBox::new(1., 1., 3., 3.)];
+/// let (output, output_boxes) = transform.apply(&image, &boxes, &mut Rng::new(0));
+///
+/// assert_eq!(output.get_shape(), vec![3, 4, 4]);
+/// assert_eq!(output_boxes.len(), 1);
+/// ```
+pub struct Compose<T: Numeric> {
+    transforms: Vec<Box<dyn Transform<T>>>,
+}
+
+impl<T: Numeric> Compose<T> {
+    /// Creates a new `Compose` without any transforms.
+    pub fn new() -> Compose<T> {
+        Compose {
+            transforms: Vec::new(),
+        }
+    }
+
+    /// Appends a transform at the end.
+    ///
+    /// * `transform` - Transform to be added.
+    pub fn add(&mut self, transform: impl Transform<T> + 'static) {
+        self.transforms.push(Box::new(transform));
+    }
+}
+
+impl<T: Numeric> Default for Compose<T> {
+    fn default() -> Self {
+        Compose::new()
+    }
+}
+
+impl<T: Numeric> Transform<T> for Compose<T> {
+    fn apply(
+        &self,
+        image: &Array<T>,
+        boxes: &[BoundingBox<T>],
+        rng: &mut Rng,
+    ) -> (Array<T>, Vec<BoundingBox<T>>) {
+        let mut output = (image.clone(), boxes.to_vec());
+        for transform in self.transforms.iter() {
+            output = transform.apply(&output.0, &output.1, rng);
+        }
+        output
+    }
+}
+
+/// Crops a region of a given size at a random position.
+pub struct RandomCrop {
+    height: usize,
+    width: usize,
+}
+
+impl RandomCrop {
+    /// Creates a new `RandomCrop`.
+    ///
+    /// * `height` - Positive height of the cropped region.
+    /// * `width` - Positive width of the cropped region.
+    ///
+    /// **Panics** if `height` or `width` is zero.
+    pub fn new(height: usize, width: usize) -> RandomCrop {
+        if height == 0 || width == 0 {
+            panic!("Crop size has to be positive. Got: {}x{}", height, width)
+        }
+        RandomCrop { height, width }
+    }
+}
+
+impl<T: Numeric> Transform<T> for RandomCrop {
+    /// **Panics** if the image is smaller than the cropped region.
+    fn apply(
+        &self,
+        image: &Array<T>,
+        boxes: &[BoundingBox<T>],
+        rng: &mut Rng,
+    ) -> (Array<T>, Vec<BoundingBox<T>>) {
+        let (_, height, width) = get_image_shape(image);
+        if height < self.height || width < self.width {
+            panic!(
+                "Image of size {}x{} is smaller than crop size {}x{}.",
+                height, width, self.height, self.width
+            )
+        }
+        let top = rng.below(height - self.height + 1);
+        let left = rng.below(width - self.width + 1);
+        let output = image
+            .s(vec![
+                (..).into(),
+                (top..top + self.height).into(),
+                (left..left + self.width).into(),
+            ])
+            .to_array();
+        let (top, left) = (T::from(top).unwrap(), T::from(left).unwrap());
+        let boxes = boxes
+            .iter()
+            .map(|bounding_box| {
+                BoundingBox::new(
+                    bounding_box.x_min - left,
+                    bounding_box.y_min - top,
+                    bounding_box.x_max - left,
+                    bounding_box.y_max - top,
+                )
+                .clip(self.height, self.width)
+            })
+            .collect();
+        (output, boxes)
+    }
+}
+
+// Reverses an image along its height (axis 1) or width (axis 2).
+fn flip<T: Numeric>(image: &Array<T>, axis: usize) -> Array<T> {
+    let (channels, height, width) = get_image_shape(image);
+    let mut data = Vec::with_capacity(image.data.len());
+    for c in 0..channels {
+        for y in 0..height {
+            let source_y = if axis == 1 { height - 1 - y } else { y };
+            let row = &image.data[(c * height + source_y) * width..][..width];
+            if axis == 2 {
+                data.extend(row.iter().rev());
+            } else {
+                data.extend_from_slice(row);
+            }
+        }
+    }
+    Array::from_vec(data, image.get_shape())
+}
+
+/// Flips images horizontally with a given probability.
+pub struct RandomHorizontalFlip {
+    p: f64,
+}
+
+impl RandomHorizontalFlip {
+    /// Creates a new `RandomHorizontalFlip`.
+    ///
+    /// * `p` - Probability of flipping an image.
+    ///
+    /// **Panics** if `p` isn't in `[0, 1]` interval.
+    pub fn new(p: f64) -> RandomHorizontalFlip {
+        check_probability(p);
+        RandomHorizontalFlip { p }
+    }
+}
+
+impl<T: Numeric> Transform<T> for RandomHorizontalFlip {
+    fn apply(
+        &self,
+        image: &Array<T>,
+        boxes: &[BoundingBox<T>],
+        rng: &mut Rng,
+    ) -> (Array<T>, Vec<BoundingBox<T>>) {
+        let (_, _, width) = get_image_shape(image);
+        if rng.next_f64() >= self.p {
+            return (image.clone(), boxes.to_vec());
+        }
+        let width = T::from(width).unwrap();
+        let boxes = boxes
+            .iter()
+            .map(|bounding_box| BoundingBox {
+                x_min: width - bounding_box.x_max,
+                x_max: width - bounding_box.x_min,
+                ..*bounding_box
+            })
+            .collect();
+        (flip(image, 2), boxes)
+    }
+}
+
+/// Flips images vertically with a given probability.
+pub struct RandomVerticalFlip {
+    p: f64,
+}
+
+impl RandomVerticalFlip {
+    /// Creates a new `RandomVerticalFlip`.
+    ///
+    /// * `p` - Probability of flipping an image.
+    ///
+    /// **Panics** if `p` isn't in `[0, 1]` interval.
+    pub fn new(p: f64) -> RandomVerticalFlip {
+        check_probability(p);
+        RandomVerticalFlip { p }
+    }
+}
+
+impl<T: Numeric> Transform<T> for RandomVerticalFlip {
+    fn apply(
+        &self,
+        image: &Array<T>,
+        boxes: &[BoundingBox<T>],
+        rng: &mut Rng,
+    ) -> (Array<T>, Vec<BoundingBox<T>>) {
+        let (_, height, _) = get_image_shape(image);
+        if rng.next_f64() >= self.p {
+            return (image.clone(), boxes.to_vec());
+        }
+        let height = T::from(height).unwrap();
+        let boxes = boxes
+            .iter()
+            .map(|bounding_box| BoundingBox {
+                y_min: height - bounding_box.y_max,
+                y_max: height - bounding_box.y_min,
+                ..*bounding_box
+            })
+            .collect();
+        (flip(image, 1), boxes)
+    }
+}
+
+/// Resizes images to a given size.
+pub struct Resize {
+    height: usize,
+    width: usize,
+    interpolation: Interpolation,
+}
+
+impl Resize {
+    /// Creates a new `Resize`.
+    ///
+    /// * `height` - Positive height of output images.
+    /// * `width` - Positive width of output images.
+    /// * `interpolation` - Interpolation method.
+    ///
+    /// **Panics** if `height` or `width` is zero.
+    pub fn new(height: usize, width: usize, interpolation: Interpolation) -> Resize {
+        if height == 0 || width == 0 {
+            panic!("Output size has to be positive. Got: {}x{}", height, width)
+        }
+        Resize {
+            height,
+            width,
+            interpolation,
+        }
+    }
+}
+
+impl<T: Numeric> Transform<T> for Resize {
+    fn apply(
+        &self,
+        image: &Array<T>,
+        boxes: &[BoundingBox<T>],
+        _: &mut Rng,
+    ) -> (Array<T>, Vec<BoundingBox<T>>) {
+        let (channels, height, width) = get_image_shape(image);
+        let output = resize_nchw(
+            &image.reshape(vec![1, channels, height, width]),
+            (self.height, self.width),
+            self.interpolation,
+        )
+        .reshape(vec![channels, self.height, self.width]);
+        let scale_y = T::from(self.height as f64 / height as f64).unwrap();
+        let scale_x = T::from(self.width as f64 / width as f64).unwrap();
+        let boxes = boxes
+            .iter()
+            .map(|bounding_box| {
+                BoundingBox::new(
+                    bounding_box.x_min * scale_x,
+                    bounding_box.y_min * scale_y,
+                    bounding_box.x_max * scale_x,
+                    bounding_box.y_max * scale_y,
+                )
+            })
+            .collect();
+        (output, boxes)
+    }
+}
+
+/// Randomly changes brightness, contrast and saturation of images with values in
+/// `[0, 1]`.
+///
+/// Every property is changed by a factor drawn uniformly from `[1 - strength,
+/// 1 + strength]`, in the above order, and results are clipped to `[0, 1]`. Saturation
+/// is changed only for 3-channel RGB images. Bounding boxes aren't changed.
+pub struct ColorJitter {
+    brightness: f64,
+    contrast: f64,
+    saturation: f64,
+}
+
+impl ColorJitter {
+    /// Creates a new `ColorJitter`.
+    ///
+    /// * `brightness` - Strength of brightness changes in `[0, 1]`.
+    /// * `contrast` - Strength of contrast changes in `[0, 1]`.
+    /// * `saturation` - Strength of saturation changes in `[0, 1]`.
+    ///
+    /// **Panics** if any strength isn't in `[0, 1]` interval.
+    pub fn new(brightness: f64, contrast: f64, saturation: f64) -> ColorJitter {
+        for &strength in [brightness, contrast, saturation].iter() {
+            if !(0. ..=1.).contains(&strength) {
+                panic!(
+                    "Jitter strength has to be in [0, 1] interval. Got: {}",
+                    strength
+                )
+            }
+        }
+        ColorJitter {
+            brightness,
+            contrast,
+            saturation,
+        }
+    }
+}
+
+// Weights of RGB channels in grayscale images (ITU-R BT.601).
+const GRAYSCALE_WEIGHTS: [f64; 3] = [0.299, 0.587, 0.114];
+
+// Returns grayscale pixels of an image, equal to the only channel of 1-channel images.
+fn grayscale(data: &[f64], channels: usize) -> Vec<f64> {
+    let pixels = data.len() / channels;
+    if channels != 3 {
+        return data[..pixels].to_vec();
+    }
+    (0..pixels)
+        .map(|i| {
+            GRAYSCALE_WEIGHTS
+                .iter()
+                .enumerate()
+                .map(|(c, weight)| weight * data[c * pixels + i])
+                .sum()
+        })
+        .collect()
+}
+
+impl<T: Numeric> Transform<T> for ColorJitter {
+    fn apply(
+        &self,
+        image: &Array<T>,
+        boxes: &[BoundingBox<T>],
+        rng: &mut Rng,
+    ) -> (Array<T>, Vec<BoundingBox<T>>) {
+        let (channels, _, _) = get_image_shape(image);
+        let mut factor = |strength: f64| rng.uniform(1. - strength, 1. + strength);
+        let (brightness, contrast, saturation) = (
+            factor(self.brightness),
+            factor(self.contrast),
+            factor(self.saturation),
+        );
+        let clip = |x: f64| x.clamp(0., 1.);
+
+        let mut data: Vec<f64> = image
+            .data
+            .iter()
+            .map(|x| clip(x.to_f64().unwrap() * brightness))
+            .collect();
+        let gray = grayscale(&data, channels);
+        let mean = gray.iter().sum::<f64>() / gray.len() as f64;
+        for x in data.iter_mut() {
+            *x = clip((*x - mean) * contrast + mean);
+        }
+        if channels == 3 {
+            let gray = grayscale(&data, channels);
+            for (i, x) in data.iter_mut().enumerate() {
+                let gray = gray[i % gray.len()];
+                *x = clip((*x - gray) * saturation + gray);
+            }
+        }
+        let output = Array::from_vec(
+            data.into_iter().map(|x| T::from(x).unwrap()).collect(),
+            image.get_shape(),
+        );
+        (output, boxes.to_vec())
+    }
+}
+
+/// Normalizes every channel of images with given mean and standard deviation.
+///
+/// # Examples
+/// ```
+/// use neurust::data::transforms::{Normalize, Transform};
+/// use neurust::linalg::Rng;
+/// use neurust::Array;
+///
+/// let normalize = Normalize::new(vec![0.5, 0.], vec![0.5, 2.]);
+/// let image = Array::from_vec(vec![0., 1., 2., 4.], vec![2, 1, 2]);
+///
+/// assert_eq!(
+///     normalize.apply_image(&image, &mut Rng::new(0)),
+///     Array::from_vec(vec![-1., 1., 1., 2.], vec![2, 1, 2])
+/// );
+/// ```
+pub struct Normalize<T: Numeric> {
+    mean: Vec<T>,
+    std: Vec<T>,
+}
+
+impl<T: Numeric> Normalize<T> {
+    /// Creates a new `Normalize`.
+    ///
+    /// * `mean` - Means of channels.
+    /// * `std` - Positive standard deviations of channels.
+    ///
+    /// **Panics** if lengths of `mean` and `std` differ or `std` isn't positive.
+    pub fn new(mean: Vec<T>, std: Vec<T>) -> Normalize<T> {
+        if mean.len() != std.len() || std.iter().any(|&x| x <= T::zero()) {
+            panic!(
+                "Mean and positive standard deviation have to be given for every channel. Got: {:?}, {:?}",
+                mean, std
+            )
+        }
+        Normalize { mean, std }
+    }
+}
+
+impl<T: Numeric> Transform<T> for Normalize<T> {
+    /// **Panics** if the image has a different number of channels than given means.
+    fn apply(
+        &self,
+        image: &Array<T>,
+        boxes: &[BoundingBox<T>],
+        _: &mut Rng,
+    ) -> (Array<T>, Vec<BoundingBox<T>>) {
+        let (channels, height, width) = get_image_shape(image);
+        if channels != self.mean.len() {
+            panic!(
+                "Image has {} channels, but normalization is defined for {}.",
+                channels,
+                self.mean.len()
+            )
+        }
+        let mut output = image.clone();
+        for (c, channel) in output.data.chunks_mut(height * width).enumerate() {
+            for x in channel.iter_mut() {
+                *x = (*x - self.mean[c]) / self.std[c];
+            }
+        }
+        (output, boxes.to_vec())
+    }
+}
+
+/// Applies a random affine transformation keeping the center of images in place:
+/// rotation, scaling and translation.
+///
+/// Pixels mapped from outside of the input image are filled with a constant value.
+/// Bounding boxes are replaced by the smallest boxes containing their transformed
+/// corners.
+pub struct RandomAffine<T: Numeric> {
+    degrees: f64,
+    translate: (f64, f64),
+    scale: (f64, f64),
+    interpolation: Interpolation,
+    fill: T,
+}
+
+impl<T: Numeric> RandomAffine<T> {
+    /// Creates a new `RandomAffine`.
+    ///
+    /// * `degrees` - Non-negative maximum angle of rotation. Angles are drawn uniformly
+    ///   from `[-degrees, degrees]`, positive ones rotate counter-clockwise.
+    /// * `translate` - Non-negative maximum horizontal and vertical translation as
+    ///   fractions of width and height.
+    /// * `scale` - Positive range of scaling factors.
+    /// * `interpolation` - Interpolation method.
+    /// * `fill` - Value of pixels mapped from outside of input images.
+    ///
+    /// **Panics** if `degrees` or `translate` is negative or `scale` isn't a positive
+    /// range.
+    pub fn new(
+        degrees: f64,
+        translate: (f64, f64),
+        scale: (f64, f64),
+        interpolation: Interpolation,
+        fill: T,
+    ) -> RandomAffine<T> {
+        if degrees < 0. || translate.0 < 0. || translate.1 < 0. {
+            panic!(
+                "Rotation and translation have to be non-negative. Got: {}, {:?}",
+                degrees, translate
+            )
+        }
+        if scale.0 <= 0. || scale.0 > scale.1 {
+            panic!("Scale has to be a positive range. Got: {:?}", scale)
+        }
+        RandomAffine {
+            degrees,
+            translate,
+            scale,
+            interpolation,
+            fill,
+        }
+    }
+}
+
+impl<T: Numeric> Transform<T> for RandomAffine<T> {
+    fn apply(
+        &self,
+        image: &Array<T>,
+        boxes: &[BoundingBox<T>],
+        rng: &mut Rng,
+    ) -> (Array<T>, Vec<BoundingBox<T>>) {
+        let (channels, height, width) = get_image_shape(image);
+        let angle = rng.uniform(-self.degrees, self.degrees).to_radians();
+        let scale = rng.uniform(self.scale.0, self.scale.1);
+        let max_dx = self.translate.0 * width as f64;
+        let max_dy = self.translate.1 * height as f64;
+        let dx = rng.uniform(-max_dx, max_dx);
+        let dy = rng.uniform(-max_dy, max_dy);
+
+        // forward mapping of points around the center, with y axis pointing down
+        let (center_x, center_y) = (width as f64 / 2., height as f64 / 2.);
+        let (cos, sin) = (scale * angle.cos(), scale * angle.sin());
+        let forward = |x: f64, y: f64| {
+            let (x, y) = (x - center_x, y - center_y);
+            (
+                cos * x + sin * y + center_x + dx,
+                -sin * x + cos * y + center_y + dy,
+            )
+        };
+        let inverse = |x: f64, y: f64| {
+            let (x, y) = (x - center_x - dx, y - center_y - dy);
+            let det = cos * cos + sin * sin;
+            (
+                (cos * x - sin * y) / det + center_x,
+                (sin * x + cos * y) / det + center_y,
+            )
+        };
+
+        let pixel = |c: usize, y: isize, x: isize| {
+            if y < 0 || x < 0 || y >= height as isize || x >= width as isize {
+                self.fill
+            } else {
+                image.data[(c * height + y as usize) * width + x as usize]
+            }
+        };
+        let mut data = Vec::with_capacity(image.data.len());
+        for c in 0..channels {
+            for y in 0..height {
+                for x in 0..width {
+                    // pixel centers are at half-integer coordinates
+                    let (source_x, source_y) = inverse(x as f64 + 0.5, y as f64 + 0.5);
+                    let value = match self.interpolation {
+                        Interpolation::Nearest => {
+                            pixel(c, source_y.floor() as isize, source_x.floor() as isize)
+                        }
+                        Interpolation::Bilinear => {
+                            let (source_x, source_y) = (source_x - 0.5, source_y - 0.5);
+                            let (left, top) = (source_x.floor(), source_y.floor());
+                            let weight_x = T::from(source_x - left).unwrap();
+                            let weight_y = T::from(source_y - top).unwrap();
+                            let (left, top) = (left as isize, top as isize);
+                            let top_value = pixel(c, top, left) * (T::one() - weight_x)
+                                + pixel(c, top, left + 1) * weight_x;
+                            let bottom_value = pixel(c, top + 1, left) * (T::one() - weight_x)
+                                + pixel(c, top + 1, left + 1) * weight_x;
+                            top_value * (T::one() - weight_y) + bottom_value * weight_y
+                        }
+                    };
+                    data.push(value);
+                }
+            }
+        }
+        let boxes = boxes
+            .iter()
+            .map(|bounding_box| bounding_box.map_corners(forward).clip(height, width))
+            .collect();
+        (Array::from_vec(data, image.get_shape()), boxes)
+    }
+}
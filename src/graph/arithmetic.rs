@@ -1,6 +1,6 @@
 use crate::graph::GraphOp;
 use crate::linalg::utils::{get_shape_after_broadcast, get_shape_after_broadcast_matmul};
-use crate::linalg::{Array, Numeric};
+use crate::linalg::{reduce_to_shape, Array, Numeric};
 use std::collections::HashMap;
 use std::rc::Rc;
 
@@ -110,25 +110,15 @@ impl<'a, T: Numeric> GraphOp<T> for AddOp<T> {
     impl_trait_op_2_inputs!(AddOp, "AddOp", +);
     fn compute_accumm_grad(
         &self,
-        feed_dict: Option<&HashMap<String, &Array<T>>>,
-        compute_cache: &mut HashMap<usize, Array<T>>,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
         dependant_node: &dyn GraphOp<T>,
         grad: &Array<T>,
     ) -> Option<Array<T>> {
         if dependant_node.ref_as_usize() == self.input_1.ref_as_usize() {
-            Some(
-                grad * &Array::<T>::new(
-                    T::one(),
-                    self.input_1.value(feed_dict, compute_cache).get_shape(),
-                ),
-            )
+            Some(reduce_to_shape(grad, &self.input_1.shape()))
         } else if dependant_node.ref_as_usize() == self.input_2.ref_as_usize() {
-            Some(
-                grad * &Array::<T>::new(
-                    T::one(),
-                    self.input_2.value(feed_dict, compute_cache).get_shape(),
-                ),
-            )
+            Some(reduce_to_shape(grad, &self.input_2.shape()))
         } else {
             None
         }
@@ -146,9 +136,15 @@ impl<'a, T: Numeric> GraphOp<T> for MulOp<T> {
         grad: &Array<T>,
     ) -> Option<Array<T>> {
         if dependant_node.ref_as_usize() == self.input_1.ref_as_usize() {
-            Some(grad * &self.input_2.value(feed_dict, compute_cache))
+            Some(reduce_to_shape(
+                &(grad * &self.input_2.value(feed_dict, compute_cache)),
+                &self.input_1.shape(),
+            ))
         } else if dependant_node.ref_as_usize() == self.input_2.ref_as_usize() {
-            Some(grad * &self.input_1.value(feed_dict, compute_cache))
+            Some(reduce_to_shape(
+                &(grad * &self.input_1.value(feed_dict, compute_cache)),
+                &self.input_2.shape(),
+            ))
         } else {
             None
         }
@@ -160,25 +156,15 @@ impl<'a, T: Numeric> GraphOp<T> for SubOp<T> {
     impl_trait_op_2_inputs!(SubOp, "SubOp", -);
     fn compute_accumm_grad(
         &self,
-        feed_dict: Option<&HashMap<String, &Array<T>>>,
-        compute_cache: &mut HashMap<usize, Array<T>>,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
         dependant_node: &dyn GraphOp<T>,
         grad: &Array<T>,
     ) -> Option<Array<T>> {
         if dependant_node.ref_as_usize() == self.input_1.ref_as_usize() {
-            Some(
-                grad * &Array::<T>::new(
-                    -T::one(),
-                    self.input_2.value(feed_dict, compute_cache).get_shape(),
-                ),
-            )
+            Some(reduce_to_shape(grad, &self.input_1.shape()))
         } else if dependant_node.ref_as_usize() == self.input_2.ref_as_usize() {
-            Some(
-                grad * &Array::<T>::new(
-                    -T::one(),
-                    self.input_1.value(feed_dict, compute_cache).get_shape(),
-                ),
-            )
+            Some(reduce_to_shape(&grad.neg(), &self.input_2.shape()))
         } else {
             None
         }
@@ -196,15 +182,15 @@ impl<'a, T: Numeric> GraphOp<T> for DivOp<T> {
         grad: &Array<T>,
     ) -> Option<Array<T>> {
         if dependant_node.ref_as_usize() == self.input_1.ref_as_usize() {
-            let value1 = self.input_1.value(feed_dict, compute_cache);
             let value2 = self.input_2.value(feed_dict, compute_cache);
-            let ones = Array::<T>::new(T::one(), value1.get_shape());
-            Some(grad * &(&ones / &value2))
+            Some(reduce_to_shape(&(grad / &value2), &self.input_1.shape()))
         } else if dependant_node.ref_as_usize() == self.input_2.ref_as_usize() {
             let value1 = self.input_1.value(feed_dict, compute_cache);
             let value2 = self.input_2.value(feed_dict, compute_cache);
-            let minus_ones = Array::<T>::new(-T::one(), value1.get_shape());
-            Some(grad * &(&minus_ones / &(&value2 * &value2)))
+            Some(reduce_to_shape(
+                &(grad * &(&value1 / &(&value2 * &value2))).neg(),
+                &self.input_2.shape(),
+            ))
         } else {
             None
         }
@@ -239,18 +225,13 @@ impl<'a, T: Numeric> GraphOp<T> for SubScalarOp<T> {
     impl_trait_op_1_input_scalar!(SubScalarOp, "SubScalarOp", -);
     fn compute_accumm_grad(
         &self,
-        feed_dict: Option<&HashMap<String, &Array<T>>>,
-        compute_cache: &mut HashMap<usize, Array<T>>,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
         dependant_node: &dyn GraphOp<T>,
         grad: &Array<T>,
     ) -> Option<Array<T>> {
         if dependant_node.ref_as_usize() == self.input.ref_as_usize() {
-            Some(
-                grad * &Array::<T>::new(
-                    -T::one(),
-                    self.input.value(feed_dict, compute_cache).get_shape(),
-                ),
-            )
+            Some(grad.clone())
         } else {
             None
         }
@@ -338,14 +319,19 @@ impl<'a, T: Numeric> GraphOp<T> for MatMulOp<T> {
         grad: &Array<T>,
     ) -> Option<Array<T>> {
         if dependant_node.ref_as_usize() == self.input_1.ref_as_usize() {
-            Some(grad.matmul(&self.input_2.value(feed_dict, compute_cache).transpose()))
+            Some(reduce_to_shape(
+                &grad.matmul(&self.input_2.value(feed_dict, compute_cache).transpose()),
+                &self.input_1.shape(),
+            ))
         } else if dependant_node.ref_as_usize() == self.input_2.ref_as_usize() {
-            Some(
-                self.input_1
+            Some(reduce_to_shape(
+                &self
+                    .input_1
                     .value(feed_dict, compute_cache)
                     .transpose()
                     .matmul(&grad),
-            )
+                &self.input_2.shape(),
+            ))
         } else {
             None
         }
@@ -0,0 +1,218 @@
+use crate::graph::GraphOp;
+use crate::linalg::{
+    check_conv2d_shapes, conv2d_backward_input, conv2d_backward_kernel, conv2d_nchw,
+    conv2d_transpose_nchw, get_conv2d_transpose_shape, Conv2dParams, DataFormat,
+};
+use crate::linalg::{Array, Numeric};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+pub(crate) struct Conv2dOp<T: Numeric> {
+    input: Rc<dyn GraphOp<T>>,
+    kernel: Rc<dyn GraphOp<T>>,
+    params: Conv2dParams,
+    data_format: DataFormat,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> Conv2dOp<T> {
+    pub fn new(
+        input: Rc<dyn GraphOp<T>>,
+        kernel: Rc<dyn GraphOp<T>>,
+        params: Conv2dParams,
+        data_format: DataFormat,
+    ) -> Conv2dOp<T> {
+        let input_shape = input.shape();
+        if input_shape.len() != 4 {
+            panic!(
+                "Convolution requires 4-dimensional input. Got shape: {:?}",
+                input_shape
+            )
+        }
+        let input_shape = data_format.shape_to_nchw(&input_shape);
+        let kernel_shape = kernel.shape();
+        check_conv2d_shapes(&input_shape, &kernel_shape, &params);
+        let (out_h, out_w) = params.output_size(
+            (input_shape[2], input_shape[3]),
+            (kernel_shape[2], kernel_shape[3]),
+        );
+        let shape = data_format.shape_from_nchw(&[input_shape[0], kernel_shape[0], out_h, out_w]);
+        Conv2dOp {
+            input,
+            kernel,
+            params,
+            data_format,
+            shape,
+        }
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for Conv2dOp<T> {
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        let input = self
+            .data_format
+            .array_to_nchw(&self.input.value(feed_dict, cache));
+        self.data_format.array_from_nchw(&conv2d_nchw(
+            &input,
+            &self.kernel.value(feed_dict, cache),
+            &self.params,
+        ))
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        compute_cache: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        let grad = self.data_format.array_to_nchw(grad);
+        if dependant_node.ref_as_usize() == self.input.ref_as_usize() {
+            let input_shape = self.data_format.shape_to_nchw(&self.input.shape());
+            Some(self.data_format.array_from_nchw(&conv2d_backward_input(
+                &grad,
+                &self.kernel.value(feed_dict, compute_cache),
+                &input_shape,
+                &self.params,
+            )))
+        } else if dependant_node.ref_as_usize() == self.kernel.ref_as_usize() {
+            let input = self
+                .data_format
+                .array_to_nchw(&self.input.value(feed_dict, compute_cache));
+            Some(conv2d_backward_kernel(
+                &grad,
+                &input,
+                &self.kernel.shape(),
+                &self.params,
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn get_name(&self) -> &str {
+        "Conv2dOp"
+    }
+
+    fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+        Some(vec![Rc::clone(&self.input), Rc::clone(&self.kernel)])
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
+
+pub(crate) struct Conv2dTransposeOp<T: Numeric> {
+    input: Rc<dyn GraphOp<T>>,
+    kernel: Rc<dyn GraphOp<T>>,
+    params: Conv2dParams,
+    output_padding: (usize, usize),
+    data_format: DataFormat,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> Conv2dTransposeOp<T> {
+    pub fn new(
+        input: Rc<dyn GraphOp<T>>,
+        kernel: Rc<dyn GraphOp<T>>,
+        params: Conv2dParams,
+        output_padding: (usize, usize),
+        data_format: DataFormat,
+    ) -> Conv2dTransposeOp<T> {
+        let input_shape = input.shape();
+        if input_shape.len() != 4 {
+            panic!(
+                "Transposed convolution requires 4-dimensional input. Got shape: {:?}",
+                input_shape
+            )
+        }
+        let shape = data_format.shape_from_nchw(&get_conv2d_transpose_shape(
+            &data_format.shape_to_nchw(&input_shape),
+            &kernel.shape(),
+            &params,
+            output_padding,
+        ));
+        Conv2dTransposeOp {
+            input,
+            kernel,
+            params,
+            output_padding,
+            data_format,
+            shape,
+        }
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for Conv2dTransposeOp<T> {
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        let input = self
+            .data_format
+            .array_to_nchw(&self.input.value(feed_dict, cache));
+        self.data_format.array_from_nchw(&conv2d_transpose_nchw(
+            &input,
+            &self.kernel.value(feed_dict, cache),
+            &self.params,
+            self.output_padding,
+        ))
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        compute_cache: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        // transposed convolution's gradients are regular convolution and its kernel gradient
+        // with roles of input and output swapped
+        let grad = self.data_format.array_to_nchw(grad);
+        if dependant_node.ref_as_usize() == self.input.ref_as_usize() {
+            Some(self.data_format.array_from_nchw(&conv2d_nchw(
+                &grad,
+                &self.kernel.value(feed_dict, compute_cache),
+                &self.params,
+            )))
+        } else if dependant_node.ref_as_usize() == self.kernel.ref_as_usize() {
+            let input = self
+                .data_format
+                .array_to_nchw(&self.input.value(feed_dict, compute_cache));
+            Some(conv2d_backward_kernel(
+                &input,
+                &grad,
+                &self.kernel.shape(),
+                &self.params,
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn get_name(&self) -> &str {
+        "Conv2dTransposeOp"
+    }
+
+    fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+        Some(vec![Rc::clone(&self.input), Rc::clone(&self.kernel)])
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
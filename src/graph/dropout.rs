@@ -0,0 +1,74 @@
+use crate::graph::GraphOp;
+use crate::linalg::{Array, Numeric, Rng};
+use crate::tensor::TrainingFlag;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+// Scaled random mask of dropout.
+//
+// In training mode every computation of the node samples a new mask with zeros
+// (with probability `rate`) and ones scaled by `1 / (1 - rate)`. The mask is stored
+// in the compute cache like any other value, so it is shared by all consumers of
+// a single computation of the graph. In inference mode the mask is filled with ones.
+pub(crate) struct DropoutMaskOp<T: Numeric> {
+    rng: RefCell<Rng>,
+    rate: T,
+    training: TrainingFlag,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> DropoutMaskOp<T> {
+    pub fn new(shape: Vec<usize>, rate: T, training: TrainingFlag, rng: Rng) -> DropoutMaskOp<T> {
+        if rate < T::zero() || rate >= T::one() {
+            panic!("Dropout rate must be in range [0, 1). Got: {}", rate)
+        }
+        DropoutMaskOp {
+            rng: RefCell::new(rng),
+            rate,
+            training,
+            shape,
+        }
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for DropoutMaskOp<T> {
+    fn compute(
+        &self,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        if !self.training.is_training() || self.rate == T::zero() {
+            return Array::new(T::one(), self.shape.clone());
+        }
+        let keep_probability = T::one() - self.rate;
+        let mut mask = Array::random_bernoulli(
+            self.shape.clone(),
+            keep_probability,
+            &mut self.rng.borrow_mut(),
+        );
+        mask.map_assign(|x| x / keep_probability);
+        mask
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
+        _: &dyn GraphOp<T>,
+        _: &Array<T>,
+    ) -> Option<Array<T>> {
+        None
+    }
+
+    fn get_name(&self) -> &str {
+        "DropoutMaskOp"
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
@@ -0,0 +1,81 @@
+use crate::graph::GraphOp;
+use crate::linalg::{gather_rows, scatter_add_rows, Array, Numeric};
+use crate::tensor::Indices;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// Lookup of rows of a `[vocab, dim]` table given integer indices.
+pub(crate) struct EmbeddingOp<T: Numeric> {
+    table: Rc<dyn GraphOp<T>>,
+    indices: Indices,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> EmbeddingOp<T> {
+    pub fn new(table: Rc<dyn GraphOp<T>>, indices: Indices) -> EmbeddingOp<T> {
+        let table_shape = table.shape();
+        if table_shape.len() != 2 {
+            panic!(
+                "Embedding table must be 2-dimensional. Got shape: {:?}",
+                table_shape
+            )
+        }
+        let mut shape = indices.shape();
+        shape.push(table_shape[1]);
+        EmbeddingOp {
+            table,
+            indices,
+            shape,
+        }
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for EmbeddingOp<T> {
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        let mut output = gather_rows(
+            &self.table.value(feed_dict, cache),
+            &self.indices.get_data(),
+        );
+        output.shape = self.shape.clone();
+        output
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        if dependant_node.ref_as_usize() != self.table.ref_as_usize() {
+            return None;
+        }
+        let mut table_grad = Array::new(T::zero(), self.table.shape());
+        scatter_add_rows(&mut table_grad, &self.indices.get_data(), grad);
+        Some(table_grad)
+    }
+
+    fn get_name(&self) -> &str {
+        "EmbeddingOp"
+    }
+
+    fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+        Some(vec![Rc::clone(&self.table)])
+    }
+
+    fn sparse_rows(&self) -> Option<Vec<usize>> {
+        Some(self.indices.get_data())
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
@@ -0,0 +1,154 @@
+use crate::graph::GraphOp;
+use crate::linalg::{
+    check_indices, get_gather_data_indices, index_select_backward, scatter_add_data, Array, Numeric,
+};
+use crate::tensor::Indices;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// Gathers values of the input along an axis (see `Array::gather()`).
+pub(crate) struct GatherOp<T: Numeric> {
+    input: Rc<dyn GraphOp<T>>,
+    axis: usize,
+    indices: Indices,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> GatherOp<T> {
+    pub fn new(input: Rc<dyn GraphOp<T>>, axis: usize, indices: Indices) -> GatherOp<T> {
+        let shape = indices.shape();
+        get_gather_data_indices(&input.shape(), axis, &indices.get_data(), &shape);
+        GatherOp {
+            input,
+            axis,
+            indices,
+            shape,
+        }
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for GatherOp<T> {
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        self.input
+            .value(feed_dict, cache)
+            .gather(self.axis, &self.indices.get_data(), &self.shape)
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        if dependant_node.ref_as_usize() != self.input.ref_as_usize() {
+            return None;
+        }
+        let input_shape = self.input.shape();
+        let data_indices = get_gather_data_indices(
+            &input_shape,
+            self.axis,
+            &self.indices.get_data(),
+            &self.shape,
+        );
+        let mut input_grad = Array::new(T::zero(), input_shape);
+        scatter_add_data(&mut input_grad, &data_indices, &grad.data);
+        Some(input_grad)
+    }
+
+    fn get_name(&self) -> &str {
+        "GatherOp"
+    }
+
+    fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+        Some(vec![Rc::clone(&self.input)])
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
+
+// Selects sub-arrays of the input along an axis (see `Array::index_select()`).
+pub(crate) struct IndexSelectOp<T: Numeric> {
+    input: Rc<dyn GraphOp<T>>,
+    axis: usize,
+    indices: Indices,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> IndexSelectOp<T> {
+    pub fn new(input: Rc<dyn GraphOp<T>>, axis: usize, indices: Indices) -> IndexSelectOp<T> {
+        let mut shape = input.shape();
+        if axis >= shape.len() || indices.shape().len() != 1 {
+            panic!(
+                "Invalid axis {} or indices of shape {:?} for input of shape {:?}.",
+                axis,
+                indices.shape(),
+                shape
+            )
+        }
+        check_indices(&indices.get_data(), shape[axis]);
+        shape[axis] = indices.shape()[0];
+        IndexSelectOp {
+            input,
+            axis,
+            indices,
+            shape,
+        }
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for IndexSelectOp<T> {
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        self.input
+            .value(feed_dict, cache)
+            .index_select(self.axis, &self.indices.get_data())
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        if dependant_node.ref_as_usize() != self.input.ref_as_usize() {
+            return None;
+        }
+        Some(index_select_backward(
+            grad,
+            &self.input.shape(),
+            self.axis,
+            &self.indices.get_data(),
+        ))
+    }
+
+    fn get_name(&self) -> &str {
+        "IndexSelectOp"
+    }
+
+    fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+        Some(vec![Rc::clone(&self.input)])
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
@@ -81,6 +81,16 @@ fn relu_derivative<T: Numeric>(x: T) -> T {
     }
 }
 
+fn abs_derivative<T: Numeric>(x: T) -> T {
+    if x > T::zero() {
+        T::one()
+    } else if x < T::zero() {
+        -T::one()
+    } else {
+        T::zero()
+    }
+}
+
 impl_map_op!(SinOp, "SinOp", |x| x.sin(), |x| x.cos());
 impl_map_op!(CosOp, "CosOp", |x| x.cos(), |x| -x.sin());
 impl_map_op!(LnOp, "LnOp", |x| x.ln(), |x| T::one() / x);
@@ -88,6 +98,7 @@ impl_map_op!(TanhOp, "TanhOp", |x| x.tanh(), |x| T::one()
     - (x.tanh().powi(2)));
 impl_map_op!(ReLUOp, "ReLUOp", |x| x.max(T::zero()), relu_derivative);
 impl_map_op!(SigmoidOp, "SigmoidOp", sigmoid, sigmoid_derivative);
+impl_map_op!(AbsOp, "AbsOp", |x| x.abs(), abs_derivative);
 
 // Defines `GraphOp` for operators that applies some parametrized function to all
 // elements of the input array.
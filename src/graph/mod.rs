@@ -1,11 +1,21 @@
 pub(crate) mod arithmetic;
+pub(crate) mod conv;
+pub(crate) mod dropout;
+pub(crate) mod embedding;
+pub(crate) mod indexing;
 pub(crate) mod math;
+pub(crate) mod normalization;
+pub(crate) mod pad;
+pub(crate) mod pool;
 pub(crate) mod reduce;
+pub(crate) mod shape;
+pub(crate) mod softmax;
+pub(crate) mod upsample;
 
 use crate::linalg::{Array, Numeric};
 use std::any::{type_name, Any};
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::rc::Rc;
 
@@ -15,6 +25,43 @@ fn check_tensor_shape_non_empty(shape: &[usize]) {
     }
 }
 
+// Returns nodes reachable from `roots` which depend on `node` (including `node` itself)
+// in topological order, that is every node is placed after all of its inputs.
+fn get_dependant_nodes<T: Numeric>(
+    roots: &[Rc<dyn GraphOp<T>>],
+    node: &dyn GraphOp<T>,
+) -> Vec<Rc<dyn GraphOp<T>>> {
+    fn visit<T: Numeric>(
+        current_node: &Rc<dyn GraphOp<T>>,
+        node_key: usize,
+        is_dependant: &mut HashMap<usize, bool>,
+        order: &mut Vec<Rc<dyn GraphOp<T>>>,
+    ) -> bool {
+        let key = current_node.ref_as_usize();
+        if let Some(&dependant) = is_dependant.get(&key) {
+            return dependant;
+        }
+        let mut dependant = key == node_key;
+        if !dependant {
+            for child in current_node.get_inputs().unwrap_or_default() {
+                dependant |= visit(&child, node_key, is_dependant, order);
+            }
+        }
+        is_dependant.insert(key, dependant);
+        if dependant {
+            order.push(Rc::clone(current_node));
+        }
+        dependant
+    }
+
+    let mut is_dependant = HashMap::new();
+    let mut order = Vec::new();
+    for root in roots {
+        visit(root, node.ref_as_usize(), &mut is_dependant, &mut order);
+    }
+    order
+}
+
 // Computational graph's node.
 // TODO: Store shapes in structs.
 pub(crate) trait GraphOp<T: Numeric> {
@@ -46,6 +93,31 @@ pub(crate) trait GraphOp<T: Numeric> {
         None
     }
 
+    // Returns indices of rows of the first input gathered by the node, if the node
+    // is a row lookup (e.g. embedding). Gradients w.r.t. the first input of such nodes
+    // are non-zero only in these rows.
+    fn sparse_rows(&self) -> Option<Vec<usize>> {
+        None
+    }
+
+    // Applies changes of the node's state recorded during its last computation,
+    // e.g. running statistics of batch normalization.
+    fn update_state(&self) {}
+
+    // Calls `update_state()` once for every node of the graph.
+    fn update_graph_state(&self) {
+        let mut visited = HashSet::new();
+        visited.insert(self.ref_as_usize());
+        self.update_state();
+        let mut stack = self.get_inputs().unwrap_or_default();
+        while let Some(current_node) = stack.pop() {
+            if visited.insert(current_node.ref_as_usize()) {
+                current_node.update_state();
+                stack.extend(current_node.get_inputs().unwrap_or_default());
+            }
+        }
+    }
+
     // Returns computed value of the node.
     // This either fetches the value from `compute_cache` or computes it via `compute()`.
     fn value(
@@ -82,13 +154,22 @@ pub(crate) trait GraphOp<T: Numeric> {
         feed_dict: Option<&HashMap<String, &Array<T>>>,
     ) -> Option<Array<T>> {
         let mut compute_cache = HashMap::<usize, Array<T>>::new();
+        self.grad_with_cache(node, feed_dict, &mut compute_cache)
+    }
+
+    // Computes gradient of the node (`self`) w.r.t. operation (variable) `node` reusing
+    // values stored in `compute_cache`, so that all of them come from the same computation
+    // of the graph.
+    fn grad_with_cache(
+        &self,
+        node: &dyn GraphOp<T>,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        compute_cache: &mut HashMap<usize, Array<T>>,
+    ) -> Option<Array<T>> {
         let mut accumm_grad_map = HashMap::<usize, Array<T>>::new();
-        let mut stack = Vec::<(Rc<dyn GraphOp<T>>, Rc<dyn GraphOp<T>>)>::new();
 
-        let accumm_grad_self = Array::<T>::new(
-            T::one(),
-            self.compute(feed_dict, &mut compute_cache).get_shape(),
-        );
+        let accumm_grad_self =
+            Array::<T>::new(T::one(), self.value(feed_dict, compute_cache).get_shape());
         if self.ref_as_usize() == node.ref_as_usize() {
             return Some(accumm_grad_self);
         }
@@ -96,29 +177,49 @@ pub(crate) trait GraphOp<T: Numeric> {
         let phantom_parent: Rc<dyn GraphOp<T>> = Rc::new(WrapperOp::<T>::new(self.as_trait()));
         accumm_grad_map.insert(phantom_parent.ref_as_usize(), accumm_grad_self);
 
+        // Every node passes its gradient to inputs only after gradients from all nodes
+        // using it have been accumulated, which is guaranteed by a reversed topological
+        // order. Only nodes depending on `node` are visited.
         let children = self.get_inputs().unwrap_or_default();
-        for child in children {
-            stack.push((Rc::clone(&child), Rc::clone(&phantom_parent)))
-        }
-
-        while let Some((current_node, current_parrent)) = stack.pop() {
-            let parrent_grad = current_parrent.compute_accumm_grad(
-                feed_dict,
-                &mut compute_cache,
-                current_node.as_ref(),
-                &accumm_grad_map[&current_parrent.ref_as_usize()],
-            );
-            if let Some(grad) = parrent_grad {
-                if let Some(accumm_grad) = accumm_grad_map.get_mut(&current_node.ref_as_usize()) {
-                    *accumm_grad += &grad;
-                } else {
-                    accumm_grad_map.insert(current_node.ref_as_usize(), grad);
+        let mut dependant_nodes = get_dependant_nodes(&children, node);
+        let dependant_keys: HashSet<usize> = dependant_nodes
+            .iter()
+            .map(|dependant_node| dependant_node.ref_as_usize())
+            .collect();
+        dependant_nodes.reverse();
+        let parents = std::iter::once((Rc::clone(&phantom_parent), children)).chain(
+            dependant_nodes
+                .into_iter()
+                .filter(|current_node| current_node.ref_as_usize() != node.ref_as_usize())
+                .map(|current_node| {
+                    let children = current_node.get_inputs().unwrap_or_default();
+                    (current_node, children)
+                }),
+        );
+        for (current_parrent, children) in parents {
+            let Some(parrent_accumm_grad) = accumm_grad_map
+                .get(&current_parrent.ref_as_usize())
+                .cloned()
+            else {
+                continue;
+            };
+            for child in children {
+                let child_key = child.ref_as_usize();
+                if !dependant_keys.contains(&child_key) {
+                    continue;
                 }
-            }
-            let children = current_node.get_inputs().unwrap_or_default();
-            if current_node.ref_as_usize() != node.ref_as_usize() && !children.is_empty() {
-                for child in children {
-                    stack.push((Rc::clone(&child), Rc::clone(&current_node)))
+                let parrent_grad = current_parrent.compute_accumm_grad(
+                    feed_dict,
+                    compute_cache,
+                    child.as_ref(),
+                    &parrent_accumm_grad,
+                );
+                if let Some(grad) = parrent_grad {
+                    if let Some(accumm_grad) = accumm_grad_map.get_mut(&child_key) {
+                        *accumm_grad += &grad;
+                    } else {
+                        accumm_grad_map.insert(child_key, grad);
+                    }
                 }
             }
         }
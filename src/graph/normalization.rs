@@ -0,0 +1,312 @@
+use crate::graph::GraphOp;
+use crate::linalg::{
+    batch_norm_backward, batch_norm_forward, channel_moments, check_channel_parameter_shape,
+    check_normalization_parameter_shape, normalization_backward, normalization_forward,
+    ChannelLayout, NormalizationLayout,
+};
+use crate::linalg::{Array, Numeric};
+use crate::tensor::TrainingFlag;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// Batch normalization with running statistics.
+//
+// In training mode the input is normalized with statistics of the current batch, which
+// are also recorded and moved into running statistics by `update_state()`, so running
+// statistics change once per training step regardless of how many times the node is
+// computed. In inference mode the running statistics are used instead and statistics
+// recorded by earlier computations are discarded.
+pub(crate) struct BatchNormOp<T: Numeric> {
+    input: Rc<dyn GraphOp<T>>,
+    gamma: Rc<dyn GraphOp<T>>,
+    beta: Rc<dyn GraphOp<T>>,
+    running_mean: Rc<RefCell<Array<T>>>,
+    running_variance: Rc<RefCell<Array<T>>>,
+    batch_statistics: RefCell<Option<(Vec<T>, Vec<T>)>>,
+    training: TrainingFlag,
+    axis: usize,
+    momentum: T,
+    epsilon: T,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> BatchNormOp<T> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        input: Rc<dyn GraphOp<T>>,
+        gamma: Rc<dyn GraphOp<T>>,
+        beta: Rc<dyn GraphOp<T>>,
+        running_mean: Rc<RefCell<Array<T>>>,
+        running_variance: Rc<RefCell<Array<T>>>,
+        training: TrainingFlag,
+        axis: usize,
+        momentum: T,
+        epsilon: T,
+    ) -> BatchNormOp<T> {
+        let shape = input.shape();
+        let channels = ChannelLayout::new(&shape, axis).channels;
+        check_channel_parameter_shape(&gamma.shape(), channels, "Gamma");
+        check_channel_parameter_shape(&beta.shape(), channels, "Beta");
+        check_channel_parameter_shape(&running_mean.borrow().get_shape(), channels, "Running mean");
+        check_channel_parameter_shape(
+            &running_variance.borrow().get_shape(),
+            channels,
+            "Running variance",
+        );
+        if momentum < T::zero() || momentum > T::one() {
+            panic!("Momentum must be in range [0, 1]. Got: {}", momentum)
+        }
+        BatchNormOp {
+            input,
+            gamma,
+            beta,
+            running_mean,
+            running_variance,
+            batch_statistics: RefCell::new(None),
+            training,
+            axis,
+            momentum,
+            epsilon,
+            shape,
+        }
+    }
+
+    // Returns statistics used to normalize given input.
+    fn statistics(&self, input: &Array<T>) -> (Vec<T>, Vec<T>) {
+        if self.training.is_training() {
+            channel_moments(input, &ChannelLayout::new(&self.shape, self.axis))
+        } else {
+            (
+                self.running_mean.borrow().data.clone(),
+                self.running_variance.borrow().data.clone(),
+            )
+        }
+    }
+
+    // Moves running statistics towards statistics of the current batch.
+    fn update_running_statistics(&self, mean: &[T], variance: &[T]) {
+        for (running, batch) in [
+            (&self.running_mean, mean),
+            (&self.running_variance, variance),
+        ] {
+            for (x, &y) in running.borrow_mut().data.iter_mut().zip(batch.iter()) {
+                *x = self.momentum * *x + (T::one() - self.momentum) * y;
+            }
+        }
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for BatchNormOp<T> {
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        let input = self.input.value(feed_dict, cache);
+        let (mean, variance) = self.statistics(&input);
+        *self.batch_statistics.borrow_mut() = if self.training.is_training() {
+            Some((mean.clone(), variance.clone()))
+        } else {
+            None
+        };
+        batch_norm_forward(
+            &input,
+            &self.gamma.value(feed_dict, cache),
+            &self.beta.value(feed_dict, cache),
+            &mean,
+            &variance,
+            &ChannelLayout::new(&self.shape, self.axis),
+            self.epsilon,
+        )
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        compute_cache: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        let key = dependant_node.ref_as_usize();
+        if key != self.input.ref_as_usize()
+            && key != self.gamma.ref_as_usize()
+            && key != self.beta.ref_as_usize()
+        {
+            return None;
+        }
+        let input = self.input.value(feed_dict, compute_cache);
+        let (mean, variance) = self.statistics(&input);
+        let (grad_input, grad_gamma, grad_beta) = batch_norm_backward(
+            grad,
+            &input,
+            &self.gamma.value(feed_dict, compute_cache),
+            &mean,
+            &variance,
+            &ChannelLayout::new(&self.shape, self.axis),
+            self.epsilon,
+            self.training.is_training(),
+        );
+        if key == self.input.ref_as_usize() {
+            Some(grad_input)
+        } else if key == self.gamma.ref_as_usize() {
+            Some(grad_gamma)
+        } else {
+            Some(grad_beta)
+        }
+    }
+
+    fn update_state(&self) {
+        if let Some((mean, variance)) = self.batch_statistics.borrow_mut().take() {
+            self.update_running_statistics(&mean, &variance);
+        }
+    }
+
+    fn get_name(&self) -> &str {
+        "BatchNormOp"
+    }
+
+    fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+        Some(vec![
+            Rc::clone(&self.input),
+            Rc::clone(&self.gamma),
+            Rc::clone(&self.beta),
+        ])
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
+
+// Normalization with statistics computed per input element group, e.g. layer
+// or group normalization, followed by an affine transformation.
+pub(crate) struct NormalizationOp<T: Numeric> {
+    input: Rc<dyn GraphOp<T>>,
+    gamma: Rc<dyn GraphOp<T>>,
+    beta: Rc<dyn GraphOp<T>>,
+    layout: NormalizationLayout,
+    epsilon: T,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> NormalizationOp<T> {
+    fn new(
+        input: Rc<dyn GraphOp<T>>,
+        gamma: Rc<dyn GraphOp<T>>,
+        beta: Rc<dyn GraphOp<T>>,
+        layout: NormalizationLayout,
+        param_shape: &[usize],
+        epsilon: T,
+    ) -> NormalizationOp<T> {
+        check_normalization_parameter_shape(&gamma.shape(), param_shape, "Gamma");
+        check_normalization_parameter_shape(&beta.shape(), param_shape, "Beta");
+        let shape = input.shape();
+        NormalizationOp {
+            input,
+            gamma,
+            beta,
+            layout,
+            epsilon,
+            shape,
+        }
+    }
+
+    pub fn new_layer_norm(
+        input: Rc<dyn GraphOp<T>>,
+        gamma: Rc<dyn GraphOp<T>>,
+        beta: Rc<dyn GraphOp<T>>,
+        axes: &[usize],
+        epsilon: T,
+    ) -> NormalizationOp<T> {
+        let shape = input.shape();
+        let layout = NormalizationLayout::layer_norm(&shape, axes);
+        let mut sorted_axes = axes.to_vec();
+        sorted_axes.sort_unstable();
+        let param_shape: Vec<usize> = sorted_axes.iter().map(|&axis| shape[axis]).collect();
+        NormalizationOp::new(input, gamma, beta, layout, &param_shape, epsilon)
+    }
+
+    pub fn new_group_norm(
+        input: Rc<dyn GraphOp<T>>,
+        gamma: Rc<dyn GraphOp<T>>,
+        beta: Rc<dyn GraphOp<T>>,
+        num_groups: usize,
+        axis: usize,
+        epsilon: T,
+    ) -> NormalizationOp<T> {
+        let shape = input.shape();
+        let layout = NormalizationLayout::group_norm(&shape, num_groups, axis);
+        NormalizationOp::new(input, gamma, beta, layout, &[shape[axis]], epsilon)
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for NormalizationOp<T> {
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        normalization_forward(
+            &self.input.value(feed_dict, cache),
+            &self.gamma.value(feed_dict, cache),
+            &self.beta.value(feed_dict, cache),
+            &self.layout,
+            self.epsilon,
+        )
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        compute_cache: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        let key = dependant_node.ref_as_usize();
+        if key != self.input.ref_as_usize()
+            && key != self.gamma.ref_as_usize()
+            && key != self.beta.ref_as_usize()
+        {
+            return None;
+        }
+        let (grad_input, grad_gamma, grad_beta) = normalization_backward(
+            grad,
+            &self.input.value(feed_dict, compute_cache),
+            &self.gamma.value(feed_dict, compute_cache),
+            &self.layout,
+            self.epsilon,
+        );
+        if key == self.input.ref_as_usize() {
+            Some(grad_input)
+        } else if key == self.gamma.ref_as_usize() {
+            Some(grad_gamma)
+        } else {
+            Some(grad_beta)
+        }
+    }
+
+    fn get_name(&self) -> &str {
+        "NormalizationOp"
+    }
+
+    fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+        Some(vec![
+            Rc::clone(&self.input),
+            Rc::clone(&self.gamma),
+            Rc::clone(&self.beta),
+        ])
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
@@ -0,0 +1,79 @@
+use crate::graph::GraphOp;
+use crate::linalg::{check_pad_widths, get_padded_shape, pad, pad_backward, PadMode};
+use crate::linalg::{Array, Numeric};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+pub(crate) struct PadOp<T: Numeric> {
+    input: Rc<dyn GraphOp<T>>,
+    pad_widths: Vec<(usize, usize)>,
+    mode: PadMode<T>,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> PadOp<T> {
+    pub fn new(
+        input: Rc<dyn GraphOp<T>>,
+        pad_widths: Vec<(usize, usize)>,
+        mode: PadMode<T>,
+    ) -> PadOp<T> {
+        let input_shape = input.shape();
+        check_pad_widths(&input_shape, &pad_widths, mode);
+        let shape = get_padded_shape(&input_shape, &pad_widths);
+        PadOp {
+            input,
+            pad_widths,
+            mode,
+            shape,
+        }
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for PadOp<T> {
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        pad(
+            &self.input.value(feed_dict, cache),
+            &self.pad_widths,
+            self.mode,
+        )
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        if dependant_node.ref_as_usize() == self.input.ref_as_usize() {
+            Some(pad_backward(
+                grad,
+                &self.input.shape(),
+                &self.pad_widths,
+                self.mode,
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn get_name(&self) -> &str {
+        "PadOp"
+    }
+
+    fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+        Some(vec![Rc::clone(&self.input)])
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
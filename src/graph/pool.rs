@@ -0,0 +1,147 @@
+use crate::graph::GraphOp;
+use crate::linalg::{
+    avg_pool2d_nchw, avg_pool2d_nchw_backward, check_pool_input, get_pool2d_shape, max_pool2d_nchw,
+    max_pool2d_nchw_backward, DataFormat, Pool2dParams,
+};
+use crate::linalg::{Array, Numeric};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// Implements `GraphOp` struct for pooling operations.
+macro_rules! impl_struct_pool_op {
+    ($op_name:ident) => {
+        pub(crate) struct $op_name<T: Numeric> {
+            input: Rc<dyn GraphOp<T>>,
+            params: Pool2dParams,
+            data_format: DataFormat,
+            shape: Vec<usize>,
+        }
+
+        impl<T: Numeric> $op_name<T> {
+            pub fn new(
+                input: Rc<dyn GraphOp<T>>,
+                params: Pool2dParams,
+                data_format: DataFormat,
+            ) -> $op_name<T> {
+                let input_shape = input.shape();
+                check_pool_input(&input_shape);
+                let shape = data_format.shape_from_nchw(&get_pool2d_shape(
+                    &data_format.shape_to_nchw(&input_shape),
+                    &params,
+                ));
+                $op_name {
+                    input,
+                    params,
+                    data_format,
+                    shape,
+                }
+            }
+
+            // Creates an operator pooling over whole spatial dimensions.
+            pub fn new_global(input: Rc<dyn GraphOp<T>>, data_format: DataFormat) -> $op_name<T> {
+                let input_shape = input.shape();
+                check_pool_input(&input_shape);
+                let input_shape = data_format.shape_to_nchw(&input_shape);
+                let params = Pool2dParams::global((input_shape[2], input_shape[3]));
+                $op_name::new(input, params, data_format)
+            }
+        }
+    };
+}
+
+// Implements `GraphOp` trait methods for pooling operations except `compute`
+// and `compute_acumm_gradient`.
+macro_rules! impl_trait_pool_op {
+    ($op_name_str:expr) => {
+        fn get_name(&self) -> &str {
+            $op_name_str
+        }
+
+        fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+            Some(vec![Rc::clone(&self.input)])
+        }
+
+        fn as_trait(&self) -> &dyn GraphOp<T> {
+            self as &dyn GraphOp<T>
+        }
+
+        fn shape(&self) -> Vec<usize> {
+            self.shape.clone()
+        }
+    };
+}
+
+impl_struct_pool_op!(MaxPool2dOp);
+impl<T: Numeric> GraphOp<T> for MaxPool2dOp<T> {
+    impl_trait_pool_op!("MaxPool2dOp");
+
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        let input = self
+            .data_format
+            .array_to_nchw(&self.input.value(feed_dict, cache));
+        self.data_format
+            .array_from_nchw(&max_pool2d_nchw(&input, &self.params).0)
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        compute_cache: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        if dependant_node.ref_as_usize() == self.input.ref_as_usize() {
+            let input = self
+                .data_format
+                .array_to_nchw(&self.input.value(feed_dict, compute_cache));
+            let (_, argmax) = max_pool2d_nchw(&input, &self.params);
+            Some(self.data_format.array_from_nchw(&max_pool2d_nchw_backward(
+                &self.data_format.array_to_nchw(grad),
+                &argmax,
+                &input.get_shape(),
+            )))
+        } else {
+            None
+        }
+    }
+}
+
+impl_struct_pool_op!(AvgPool2dOp);
+impl<T: Numeric> GraphOp<T> for AvgPool2dOp<T> {
+    impl_trait_pool_op!("AvgPool2dOp");
+
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        let input = self
+            .data_format
+            .array_to_nchw(&self.input.value(feed_dict, cache));
+        self.data_format
+            .array_from_nchw(&avg_pool2d_nchw(&input, &self.params))
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        if dependant_node.ref_as_usize() == self.input.ref_as_usize() {
+            let input_shape = self.data_format.shape_to_nchw(&self.input.shape());
+            Some(self.data_format.array_from_nchw(&avg_pool2d_nchw_backward(
+                &self.data_format.array_to_nchw(grad),
+                &input_shape,
+                &self.params,
+            )))
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use crate::graph::GraphOp;
+use crate::linalg::{
+    check_stack_shapes, get_selected_shape, get_stacked_shape, index_select_backward, stack, Array,
+    Numeric,
+};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// Changes shape of the input keeping its data.
+pub(crate) struct ReshapeOp<T: Numeric> {
+    input: Rc<dyn GraphOp<T>>,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> ReshapeOp<T> {
+    pub fn new(input: Rc<dyn GraphOp<T>>, shape: Vec<usize>) -> ReshapeOp<T> {
+        let input_shape = input.shape();
+        if shape.contains(&0)
+            || shape.iter().product::<usize>() != input_shape.iter().product::<usize>()
+        {
+            panic!(
+                "Tensor of shape {:?} can't be reshaped to {:?}.",
+                input_shape, shape
+            )
+        }
+        ReshapeOp { input, shape }
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for ReshapeOp<T> {
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        self.input
+            .value(feed_dict, cache)
+            .reshape(self.shape.clone())
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        if dependant_node.ref_as_usize() == self.input.ref_as_usize() {
+            Some(grad.reshape(self.input.shape()))
+        } else {
+            None
+        }
+    }
+
+    fn get_name(&self) -> &str {
+        "ReshapeOp"
+    }
+
+    fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+        Some(vec![Rc::clone(&self.input)])
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
+
+// Stacks inputs of equal shapes along a new axis.
+pub(crate) struct StackOp<T: Numeric> {
+    inputs: Vec<Rc<dyn GraphOp<T>>>,
+    axis: usize,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> StackOp<T> {
+    pub fn new(inputs: Vec<Rc<dyn GraphOp<T>>>, axis: usize) -> StackOp<T> {
+        let shapes: Vec<Vec<usize>> = inputs.iter().map(|input| input.shape()).collect();
+        check_stack_shapes(&shapes, axis);
+        let shape = get_stacked_shape(&shapes[0], inputs.len(), axis);
+        StackOp {
+            inputs,
+            axis,
+            shape,
+        }
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for StackOp<T> {
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        let values: Vec<Array<T>> = self
+            .inputs
+            .iter()
+            .map(|input| input.value(feed_dict, cache))
+            .collect();
+        stack(&values.iter().collect::<Vec<&Array<T>>>(), self.axis)
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        // the same node may be stacked multiple times
+        let input_grads: Vec<Array<T>> = self
+            .inputs
+            .iter()
+            .enumerate()
+            .filter(|(_, input)| input.ref_as_usize() == dependant_node.ref_as_usize())
+            .map(|(i, _)| grad.select(self.axis, i).reshape(self.inputs[i].shape()))
+            .collect();
+        input_grads.into_iter().reduce(|x, y| &x + &y)
+    }
+
+    fn get_name(&self) -> &str {
+        "StackOp"
+    }
+
+    // Repeated inputs are listed once, as their gradient already includes all occurrences.
+    fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+        let mut inputs: Vec<Rc<dyn GraphOp<T>>> = Vec::with_capacity(self.inputs.len());
+        for input in self.inputs.iter() {
+            if inputs
+                .iter()
+                .all(|other| other.ref_as_usize() != input.ref_as_usize())
+            {
+                inputs.push(Rc::clone(input));
+            }
+        }
+        Some(inputs)
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
+
+// Selects a sub-array of the input along an axis, removing the axis.
+pub(crate) struct SelectOp<T: Numeric> {
+    input: Rc<dyn GraphOp<T>>,
+    axis: usize,
+    index: usize,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> SelectOp<T> {
+    pub fn new(input: Rc<dyn GraphOp<T>>, axis: usize, index: usize) -> SelectOp<T> {
+        let input_shape = input.shape();
+        if axis >= input_shape.len() || index >= input_shape[axis] {
+            panic!(
+                "Invalid axis {} or index {} for shape {:?}.",
+                axis, index, input_shape
+            )
+        }
+        let shape = get_selected_shape(&input_shape, axis);
+        SelectOp {
+            input,
+            axis,
+            index,
+            shape,
+        }
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for SelectOp<T> {
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        self.input
+            .value(feed_dict, cache)
+            .select(self.axis, self.index)
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        if dependant_node.ref_as_usize() == self.input.ref_as_usize() {
+            Some(index_select_backward(
+                grad,
+                &self.input.shape(),
+                self.axis,
+                &[self.index],
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn get_name(&self) -> &str {
+        "SelectOp"
+    }
+
+    fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+        Some(vec![Rc::clone(&self.input)])
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
+
+// Permutes dimensions of the input.
+pub(crate) struct PermuteOp<T: Numeric> {
+    input: Rc<dyn GraphOp<T>>,
+    axes: Vec<usize>,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> PermuteOp<T> {
+    pub fn new(input: Rc<dyn GraphOp<T>>, axes: &[usize]) -> PermuteOp<T> {
+        let input_shape = input.shape();
+        let mut sorted_axes = axes.to_vec();
+        sorted_axes.sort_unstable();
+        if sorted_axes != (0..input_shape.len()).collect::<Vec<usize>>() {
+            panic!(
+                "Invalid permutation of axes. Got: {:?} for shape {:?}",
+                axes, input_shape
+            )
+        }
+        let shape = axes.iter().map(|&axis| input_shape[axis]).collect();
+        PermuteOp {
+            input,
+            axes: axes.to_vec(),
+            shape,
+        }
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for PermuteOp<T> {
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        self.input.value(feed_dict, cache).permute(&self.axes)
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        if dependant_node.ref_as_usize() == self.input.ref_as_usize() {
+            let mut inverse_axes = vec![0; self.axes.len()];
+            for (i, &axis) in self.axes.iter().enumerate() {
+                inverse_axes[axis] = i;
+            }
+            Some(grad.permute(&inverse_axes))
+        } else {
+            None
+        }
+    }
+
+    fn get_name(&self) -> &str {
+        "PermuteOp"
+    }
+
+    fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+        Some(vec![Rc::clone(&self.input)])
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
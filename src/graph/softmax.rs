@@ -0,0 +1,112 @@
+use crate::graph::GraphOp;
+use crate::linalg::{
+    check_attention_mask_shape, get_attention_mask, masked_softmax, softmax_backward, Array,
+    Numeric,
+};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// Softmax along an axis. Attention softmax is computed along the last axis of scores
+// of shape `[batch, ..., query, key]` and may mask padded or future keys.
+pub(crate) struct SoftmaxOp<T: Numeric> {
+    input: Rc<dyn GraphOp<T>>,
+    padding_mask: Option<Rc<dyn GraphOp<T>>>,
+    axis: usize,
+    causal: bool,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> SoftmaxOp<T> {
+    pub fn new(input: Rc<dyn GraphOp<T>>, axis: usize) -> SoftmaxOp<T> {
+        let shape = input.shape();
+        if axis >= shape.len() {
+            panic!("Axis {} is out of bounds for shape {:?}.", axis, shape)
+        }
+        SoftmaxOp {
+            input,
+            padding_mask: None,
+            axis,
+            causal: false,
+            shape,
+        }
+    }
+
+    pub fn new_attention(
+        input: Rc<dyn GraphOp<T>>,
+        padding_mask: Option<Rc<dyn GraphOp<T>>>,
+        causal: bool,
+    ) -> SoftmaxOp<T> {
+        let shape = input.shape();
+        check_attention_mask_shape(
+            &shape,
+            &padding_mask.as_ref().map_or_else(
+                || vec![shape[0], shape[shape.len() - 1]],
+                |mask| mask.shape(),
+            ),
+        );
+        SoftmaxOp {
+            input,
+            padding_mask,
+            axis: shape.len() - 1,
+            causal,
+            shape,
+        }
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for SoftmaxOp<T> {
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        let padding_mask = self
+            .padding_mask
+            .as_ref()
+            .map(|mask| mask.value(feed_dict, cache));
+        let mask = get_attention_mask(&self.shape, padding_mask.as_ref(), self.causal);
+        masked_softmax(
+            &self.input.value(feed_dict, cache),
+            self.axis,
+            mask.as_deref(),
+        )
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        compute_cache: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        if dependant_node.ref_as_usize() == self.input.ref_as_usize() {
+            Some(softmax_backward(
+                grad,
+                &self.value(feed_dict, compute_cache),
+                self.axis,
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn get_name(&self) -> &str {
+        "SoftmaxOp"
+    }
+
+    fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+        let mut inputs = vec![Rc::clone(&self.input)];
+        if let Some(mask) = &self.padding_mask {
+            inputs.push(Rc::clone(mask));
+        }
+        Some(inputs)
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
@@ -0,0 +1,85 @@
+use crate::graph::GraphOp;
+use crate::linalg::{
+    check_upsample2d_params, resize_nchw, resize_nchw_backward, DataFormat, Interpolation,
+};
+use crate::linalg::{Array, Numeric};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+pub(crate) struct Upsample2dOp<T: Numeric> {
+    input: Rc<dyn GraphOp<T>>,
+    mode: Interpolation,
+    data_format: DataFormat,
+    shape: Vec<usize>,
+}
+
+impl<T: Numeric> Upsample2dOp<T> {
+    pub fn new(
+        input: Rc<dyn GraphOp<T>>,
+        scale_factor: (usize, usize),
+        mode: Interpolation,
+        data_format: DataFormat,
+    ) -> Upsample2dOp<T> {
+        let input_shape = input.shape();
+        check_upsample2d_params(&input_shape, scale_factor);
+        let mut shape = data_format.shape_to_nchw(&input_shape);
+        shape[2] *= scale_factor.0;
+        shape[3] *= scale_factor.1;
+        Upsample2dOp {
+            input,
+            mode,
+            data_format,
+            shape: data_format.shape_from_nchw(&shape),
+        }
+    }
+}
+
+impl<T: Numeric> GraphOp<T> for Upsample2dOp<T> {
+    fn compute(
+        &self,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+        cache: &mut HashMap<usize, Array<T>>,
+    ) -> Array<T> {
+        let shape = self.data_format.shape_to_nchw(&self.shape);
+        let input = self
+            .data_format
+            .array_to_nchw(&self.input.value(feed_dict, cache));
+        self.data_format
+            .array_from_nchw(&resize_nchw(&input, (shape[2], shape[3]), self.mode))
+    }
+
+    fn compute_accumm_grad(
+        &self,
+        _: Option<&HashMap<String, &Array<T>>>,
+        _: &mut HashMap<usize, Array<T>>,
+        dependant_node: &dyn GraphOp<T>,
+        grad: &Array<T>,
+    ) -> Option<Array<T>> {
+        if dependant_node.ref_as_usize() == self.input.ref_as_usize() {
+            let input_shape = self.data_format.shape_to_nchw(&self.input.shape());
+            Some(self.data_format.array_from_nchw(&resize_nchw_backward(
+                &self.data_format.array_to_nchw(grad),
+                &input_shape,
+                self.mode,
+            )))
+        } else {
+            None
+        }
+    }
+
+    fn get_name(&self) -> &str {
+        "Upsample2dOp"
+    }
+
+    fn get_inputs(&self) -> Option<Vec<Rc<dyn GraphOp<T>>>> {
+        Some(vec![Rc::clone(&self.input)])
+    }
+
+    fn as_trait(&self) -> &dyn GraphOp<T> {
+        self as &dyn GraphOp<T>
+    }
+
+    fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
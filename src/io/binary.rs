@@ -0,0 +1,62 @@
+use crate::io::{Dtype, Error, Result};
+use crate::linalg::Numeric;
+use std::convert::TryInto;
+use std::io::{Read, Write};
+
+// Reads exactly `len` bytes. Unexpected end of data is reported as a format error.
+pub(crate) fn read_bytes(reader: &mut impl Read, len: usize) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(Error::Format("unexpected end of data".to_owned()));
+    }
+    Ok(bytes)
+}
+
+pub(crate) fn read_u32(reader: &mut impl Read) -> Result<u32> {
+    Ok(u32::from_le_bytes(
+        read_bytes(reader, 4)?.try_into().unwrap(),
+    ))
+}
+
+pub(crate) fn read_u64(reader: &mut impl Read) -> Result<u64> {
+    Ok(u64::from_le_bytes(
+        read_bytes(reader, 8)?.try_into().unwrap(),
+    ))
+}
+
+pub(crate) fn write_u32(writer: &mut impl Write, value: u32) -> Result<()> {
+    writer.write_all(&value.to_le_bytes())?;
+    Ok(())
+}
+
+pub(crate) fn write_u64(writer: &mut impl Write, value: u64) -> Result<()> {
+    writer.write_all(&value.to_le_bytes())?;
+    Ok(())
+}
+
+// Converts values to bytes of a given data type in little-endian order.
+pub(crate) fn encode_values<T: Numeric>(values: &[T], dtype: Dtype) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(values.len() * dtype.size());
+    for value in values {
+        match dtype {
+            Dtype::F32 => bytes.extend_from_slice(&value.to_f32().unwrap().to_le_bytes()),
+            Dtype::F64 => bytes.extend_from_slice(&value.to_f64().unwrap().to_le_bytes()),
+        }
+    }
+    bytes
+}
+
+// Converts little-endian bytes of a given data type to values.
+pub(crate) fn decode_values<T: Numeric>(bytes: &[u8], dtype: Dtype) -> Vec<T> {
+    bytes
+        .chunks_exact(dtype.size())
+        .map(|chunk| {
+            let value = match dtype {
+                Dtype::F32 => f32::from_le_bytes(chunk.try_into().unwrap()) as f64,
+                Dtype::F64 => f64::from_le_bytes(chunk.try_into().unwrap()),
+            };
+            T::from(value).unwrap()
+        })
+        .collect()
+}
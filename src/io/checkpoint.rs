@@ -0,0 +1,296 @@
+use crate::io::binary::{
+    decode_values, encode_values, read_bytes, read_u32, read_u64, write_u32, write_u64,
+};
+use crate::io::{Dtype, Error, Result};
+use crate::linalg::{Array, Numeric};
+use crate::Tensor;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const MAGIC: &[u8] = b"NRCKPT";
+const VERSION: u8 = 1;
+
+/// Named values of variables and additional training state, e.g. optimizer moments
+/// or step counters, that can be saved to and loaded from a single file.
+///
+/// Checkpoints are stored in a little-endian binary format with all arrays of the same
+/// data type (`f32` or `f64`).
+///
+/// * `variables` - Values of variables in insertion order.
+/// * `state` - Additional named arrays in insertion order.
+///
+/// Models are saved with `Module::named_variables`, which includes non-trainable
+/// variables (e.g. running statistics of batch normalization) next to parameters.
+///
+/// # Examples
+/// ```
+/// use neurust::io::Checkpoint;
+/// use neurust::linalg::Rng;
+/// use neurust::nn::initializers::Initializer;
+/// use neurust::nn::layers::{BatchNorm, Dense};
+/// use neurust::nn::{Module, Sequential};
+/// use neurust::prelude::*;
+///
+/// let build_model = |seed| {
+///     let mut model = Sequential::new();
+///     model.add(Dense::new(2, 3, None, Initializer::HeUniform, true, &mut Rng::new(seed)));
+///     model.add(BatchNorm::new(3, 1, 0.9, 1e-5));
+///     model
+/// };
+/// let input = Array::random_uniform(vec![4, 2], -1., 1., &mut Rng::new(2));
+/// let model = build_model(0);
+/// // a training step updates running statistics of `BatchNorm`
+/// model.forward(&Tensor::new_variable(input.clone())).eval_and_grads(&[], None);
+/// model.set_training(false);
+///
+/// let mut checkpoint = Checkpoint::from_variables(&model.named_variables());
+/// checkpoint.add_state("step", Array::new(10., vec![1]));
+/// let mut buffer = Vec::new();
+/// checkpoint.write(&mut buffer).unwrap();
+///
+/// let restored_model = build_model(1);
+/// restored_model.set_training(false);
+/// let restored = Checkpoint::<f64>::read(&mut buffer.as_slice()).unwrap();
+/// restored.restore(&restored_model.named_variables()).unwrap();
+///
+/// assert_eq!(restored_model.predict(&input), model.predict(&input));
+/// assert_eq!(restored.state("step"), Some(&Array::new(10., vec![1])));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint<T: Numeric> {
+    variables: Vec<(String, Array<T>)>,
+    state: Vec<(String, Array<T>)>,
+}
+
+impl<T: Numeric> Checkpoint<T> {
+    /// Creates an empty checkpoint.
+    pub fn new() -> Checkpoint<T> {
+        Checkpoint {
+            variables: Vec::new(),
+            state: Vec::new(),
+        }
+    }
+
+    /// Creates a checkpoint with current values of named variables, e.g. returned by
+    /// `Module::named_variables`.
+    ///
+    /// * `variables` - Variable tensors together with their names.
+    pub fn from_variables(variables: &[(String, &Tensor<T>)]) -> Checkpoint<T> {
+        let mut checkpoint = Checkpoint::new();
+        for (name, variable) in variables {
+            checkpoint.add_variable(name, variable);
+        }
+        checkpoint
+    }
+
+    /// Stores current value of a variable under a given name, replacing a previously
+    /// stored value with the same name.
+    ///
+    /// * `name` - Name of the variable.
+    /// * `variable` - Variable tensor.
+    pub fn add_variable(&mut self, name: &str, variable: &Tensor<T>) {
+        insert(&mut self.variables, name, variable.eval(None));
+    }
+
+    /// Returns stored value of a variable.
+    pub fn variable(&self, name: &str) -> Option<&Array<T>> {
+        get(&self.variables, name)
+    }
+
+    /// Returns names of stored variables in insertion order.
+    pub fn variable_names(&self) -> Vec<&str> {
+        self.variables
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Stores additional state under a given name, replacing a previously stored value
+    /// with the same name.
+    ///
+    /// * `name` - Name of the state, e.g. `"adam/m/dense_0.kernel"`.
+    /// * `value` - Value of the state.
+    pub fn add_state(&mut self, name: &str, value: Array<T>) {
+        insert(&mut self.state, name, value);
+    }
+
+    /// Returns stored state.
+    pub fn state(&self, name: &str) -> Option<&Array<T>> {
+        get(&self.state, name)
+    }
+
+    /// Returns names of stored states in insertion order.
+    pub fn state_names(&self) -> Vec<&str> {
+        self.state.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Assigns stored values to variables with the same names.
+    ///
+    /// All variables are validated before any of them is assigned, so on error
+    /// the graph is left unchanged. Stored variables without a counterpart in
+    /// `variables` are ignored.
+    ///
+    /// * `variables` - Variable tensors together with their names.
+    ///
+    /// Returns `Error::Missing` if a variable isn't stored in the checkpoint and
+    /// `Error::ShapeMismatch` if a stored value has different shape than the variable.
+    ///
+    /// **Panics** if any of the tensors isn't a variable.
+    pub fn restore(&self, variables: &[(String, &Tensor<T>)]) -> Result<()> {
+        let mut values = Vec::with_capacity(variables.len());
+        for (name, variable) in variables {
+            let value = self
+                .variable(name)
+                .ok_or_else(|| Error::Missing(name.clone()))?;
+            if value.get_shape() != variable.shape() {
+                return Err(Error::ShapeMismatch {
+                    name: name.clone(),
+                    expected: variable.shape(),
+                    found: value.get_shape(),
+                });
+            }
+            values.push(value);
+        }
+        for ((_, variable), value) in variables.iter().zip(values) {
+            variable.assign(value);
+        }
+        Ok(())
+    }
+
+    /// Writes the checkpoint in binary format.
+    ///
+    /// * `writer` - Destination of the data.
+    pub fn write(&self, writer: &mut impl Write) -> Result<()> {
+        let dtype = Dtype::of::<T>();
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[
+            VERSION,
+            match dtype {
+                Dtype::F32 => 0,
+                Dtype::F64 => 1,
+            },
+        ])?;
+        for entries in [&self.variables, &self.state] {
+            write_u32(writer, entries.len() as u32)?;
+            for (name, value) in entries.iter() {
+                write_u32(writer, name.len() as u32)?;
+                writer.write_all(name.as_bytes())?;
+                let shape = value.get_shape();
+                write_u32(writer, shape.len() as u32)?;
+                for &dim in shape.iter() {
+                    write_u64(writer, dim as u64)?;
+                }
+                writer.write_all(&encode_values(&value.data, dtype))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a checkpoint written by `Checkpoint::write`.
+    ///
+    /// * `reader` - Source of the data.
+    ///
+    /// Returns `Error::DtypeMismatch` if the checkpoint was stored with a different
+    /// data type than `T` and `Error::Format` if data is malformed.
+    pub fn read(reader: &mut impl Read) -> Result<Checkpoint<T>> {
+        if read_bytes(reader, MAGIC.len())? != MAGIC {
+            return Err(Error::Format("not a checkpoint file".to_owned()));
+        }
+        let header = read_bytes(reader, 2)?;
+        if header[0] != VERSION {
+            return Err(Error::Format(format!(
+                "unsupported checkpoint version {}",
+                header[0]
+            )));
+        }
+        let found = match header[1] {
+            0 => Dtype::F32,
+            1 => Dtype::F64,
+            code => return Err(Error::Format(format!("unknown data type code {}", code))),
+        };
+        let expected = Dtype::of::<T>();
+        if found != expected {
+            return Err(Error::DtypeMismatch { expected, found });
+        }
+
+        let mut checkpoint = Checkpoint::new();
+        for entries in [&mut checkpoint.variables, &mut checkpoint.state] {
+            let count = read_u32(reader)?;
+            for _ in 0..count {
+                let name = read_string(reader)?;
+                if get(entries, &name).is_some() {
+                    return Err(Error::Format(format!("duplicated entry '{}'", name)));
+                }
+                let rank = read_u32(reader)? as usize;
+                if rank == 0 {
+                    return Err(Error::Format(format!("entry '{}' has no dimensions", name)));
+                }
+                let mut shape = Vec::with_capacity(rank);
+                for _ in 0..rank {
+                    let dim = read_u64(reader)? as usize;
+                    if dim == 0 {
+                        return Err(Error::Format(format!(
+                            "entry '{}' has a zero dimension",
+                            name
+                        )));
+                    }
+                    shape.push(dim);
+                }
+                let len = shape
+                    .iter()
+                    .try_fold(expected.size(), |acc, &dim| acc.checked_mul(dim))
+                    .ok_or_else(|| Error::Format(format!("entry '{}' is too large", name)))?;
+                let data = decode_values(&read_bytes(reader, len)?, expected);
+                entries.push((name, Array::from_vec(data, shape)));
+            }
+        }
+        Ok(checkpoint)
+    }
+
+    /// Saves the checkpoint to a file, see `Checkpoint::write`.
+    ///
+    /// * `path` - Path of the file, it's created or truncated.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Loads a checkpoint from a file, see `Checkpoint::read`.
+    ///
+    /// * `path` - Path of the file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Checkpoint<T>> {
+        Checkpoint::read(&mut BufReader::new(File::open(path)?))
+    }
+}
+
+impl<T: Numeric> Default for Checkpoint<T> {
+    fn default() -> Self {
+        Checkpoint::new()
+    }
+}
+
+fn get<'a, T: Numeric>(entries: &'a [(String, Array<T>)], name: &str) -> Option<&'a Array<T>> {
+    entries
+        .iter()
+        .find(|(entry_name, _)| entry_name == name)
+        .map(|(_, value)| value)
+}
+
+fn insert<T: Numeric>(entries: &mut Vec<(String, Array<T>)>, name: &str, value: Array<T>) {
+    match entries
+        .iter_mut()
+        .find(|(entry_name, _)| entry_name == name)
+    {
+        Some(entry) => entry.1 = value,
+        None => entries.push((name.to_owned(), value)),
+    }
+}
+
+fn read_string(reader: &mut impl Read) -> Result<String> {
+    let len = read_u32(reader)? as usize;
+    String::from_utf8(read_bytes(reader, len)?)
+        .map_err(|_| Error::Format("name is not valid UTF-8".to_owned()))
+}
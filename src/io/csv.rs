@@ -0,0 +1,416 @@
+use crate::io::{Error, Result};
+use crate::linalg::{Array, Numeric};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Lines, Read, Write};
+use std::path::Path;
+
+/// Columns read from a delimited text file.
+///
+/// * `All` - All columns.
+/// * `Indices` - Columns with given zero-based indices, in the given order.
+/// * `Names` - Columns with given header names, in the given order. Requires a header.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CsvColumns {
+    All,
+    Indices(Vec<usize>),
+    Names(Vec<String>),
+}
+
+/// Handling of missing values, i.e. fields equal to one of `CsvOptions::missing_values`.
+///
+/// * `Error` - Missing values are reported as errors.
+/// * `Fill` - Missing values are replaced with a given value, e.g. `NaN` or `0`.
+/// * `SkipRow` - Rows with missing values in selected columns are skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MissingValues<T: Numeric> {
+    Error,
+    Fill(T),
+    SkipRow,
+}
+
+/// Options of reading delimited text files.
+///
+/// * `delimiter` - Character separating fields, e.g. `','` or `'\t'`.
+/// * `has_header` - If true, the first non-empty line contains column names and is skipped.
+/// * `skip_rows` - Number of lines skipped before the header or data, e.g. comments.
+/// * `columns` - Columns to read.
+/// * `missing_values` - Fields treated as missing values, compared after trimming whitespaces.
+/// * `missing` - Handling of missing values.
+///
+/// # Examples
+/// ```
+/// use neurust::io::{CsvColumns, CsvOptions, MissingValues};
+///
+/// let options = CsvOptions {
+///     delimiter: ';',
+///     columns: CsvColumns::Indices(vec![0, 2]),
+///     missing: MissingValues::Fill(0.),
+///     ..CsvOptions::default()
+/// };
+///
+/// assert!(options.has_header);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvOptions<T: Numeric> {
+    pub delimiter: char,
+    pub has_header: bool,
+    pub skip_rows: usize,
+    pub columns: CsvColumns,
+    pub missing_values: Vec<String>,
+    pub missing: MissingValues<T>,
+}
+
+impl<T: Numeric> Default for CsvOptions<T> {
+    /// Comma-separated values with a header, all columns and empty or `NA` fields
+    /// reported as errors.
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: ',',
+            has_header: true,
+            skip_rows: 0,
+            columns: CsvColumns::All,
+            missing_values: vec!["".to_owned(), "NA".to_owned()],
+            missing: MissingValues::Error,
+        }
+    }
+}
+
+// Splits a record into fields. Fields can be enclosed in double quotes, which allows
+// delimiters and line breaks inside them, and quotes are escaped by doubling them.
+// Returns `None` if the last quoted field isn't terminated.
+fn split_fields(record: &str, delimiter: char) -> Option<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut characters = record.chars().peekable();
+    while let Some(character) = characters.next() {
+        if in_quotes {
+            if character == '"' {
+                if characters.peek() == Some(&'"') {
+                    characters.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(character);
+            }
+        } else if character == '"' && field.trim().is_empty() {
+            field.clear();
+            in_quotes = true;
+        } else if character == delimiter {
+            fields.push(std::mem::take(&mut field));
+        } else {
+            field.push(character);
+        }
+    }
+    if in_quotes {
+        return None;
+    }
+    fields.push(field);
+    Some(fields)
+}
+
+// Reads records of fields with numbers of their first lines. A record spans multiple
+// lines if a quoted field contains line breaks. Empty lines between records are skipped.
+struct Records<R> {
+    lines: Lines<R>,
+    line_number: usize,
+    delimiter: char,
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = Result<(usize, Vec<String>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record = String::new();
+        let mut first_line = None;
+        loop {
+            let line = match (self.lines.next(), first_line) {
+                (Some(Ok(line)), _) => line,
+                (Some(Err(error)), _) => return Some(Err(error.into())),
+                (None, None) => return None,
+                (None, Some(first_line)) => {
+                    return Some(Err(Error::Format(format!(
+                        "unterminated quoted field in line {}",
+                        first_line
+                    ))))
+                }
+            };
+            self.line_number += 1;
+            if first_line.is_none() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                first_line = Some(self.line_number);
+            } else {
+                record.push('\n');
+            }
+            record.push_str(&line);
+            if let Some(fields) = split_fields(&record, self.delimiter) {
+                return first_line.map(|first_line| Ok((first_line, fields)));
+            }
+        }
+    }
+}
+
+// Returns indices of selected columns.
+fn get_column_indices(
+    columns: &CsvColumns,
+    header: Option<&[String]>,
+    field_count: usize,
+) -> Result<Vec<usize>> {
+    let indices = match columns {
+        CsvColumns::All => (0..field_count).collect(),
+        CsvColumns::Indices(indices) => indices.clone(),
+        CsvColumns::Names(names) => {
+            let header = header.ok_or_else(|| {
+                Error::Format("columns can be selected by names only with a header".to_owned())
+            })?;
+            names
+                .iter()
+                .map(|name| {
+                    header
+                        .iter()
+                        .position(|column| column.trim() == name)
+                        .ok_or_else(|| Error::Missing(name.clone()))
+                })
+                .collect::<Result<Vec<usize>>>()?
+        }
+    };
+    if let Some(&index) = indices.iter().find(|&&index| index >= field_count) {
+        return Err(Error::Format(format!(
+            "column {} is out of bounds for {} columns",
+            index, field_count
+        )));
+    }
+    Ok(indices)
+}
+
+fn quote_field(field: &str, delimiter: char) -> String {
+    if field.contains(delimiter) || field.contains('"') || field.contains('\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+impl<T: Numeric> Array<T> {
+    /// Reads a 2D array of shape `[rows, columns]` from delimited text, e.g. CSV.
+    ///
+    /// Empty lines are ignored and fields are trimmed before parsing. Every line must
+    /// have the same number of fields. Quoted fields may contain delimiters and line
+    /// breaks.
+    ///
+    /// * `reader` - Source of the data.
+    /// * `options` - Delimiter, header, column selection and missing values handling.
+    ///
+    /// Returns `Error::Format` if a field isn't a number or a missing value isn't
+    /// allowed, lines have different number of fields or there are no data rows, and
+    /// `Error::Missing` if a column selected by name doesn't exist.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::io::{CsvColumns, CsvOptions, MissingValues};
+    /// use neurust::Array;
+    ///
+    /// let csv = "id,height,weight\n1,1.8,80\n2,,65\n3,1.6,55\n";
+    /// let options = CsvOptions {
+    ///     columns: CsvColumns::Names(vec!["weight".to_owned(), "height".to_owned()]),
+    ///     missing: MissingValues::SkipRow,
+    ///     ..CsvOptions::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     Array::from_csv(csv.as_bytes(), &options).unwrap(),
+    ///     Array::from_vec(vec![80., 1.8, 55., 1.6], vec![2, 2])
+    /// );
+    /// ```
+    pub fn from_csv(reader: impl Read, options: &CsvOptions<T>) -> Result<Array<T>> {
+        let mut lines = BufReader::new(reader).lines();
+        for _ in lines.by_ref().take(options.skip_rows) {}
+        let mut records = Records {
+            lines,
+            line_number: options.skip_rows,
+            delimiter: options.delimiter,
+        };
+
+        let header = if options.has_header {
+            match records.next() {
+                Some(record) => Some(record?.1),
+                None => None,
+            }
+        } else {
+            None
+        };
+
+        let mut field_count = header.as_ref().map(|header| header.len());
+        let mut columns = None;
+        let mut data = Vec::new();
+        let mut rows = 0;
+        for record in records {
+            let (line_number, fields) = record?;
+            let expected_count = *field_count.get_or_insert(fields.len());
+            if fields.len() != expected_count {
+                return Err(Error::Format(format!(
+                    "line {} has {} fields, expected {}",
+                    line_number,
+                    fields.len(),
+                    expected_count
+                )));
+            }
+            if columns.is_none() {
+                columns = Some(get_column_indices(
+                    &options.columns,
+                    header.as_deref(),
+                    expected_count,
+                )?);
+            }
+
+            let mut row = Vec::new();
+            for &column in columns.as_ref().unwrap() {
+                let field = fields[column].trim();
+                if options
+                    .missing_values
+                    .iter()
+                    .any(|missing| missing == field)
+                {
+                    match options.missing {
+                        MissingValues::Error => {
+                            return Err(Error::Format(format!(
+                                "missing value in line {}, column {}",
+                                line_number, column
+                            )))
+                        }
+                        MissingValues::Fill(value) => row.push(value),
+                        MissingValues::SkipRow => {
+                            row.clear();
+                            break;
+                        }
+                    }
+                } else {
+                    row.push(field.parse::<f64>().ok().and_then(T::from).ok_or_else(|| {
+                        Error::Format(format!(
+                            "invalid number '{}' in line {}, column {}",
+                            field, line_number, column
+                        ))
+                    })?);
+                }
+            }
+            if row.len() == columns.as_ref().unwrap().len() && !row.is_empty() {
+                data.extend(row);
+                rows += 1;
+            }
+        }
+        let columns = columns.map_or(0, |columns| columns.len());
+        if rows == 0 || columns == 0 {
+            return Err(Error::Format("no data to read".to_owned()));
+        }
+        Ok(Array::from_vec(data, vec![rows, columns]))
+    }
+
+    /// Loads a 2D array from a delimited text file, see `Array::from_csv`.
+    ///
+    /// * `path` - Path of the file.
+    /// * `options` - Delimiter, header, column selection and missing values handling.
+    pub fn load_csv(path: impl AsRef<Path>, options: &CsvOptions<T>) -> Result<Array<T>> {
+        Array::from_csv(File::open(path)?, options)
+    }
+
+    /// Writes a 2D array as comma-separated values without a header, one row per line.
+    ///
+    /// * `writer` - Destination of the data.
+    ///
+    /// **Panics** if the array isn't 2-dimensional.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::Array;
+    ///
+    /// let a = Array::from_vec(vec![1., 0.5, -2., 3.], vec![2, 2]);
+    /// let mut buffer = Vec::new();
+    /// a.to_csv(&mut buffer).unwrap();
+    ///
+    /// assert_eq!(String::from_utf8(buffer).unwrap(), "1,0.5\n-2,3\n");
+    /// ```
+    pub fn to_csv(&self, writer: &mut impl Write) -> Result<()> {
+        self.write_csv(writer, None, ',')
+    }
+
+    /// Writes a 2D array as delimited text, one row per line.
+    ///
+    /// Values are written with the shortest representation that reads back to the same
+    /// value.
+    ///
+    /// * `writer` - Destination of the data.
+    /// * `header` - Optional names of columns written in the first line. Names containing
+    ///   the delimiter, quotes or line breaks are quoted.
+    /// * `delimiter` - Character separating fields.
+    ///
+    /// **Panics** if the array isn't 2-dimensional or length of `header` differs from
+    /// the number of columns.
+    pub fn write_csv(
+        &self,
+        writer: &mut impl Write,
+        header: Option<&[&str]>,
+        delimiter: char,
+    ) -> Result<()> {
+        if self.shape.len() != 2 {
+            panic!(
+                "Only 2-dimensional arrays can be written as CSV. Got shape: {:?}",
+                self.shape
+            )
+        }
+        let mut writer = BufWriter::new(writer);
+        let separator = delimiter.to_string();
+        if let Some(header) = header {
+            if header.len() != self.shape[1] {
+                panic!(
+                    "Header has {} names, but the array has {} columns.",
+                    header.len(),
+                    self.shape[1]
+                )
+            }
+            let names: Vec<String> = header
+                .iter()
+                .map(|name| quote_field(name, delimiter))
+                .collect();
+            writeln!(writer, "{}", names.join(&separator))?;
+        }
+        for row in self.data.chunks(self.shape[1]) {
+            let values: Vec<String> = row.iter().map(|value| value.to_string()).collect();
+            writeln!(writer, "{}", values.join(&separator))?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Saves a 2D array to a comma-separated values file, see `Array::write_csv`.
+    ///
+    /// * `path` - Path of the file, it's created or truncated.
+    /// * `header` - Optional names of columns.
+    ///
+    /// **Panics** if the array isn't 2-dimensional or length of `header` differs from
+    /// the number of columns.
+    pub fn save_csv(&self, path: impl AsRef<Path>, header: Option<&[&str]>) -> Result<()> {
+        self.write_csv(&mut File::create(path)?, header, ',')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_fields() {
+        assert_eq!(
+            split_fields(r#"1, "a,b" ,"say ""hi""",,"#, ',').unwrap(),
+            vec!["1", "a,b ", "say \"hi\"", "", ""]
+        );
+        assert_eq!(
+            split_fields("\"two\nlines\",x", ',').unwrap(),
+            vec!["two\nlines", "x"]
+        );
+        assert!(split_fields("\"open", ',').is_none());
+    }
+}
@@ -0,0 +1,249 @@
+use crate::io::binary::read_bytes;
+use crate::io::inflate::{gunzip, is_gzip};
+use crate::io::{Error, Result};
+use crate::linalg::{Array, Numeric};
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+// Maximum value of unsigned bytes, used to normalize images.
+const MAX_PIXEL_VALUE: f64 = 255.;
+
+/// Encoding of labels read from IDX files.
+///
+/// * `Integer` - Labels as numbers, giving an array of shape `[samples]`.
+/// * `OneHot` - Labels as one-hot vectors of a given number of classes, giving an array
+///   of shape `[samples, classes]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelEncoding {
+    Integer,
+    OneHot(usize),
+}
+
+// Data types of IDX files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IdxType {
+    U8,
+    I8,
+    I16,
+    I32,
+    F32,
+    F64,
+}
+
+impl IdxType {
+    fn from_code(code: u8) -> Result<IdxType> {
+        match code {
+            0x08 => Ok(IdxType::U8),
+            0x09 => Ok(IdxType::I8),
+            0x0b => Ok(IdxType::I16),
+            0x0c => Ok(IdxType::I32),
+            0x0d => Ok(IdxType::F32),
+            0x0e => Ok(IdxType::F64),
+            _ => Err(Error::Format(format!(
+                "unknown IDX data type 0x{:02x}",
+                code
+            ))),
+        }
+    }
+
+    fn size(self) -> usize {
+        match self {
+            IdxType::U8 | IdxType::I8 => 1,
+            IdxType::I16 => 2,
+            IdxType::I32 | IdxType::F32 => 4,
+            IdxType::F64 => 8,
+        }
+    }
+
+    // Converts a big-endian value to `f64`.
+    fn decode(self, bytes: &[u8]) -> f64 {
+        match self {
+            IdxType::U8 => bytes[0] as f64,
+            IdxType::I8 => bytes[0] as i8 as f64,
+            IdxType::I16 => i16::from_be_bytes(bytes.try_into().unwrap()) as f64,
+            IdxType::I32 => i32::from_be_bytes(bytes.try_into().unwrap()) as f64,
+            IdxType::F32 => f32::from_be_bytes(bytes.try_into().unwrap()) as f64,
+            IdxType::F64 => f64::from_be_bytes(bytes.try_into().unwrap()),
+        }
+    }
+}
+
+// Reads a whole IDX file, decompressing it if it's gzipped, and returns its data type,
+// shape and values.
+fn read_idx_values(mut reader: impl Read) -> Result<(IdxType, Vec<usize>, Vec<f64>)> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    if is_gzip(&data) {
+        data = gunzip(&data)?;
+    }
+    let mut data = data.as_slice();
+
+    let magic = read_bytes(&mut data, 4)?;
+    if magic[..2] != [0, 0] {
+        return Err(Error::Format("invalid IDX magic number".to_owned()));
+    }
+    let idx_type = IdxType::from_code(magic[2])?;
+    let mut shape = Vec::with_capacity(magic[3] as usize);
+    for _ in 0..magic[3] {
+        let dim = u32::from_be_bytes(read_bytes(&mut data, 4)?.try_into().unwrap());
+        shape.push(dim as usize);
+    }
+    if shape.contains(&0) {
+        return Err(Error::Format(format!(
+            "empty IDX data of shape {:?}",
+            shape
+        )));
+    }
+    if shape.is_empty() {
+        shape.push(1);
+    }
+
+    let len = shape
+        .iter()
+        .try_fold(idx_type.size(), |acc, &dim| acc.checked_mul(dim))
+        .ok_or_else(|| Error::Format(format!("IDX data of shape {:?} is too large", shape)))?;
+    let bytes = read_bytes(&mut data, len)?;
+    let values = bytes
+        .chunks_exact(idx_type.size())
+        .map(|chunk| idx_type.decode(chunk))
+        .collect();
+    Ok((idx_type, shape, values))
+}
+
+impl<T: Numeric> Array<T> {
+    /// Reads an array from the IDX format, used e.g. by MNIST.
+    ///
+    /// All IDX data types are supported and values are converted to `T` as they are.
+    /// Gzipped data is decompressed. A file without dimensions is read as shape `[1]`.
+    ///
+    /// * `reader` - Source of the data.
+    ///
+    /// Returns `Error::Format` if the data isn't a valid IDX file.
+    pub fn read_idx(reader: impl Read) -> Result<Array<T>> {
+        let (_, shape, values) = read_idx_values(reader)?;
+        Ok(Array::from_vec(
+            values.into_iter().map(|x| T::from(x).unwrap()).collect(),
+            shape,
+        ))
+    }
+
+    /// Loads an array from an IDX file, optionally gzipped, see `Array::read_idx`.
+    ///
+    /// * `path` - Path of the file.
+    pub fn load_idx(path: impl AsRef<Path>) -> Result<Array<T>> {
+        Array::read_idx(File::open(path)?)
+    }
+}
+
+/// Reads images from the IDX format, e.g. MNIST `train-images-idx3-ubyte`.
+///
+/// Images must be stored as unsigned bytes of shape `[samples, height, width]`. They are
+/// normalized to `[0, 1]` and returned in NCHW format, i.e. of shape
+/// `[samples, 1, height, width]`. Gzipped data is decompressed.
+///
+/// * `reader` - Source of the data.
+///
+/// Returns `Error::Format` if the data isn't a valid IDX file of unsigned byte images.
+///
+/// # Examples
+/// ```
+/// use neurust::io::read_idx_images;
+/// use neurust::Array;
+///
+/// let mut idx = vec![0, 0, 8, 3, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 2];
+/// idx.extend_from_slice(&[0, 51, 204, 255]);
+///
+/// assert_eq!(
+///     read_idx_images::<f64>(idx.as_slice()).unwrap(),
+///     Array::from_vec(vec![0., 0.2, 0.8, 1.], vec![1, 1, 2, 2])
+/// );
+/// ```
+pub fn read_idx_images<T: Numeric>(reader: impl Read) -> Result<Array<T>> {
+    let (idx_type, shape, values) = read_idx_values(reader)?;
+    if idx_type != IdxType::U8 || shape.len() != 3 {
+        return Err(Error::Format(format!(
+            "expected unsigned byte images of shape [samples, height, width], found {:?} of shape {:?}",
+            idx_type, shape
+        )));
+    }
+    Ok(Array::from_vec(
+        values
+            .into_iter()
+            .map(|x| T::from(x / MAX_PIXEL_VALUE).unwrap())
+            .collect(),
+        vec![shape[0], 1, shape[1], shape[2]],
+    ))
+}
+
+/// Loads images from an IDX file, optionally gzipped, see `read_idx_images`.
+///
+/// * `path` - Path of the file.
+pub fn load_idx_images<T: Numeric>(path: impl AsRef<Path>) -> Result<Array<T>> {
+    read_idx_images(File::open(path)?)
+}
+
+/// Reads labels from the IDX format, e.g. MNIST `train-labels-idx1-ubyte`.
+///
+/// Labels must be stored as unsigned bytes of shape `[samples]`. Gzipped data is
+/// decompressed.
+///
+/// * `reader` - Source of the data.
+/// * `encoding` - Encoding of returned labels.
+///
+/// Returns `Error::Format` if the data isn't a valid IDX file of unsigned byte labels or
+/// a label doesn't fit in the given number of one-hot classes.
+///
+/// # Examples
+/// ```
+/// use neurust::io::{read_idx_labels, LabelEncoding};
+/// use neurust::Array;
+///
+/// let idx = [0, 0, 8, 1, 0, 0, 0, 2, 2, 0];
+///
+/// assert_eq!(
+///     read_idx_labels::<f64>(&idx[..], LabelEncoding::OneHot(3)).unwrap(),
+///     Array::from_vec(vec![0., 0., 1., 1., 0., 0.], vec![2, 3])
+/// );
+/// ```
+pub fn read_idx_labels<T: Numeric>(reader: impl Read, encoding: LabelEncoding) -> Result<Array<T>> {
+    let (idx_type, shape, values) = read_idx_values(reader)?;
+    if idx_type != IdxType::U8 || shape.len() != 1 {
+        return Err(Error::Format(format!(
+            "expected unsigned byte labels of shape [samples], found {:?} of shape {:?}",
+            idx_type, shape
+        )));
+    }
+    match encoding {
+        LabelEncoding::Integer => Ok(Array::from_vec(
+            values.into_iter().map(|x| T::from(x).unwrap()).collect(),
+            shape,
+        )),
+        LabelEncoding::OneHot(classes) => {
+            let mut data = vec![T::zero(); shape[0] * classes];
+            for (i, &label) in values.iter().enumerate() {
+                let label = label as usize;
+                if label >= classes {
+                    return Err(Error::Format(format!(
+                        "label {} doesn't fit in {} classes",
+                        label, classes
+                    )));
+                }
+                data[i * classes + label] = T::one();
+            }
+            Ok(Array::from_vec(data, vec![shape[0], classes]))
+        }
+    }
+}
+
+/// Loads labels from an IDX file, optionally gzipped, see `read_idx_labels`.
+///
+/// * `path` - Path of the file.
+/// * `encoding` - Encoding of returned labels.
+pub fn load_idx_labels<T: Numeric>(
+    path: impl AsRef<Path>,
+    encoding: LabelEncoding,
+) -> Result<Array<T>> {
+    read_idx_labels(File::open(path)?, encoding)
+}
@@ -0,0 +1,427 @@
+use crate::io::{Error, Result};
+use crate::linalg::{Array, DataFormat, Numeric};
+use std::convert::{TryFrom, TryInto};
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+// Size of the BMP file header and the `BITMAPINFOHEADER`.
+const BMP_FILE_HEADER_SIZE: usize = 14;
+const BMP_INFO_HEADER_SIZE: usize = 40;
+// Resolution written to BMP files, 72 DPI.
+const BMP_PIXELS_PER_METER: u32 = 2835;
+
+/// Format of image files.
+///
+/// * `PnmAscii` - ASCII PGM (`P2`) for 1-channel or PPM (`P3`) for 3-channel images.
+/// * `PnmBinary` - Binary PGM (`P5`) for 1-channel or PPM (`P6`) for 3-channel images.
+/// * `Bmp` - Uncompressed 24-bit BMP, 1-channel images are stored as gray RGB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    PnmAscii,
+    PnmBinary,
+    Bmp,
+}
+
+impl ImageFormat {
+    /// Returns the format matching an extension of a path: `pgm`, `ppm` or `pnm` for
+    /// binary PNM and `bmp` for BMP, ignoring case.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<ImageFormat> {
+        match lowercase_extension(&path)?.as_str() {
+            "pgm" | "ppm" | "pnm" => Some(ImageFormat::PnmBinary),
+            "bmp" => Some(ImageFormat::Bmp),
+            _ => None,
+        }
+    }
+}
+
+fn lowercase_extension(path: impl AsRef<Path>) -> Option<String> {
+    Some(path.as_ref().extension()?.to_str()?.to_lowercase())
+}
+
+// Returns the number of channels required by an extension of a path, i.e. 1 for PGM
+// and 3 for PPM files.
+fn required_channels(path: impl AsRef<Path>) -> Option<usize> {
+    match lowercase_extension(path)?.as_str() {
+        "pgm" => Some(1),
+        "ppm" => Some(3),
+        _ => None,
+    }
+}
+
+// Image with 8 or 16-bit samples stored in `[height, width, channels]` order.
+struct RawImage {
+    height: usize,
+    width: usize,
+    channels: usize,
+    max_value: u16,
+    samples: Vec<u16>,
+}
+
+// Splits PNM headers and ASCII data into whitespace separated tokens, skipping comments.
+struct PnmTokens<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> PnmTokens<'a> {
+    fn next_token(&mut self) -> Result<&'a [u8]> {
+        loop {
+            match self.data.get(self.position) {
+                Some(b'#') => {
+                    while self.data.get(self.position).is_some_and(|&c| c != b'\n') {
+                        self.position += 1;
+                    }
+                }
+                Some(c) if c.is_ascii_whitespace() => self.position += 1,
+                Some(_) => break,
+                None => return Err(Error::Format("unexpected end of PNM data".to_owned())),
+            }
+        }
+        let start = self.position;
+        while self
+            .data
+            .get(self.position)
+            .is_some_and(|c| !c.is_ascii_whitespace())
+        {
+            self.position += 1;
+        }
+        Ok(&self.data[start..self.position])
+    }
+
+    fn next_number(&mut self) -> Result<usize> {
+        let token = self.next_token()?;
+        std::str::from_utf8(token)
+            .ok()
+            .and_then(|token| token.parse().ok())
+            .ok_or_else(|| {
+                Error::Format(format!(
+                    "invalid number '{}' in PNM data",
+                    String::from_utf8_lossy(token)
+                ))
+            })
+    }
+}
+
+fn read_pnm(data: &[u8]) -> Result<RawImage> {
+    let (channels, is_binary) = match &data[..2] {
+        b"P2" => (1, false),
+        b"P3" => (3, false),
+        b"P5" => (1, true),
+        b"P6" => (3, true),
+        _ => unreachable!(),
+    };
+    let mut tokens = PnmTokens { data, position: 2 };
+    let width = tokens.next_number()?;
+    let height = tokens.next_number()?;
+    let max_value = tokens.next_number()?;
+    if width == 0 || height == 0 || max_value == 0 || max_value > u16::MAX as usize {
+        return Err(Error::Format(format!(
+            "invalid PNM size {}x{} or maximum value {}",
+            width, height, max_value
+        )));
+    }
+
+    let len = width
+        .checked_mul(height)
+        .and_then(|len| len.checked_mul(channels))
+        .ok_or_else(|| Error::Format(format!("PNM size {}x{} is too large", width, height)))?;
+    let samples: Vec<usize> = if is_binary {
+        // a single whitespace separates the header from data
+        let start = tokens.position + 1;
+        let sample_size = if max_value < 256 { 1 } else { 2 };
+        let bytes = len
+            .checked_mul(sample_size)
+            .and_then(|size| start.checked_add(size))
+            .and_then(|end| data.get(start..end))
+            .ok_or_else(|| Error::Format("unexpected end of PNM data".to_owned()))?;
+        if sample_size == 1 {
+            bytes.iter().map(|&x| x as usize).collect()
+        } else {
+            bytes
+                .chunks_exact(2)
+                .map(|x| u16::from_be_bytes([x[0], x[1]]) as usize)
+                .collect()
+        }
+    } else {
+        (0..len)
+            .map(|_| tokens.next_number())
+            .collect::<Result<Vec<usize>>>()?
+    };
+    Ok(RawImage {
+        height,
+        width,
+        channels,
+        max_value: max_value as u16,
+        samples: samples
+            .into_iter()
+            .map(|x| x.min(max_value) as u16)
+            .collect(),
+    })
+}
+
+fn read_bmp(data: &[u8]) -> Result<RawImage> {
+    let invalid = |message: &str| Error::Format(format!("invalid BMP: {}", message));
+    if data.len() < BMP_FILE_HEADER_SIZE + BMP_INFO_HEADER_SIZE {
+        return Err(invalid("unexpected end of data"));
+    }
+    let u16_at = |i: usize| u16::from_le_bytes(data[i..i + 2].try_into().unwrap());
+    let u32_at = |i: usize| u32::from_le_bytes(data[i..i + 4].try_into().unwrap());
+    let offset = u32_at(10) as usize;
+    if (u32_at(14) as usize) < BMP_INFO_HEADER_SIZE {
+        return Err(invalid("unsupported header"));
+    }
+    let width = u32_at(18) as i32;
+    let height = u32_at(22) as i32;
+    if u16_at(28) != 24 || u32_at(30) != 0 {
+        return Err(invalid("only uncompressed 24-bit images are supported"));
+    }
+    if width <= 0 || height == 0 {
+        return Err(invalid("empty image"));
+    }
+
+    // rows are stored bottom-up unless height is negative and padded to 4 bytes
+    let (width, is_bottom_up) = (width as usize, height > 0);
+    let height = height.unsigned_abs() as usize;
+    let stride = width
+        .checked_mul(3)
+        .and_then(|row| row.checked_next_multiple_of(4))
+        .ok_or_else(|| invalid("image is too large"))?;
+    stride
+        .checked_mul(height)
+        .and_then(|size| offset.checked_add(size))
+        .filter(|&end| end <= data.len())
+        .ok_or_else(|| invalid("unexpected end of data"))?;
+    let mut samples = Vec::with_capacity(height * width * 3);
+    for y in 0..height {
+        let row = if is_bottom_up { height - 1 - y } else { y };
+        let start = offset + row * stride;
+        for pixel in data[start..start + width * 3].chunks_exact(3) {
+            samples.extend([pixel[2], pixel[1], pixel[0]].iter().map(|&x| x as u16));
+        }
+    }
+    Ok(RawImage {
+        height,
+        width,
+        channels: 3,
+        max_value: u8::MAX as u16,
+        samples,
+    })
+}
+
+fn write_pnm(writer: &mut impl Write, image: &RawImage, is_binary: bool) -> Result<()> {
+    let magic = match (image.channels, is_binary) {
+        (1, false) => "P2",
+        (3, false) => "P3",
+        (1, true) => "P5",
+        _ => "P6",
+    };
+    write!(
+        writer,
+        "{}\n{} {}\n{}\n",
+        magic, image.width, image.height, image.max_value
+    )?;
+    if is_binary {
+        let bytes: Vec<u8> = image.samples.iter().map(|&x| x as u8).collect();
+        writer.write_all(&bytes)?;
+    } else {
+        for row in image.samples.chunks(image.width * image.channels) {
+            let values: Vec<String> = row.iter().map(|x| x.to_string()).collect();
+            writeln!(writer, "{}", values.join(" "))?;
+        }
+    }
+    Ok(())
+}
+
+fn write_bmp(writer: &mut impl Write, image: &RawImage) -> Result<()> {
+    let too_large = || {
+        Error::Format(format!(
+            "image of size {}x{} is too large for BMP",
+            image.width, image.height
+        ))
+    };
+    let stride = image
+        .width
+        .checked_mul(3)
+        .and_then(|row| row.checked_next_multiple_of(4))
+        .ok_or_else(too_large)?;
+    let offset = (BMP_FILE_HEADER_SIZE + BMP_INFO_HEADER_SIZE) as u32;
+    let data_size = stride
+        .checked_mul(image.height)
+        .and_then(|size| u32::try_from(size).ok())
+        .ok_or_else(too_large)?;
+    let file_size = data_size.checked_add(offset).ok_or_else(too_large)?;
+    let width = i32::try_from(image.width).map_err(|_| too_large())?;
+    let height = i32::try_from(image.height).map_err(|_| too_large())?;
+
+    let mut bytes = Vec::with_capacity(file_size as usize);
+    bytes.extend_from_slice(b"BM");
+    bytes.extend_from_slice(&file_size.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&offset.to_le_bytes());
+    bytes.extend_from_slice(&(BMP_INFO_HEADER_SIZE as u32).to_le_bytes());
+    bytes.extend_from_slice(&width.to_le_bytes());
+    bytes.extend_from_slice(&height.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&24u16.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&data_size.to_le_bytes());
+    bytes.extend_from_slice(&BMP_PIXELS_PER_METER.to_le_bytes());
+    bytes.extend_from_slice(&BMP_PIXELS_PER_METER.to_le_bytes());
+    bytes.extend_from_slice(&[0; 8]);
+    for row in image.samples.chunks(image.width * image.channels).rev() {
+        for pixel in row.chunks(image.channels) {
+            let (red, green, blue) = if image.channels == 1 {
+                (pixel[0], pixel[0], pixel[0])
+            } else {
+                (pixel[0], pixel[1], pixel[2])
+            };
+            bytes.extend_from_slice(&[blue as u8, green as u8, red as u8]);
+        }
+        bytes.extend(std::iter::repeat_n(0, stride - image.width * 3));
+    }
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+impl<T: Numeric> Array<T> {
+    /// Reads an image in one of supported formats, detected from its content: ASCII or
+    /// binary PGM and PPM (`P2`, `P3`, `P5`, `P6`) and uncompressed 24-bit BMP.
+    ///
+    /// Values are normalized to `[0, 1]`, samples exceeding the maximum value of PNM
+    /// images are clipped. PGM images have 1 channel and others 3 RGB
+    /// channels.
+    ///
+    /// * `reader` - Source of the data.
+    /// * `data_format` - Layout of the image: `[channels, height, width]` for `Nchw`
+    ///   and `[height, width, channels]` for `Nhwc`.
+    ///
+    /// Returns `Error::Format` if the data isn't a valid image of supported formats.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::DataFormat;
+    /// use neurust::Array;
+    ///
+    /// let pgm = "P2\n# 2x1 image\n2 1\n4\n1 4\n";
+    ///
+    /// assert_eq!(
+    ///     Array::read_image(pgm.as_bytes(), DataFormat::Nchw).unwrap(),
+    ///     Array::from_vec(vec![0.25, 1.], vec![1, 1, 2])
+    /// );
+    /// ```
+    pub fn read_image(mut reader: impl Read, data_format: DataFormat) -> Result<Array<T>> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        let image = match data.get(..2) {
+            Some(b"P2") | Some(b"P3") | Some(b"P5") | Some(b"P6") => read_pnm(&data)?,
+            Some(b"BM") => read_bmp(&data)?,
+            _ => return Err(Error::Format("unsupported image format".to_owned())),
+        };
+
+        let max_value = image.max_value as f64;
+        let array = Array::from_vec(
+            image
+                .samples
+                .iter()
+                .map(|&x| T::from(x as f64 / max_value).unwrap())
+                .collect(),
+            vec![image.height, image.width, image.channels],
+        );
+        Ok(match data_format {
+            DataFormat::Nchw => array.permute(&[2, 0, 1]),
+            DataFormat::Nhwc => array,
+        })
+    }
+
+    /// Loads an image from a file, see `Array::read_image`.
+    ///
+    /// * `path` - Path of the file.
+    /// * `data_format` - Layout of the image.
+    pub fn load_image(path: impl AsRef<Path>, data_format: DataFormat) -> Result<Array<T>> {
+        Array::read_image(File::open(path)?, data_format)
+    }
+
+    /// Writes an image with values in `[0, 1]`, stored with 8 bits per sample.
+    ///
+    /// Values outside of `[0, 1]` are clipped.
+    ///
+    /// * `writer` - Destination of the data.
+    /// * `data_format` - Layout of the image: `[channels, height, width]` for `Nchw`
+    ///   and `[height, width, channels]` for `Nhwc`.
+    /// * `image_format` - Format of the file.
+    ///
+    /// Returns `Error::Format` if the image is too large for the format.
+    ///
+    /// **Panics** if the array isn't 3-dimensional or doesn't have 1 or 3 channels.
+    pub fn write_image(
+        &self,
+        writer: &mut impl Write,
+        data_format: DataFormat,
+        image_format: ImageFormat,
+    ) -> Result<()> {
+        let array = match data_format {
+            DataFormat::Nchw if self.shape.len() == 3 => self.permute(&[1, 2, 0]),
+            _ => self.clone(),
+        };
+        if array.shape.len() != 3 || (array.shape[2] != 1 && array.shape[2] != 3) {
+            panic!(
+                "Only 3-dimensional arrays with 1 or 3 channels can be written as images. Got shape: {:?}",
+                self.shape
+            )
+        }
+        let image = RawImage {
+            height: array.shape[0],
+            width: array.shape[1],
+            channels: array.shape[2],
+            max_value: u8::MAX as u16,
+            samples: array
+                .data
+                .iter()
+                .map(|x| (x.to_f64().unwrap().clamp(0., 1.) * 255.).round() as u16)
+                .collect(),
+        };
+
+        let mut writer = BufWriter::new(writer);
+        match image_format {
+            ImageFormat::PnmAscii => write_pnm(&mut writer, &image, false)?,
+            ImageFormat::PnmBinary => write_pnm(&mut writer, &image, true)?,
+            ImageFormat::Bmp => write_bmp(&mut writer, &image)?,
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Saves an image to a file of a format matching its extension, see
+    /// `ImageFormat::from_path` and `Array::write_image`.
+    ///
+    /// * `path` - Path of the file, it's created or truncated.
+    /// * `data_format` - Layout of the image.
+    ///
+    /// Returns `Error::Format` if the extension isn't supported, a 3-channel image is
+    /// saved as `pgm` or a 1-channel image as `ppm`.
+    ///
+    /// **Panics** if the array isn't 3-dimensional or doesn't have 1 or 3 channels.
+    pub fn save_image(&self, path: impl AsRef<Path>, data_format: DataFormat) -> Result<()> {
+        let image_format = ImageFormat::from_path(&path).ok_or_else(|| {
+            Error::Format(format!(
+                "unsupported image extension of {}",
+                path.as_ref().display()
+            ))
+        })?;
+        let channels = match data_format {
+            DataFormat::Nchw => self.shape.first(),
+            DataFormat::Nhwc => self.shape.get(2),
+        };
+        if let (Some(required), Some(&channels)) = (required_channels(&path), channels) {
+            if channels != required {
+                return Err(Error::Format(format!(
+                    "images with {} channels can't be saved as {}",
+                    channels,
+                    path.as_ref().display()
+                )));
+            }
+        }
+        self.write_image(&mut File::create(path)?, data_format, image_format)
+    }
+}
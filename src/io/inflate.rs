@@ -0,0 +1,317 @@
+use crate::io::zip::crc32;
+use crate::io::{Error, Result};
+
+const MAX_BITS: usize = 15;
+const LENGTH_BASE: [usize; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [usize; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [usize; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [usize; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+// Order in which lengths of the code length alphabet are stored.
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+fn corrupted() -> Error {
+    Error::Format("corrupted deflate stream".to_owned())
+}
+
+fn too_large() -> Error {
+    Error::Format("deflate stream exceeds its declared size".to_owned())
+}
+
+// Reads bits starting from the least significant bit of every byte.
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn bits(&mut self, count: usize) -> Result<usize> {
+        let mut value = 0;
+        for i in 0..count {
+            let byte = self.data.get(self.position / 8).ok_or_else(corrupted)?;
+            value |= (((byte >> (self.position % 8)) & 1) as usize) << i;
+            self.position += 1;
+        }
+        Ok(value)
+    }
+
+    fn align_to_byte(&mut self) {
+        self.position = self.position.div_ceil(8) * 8;
+    }
+}
+
+// Canonical Huffman code described by numbers of codes of every length
+// and symbols ordered by their codes.
+struct Huffman {
+    counts: [usize; MAX_BITS + 1],
+    symbols: Vec<usize>,
+}
+
+impl Huffman {
+    fn new(lengths: &[usize]) -> Huffman {
+        let mut counts = [0; MAX_BITS + 1];
+        for &length in lengths {
+            counts[length] += 1;
+        }
+        counts[0] = 0;
+        let mut symbols = Vec::with_capacity(lengths.len());
+        for length in 1..=MAX_BITS {
+            symbols.extend(
+                lengths
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &symbol_length)| symbol_length == length)
+                    .map(|(symbol, _)| symbol),
+            );
+        }
+        Huffman { counts, symbols }
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<usize> {
+        let (mut code, mut first, mut index) = (0, 0, 0);
+        for &count in self.counts[1..].iter() {
+            code |= reader.bits(1)?;
+            if code < first + count {
+                return Ok(self.symbols[index + code - first]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(corrupted())
+    }
+}
+
+fn fixed_codes() -> (Huffman, Huffman) {
+    let mut lengths = [8; 288];
+    lengths[144..256].iter_mut().for_each(|length| *length = 9);
+    lengths[256..280].iter_mut().for_each(|length| *length = 7);
+    (Huffman::new(&lengths), Huffman::new(&[5; 30]))
+}
+
+fn dynamic_codes(reader: &mut BitReader) -> Result<(Huffman, Huffman)> {
+    let literals = reader.bits(5)? + 257;
+    let distances = reader.bits(5)? + 1;
+    let code_lengths = reader.bits(4)? + 4;
+    let mut lengths = [0; 19];
+    for &symbol in CODE_LENGTH_ORDER[..code_lengths].iter() {
+        lengths[symbol] = reader.bits(3)?;
+    }
+    let code_length_code = Huffman::new(&lengths);
+
+    let mut lengths = Vec::with_capacity(literals + distances);
+    while lengths.len() < literals + distances {
+        let symbol = code_length_code.decode(reader)?;
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol, 1),
+            16 => (*lengths.last().ok_or_else(corrupted)?, 3 + reader.bits(2)?),
+            17 => (0, 3 + reader.bits(3)?),
+            _ => (0, 11 + reader.bits(7)?),
+        };
+        lengths.extend(std::iter::repeat_n(value, repeat));
+    }
+    if lengths.len() != literals + distances {
+        return Err(corrupted());
+    }
+    Ok((
+        Huffman::new(&lengths[..literals]),
+        Huffman::new(&lengths[literals..]),
+    ))
+}
+
+fn inflate_block(
+    reader: &mut BitReader,
+    output: &mut Vec<u8>,
+    max_size: usize,
+    literal_code: &Huffman,
+    distance_code: &Huffman,
+) -> Result<()> {
+    loop {
+        let symbol = literal_code.decode(reader)?;
+        if symbol < 256 {
+            if output.len() >= max_size {
+                return Err(too_large());
+            }
+            output.push(symbol as u8);
+        } else if symbol == 256 {
+            return Ok(());
+        } else {
+            let symbol = symbol - 257;
+            if symbol >= LENGTH_BASE.len() {
+                return Err(corrupted());
+            }
+            let length = LENGTH_BASE[symbol] + reader.bits(LENGTH_EXTRA[symbol])?;
+            let symbol = distance_code.decode(reader)?;
+            if symbol >= DISTANCE_BASE.len() {
+                return Err(corrupted());
+            }
+            let distance = DISTANCE_BASE[symbol] + reader.bits(DISTANCE_EXTRA[symbol])?;
+            if distance > output.len() {
+                return Err(corrupted());
+            }
+            if length > max_size - output.len() {
+                return Err(too_large());
+            }
+            let start = output.len() - distance;
+            for i in 0..length {
+                output.push(output[start + i]);
+            }
+        }
+    }
+}
+
+// Decompresses raw DEFLATE data (RFC 1951). Fails as soon as the output
+// grows beyond `max_size` bytes.
+pub(crate) fn inflate(data: &[u8], max_size: usize) -> Result<Vec<u8>> {
+    let mut reader = BitReader { data, position: 0 };
+    let mut output = Vec::new();
+    loop {
+        let is_last = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => {
+                reader.align_to_byte();
+                let len = reader.bits(16)?;
+                if reader.bits(16)? != !len & 0xffff {
+                    return Err(corrupted());
+                }
+                let start = reader.position / 8;
+                let block = data.get(start..start + len).ok_or_else(corrupted)?;
+                if len > max_size - output.len() {
+                    return Err(too_large());
+                }
+                output.extend_from_slice(block);
+                reader.position += len * 8;
+            }
+            1 => {
+                let (literal_code, distance_code) = fixed_codes();
+                inflate_block(
+                    &mut reader,
+                    &mut output,
+                    max_size,
+                    &literal_code,
+                    &distance_code,
+                )?;
+            }
+            2 => {
+                let (literal_code, distance_code) = dynamic_codes(&mut reader)?;
+                inflate_block(
+                    &mut reader,
+                    &mut output,
+                    max_size,
+                    &literal_code,
+                    &distance_code,
+                )?;
+            }
+            _ => return Err(corrupted()),
+        }
+        if is_last {
+            return Ok(output);
+        }
+    }
+}
+
+// Gzip header flags.
+const FHCRC: u8 = 0x02;
+const FEXTRA: u8 = 0x04;
+const FNAME: u8 = 0x08;
+const FCOMMENT: u8 = 0x10;
+
+// Returns true if data starts with the gzip magic number.
+pub(crate) fn is_gzip(data: &[u8]) -> bool {
+    data.starts_with(&[0x1f, 0x8b])
+}
+
+// Decompresses a single-member gzip file (RFC 1952) and validates its checksum.
+pub(crate) fn gunzip(data: &[u8]) -> Result<Vec<u8>> {
+    let invalid = || Error::Format("invalid gzip data".to_owned());
+    if !is_gzip(data) || data.get(2) != Some(&8) {
+        return Err(invalid());
+    }
+    let flags = *data.get(3).ok_or_else(invalid)?;
+    let mut position = 10;
+    if flags & FEXTRA != 0 {
+        let len = data.get(position..position + 2).ok_or_else(invalid)?;
+        position += 2 + u16::from_le_bytes([len[0], len[1]]) as usize;
+    }
+    for flag in [FNAME, FCOMMENT].iter() {
+        if flags & flag != 0 {
+            let len = data
+                .get(position..)
+                .and_then(|rest| rest.iter().position(|&byte| byte == 0))
+                .ok_or_else(invalid)?;
+            position += len + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        position += 2;
+    }
+    if data.len() < position + 8 {
+        return Err(invalid());
+    }
+    let output = inflate(&data[position..], usize::MAX)?;
+    let trailer = &data[data.len() - 8..];
+    let checksum = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    let size = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+    if checksum != crc32(&output) || size != output.len() as u32 {
+        return Err(Error::Format("gzip checksum mismatch".to_owned()));
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inflate_stored_block() {
+        assert_eq!(
+            inflate(&[0x01, 0x03, 0x00, 0xfc, 0xff, b'a', b'b', b'c'], 3).unwrap(),
+            b"abc"
+        );
+    }
+
+    #[test]
+    fn test_inflate_fixed_codes() {
+        // zlib.compress(b"abcabcabc")[2:-4]
+        assert_eq!(
+            inflate(&[0x4b, 0x4c, 0x4a, 0x4e, 0x04, 0x23, 0x00], 9).unwrap(),
+            b"abcabcabc"
+        );
+    }
+
+    #[test]
+    fn test_inflate_exceeds_max_size() {
+        assert!(inflate(&[0x01, 0x03, 0x00, 0xfc, 0xff, b'a', b'b', b'c'], 2).is_err());
+        assert!(inflate(&[0x4b, 0x4c, 0x4a, 0x4e, 0x04, 0x23, 0x00], 8).is_err());
+        assert!(inflate(&[0x4b, 0x4c, 0x4a, 0x4e, 0x04, 0x23, 0x00], 2).is_err());
+    }
+
+    #[test]
+    fn test_gunzip() {
+        // gzip.compress(b"abcabcabc", mtime=0)
+        let data = [
+            0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x4b, 0x4c, 0x4a, 0x4e,
+            0x04, 0x23, 0x00, 0x18, 0x48, 0x2d, 0x46, 0x09, 0x00, 0x00, 0x00,
+        ];
+
+        assert_eq!(gunzip(&data).unwrap(), b"abcabcabc");
+        assert!(gunzip(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_inflate_corrupted() {
+        assert!(inflate(&[0x07], usize::MAX).is_err());
+    }
+}
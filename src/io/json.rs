@@ -0,0 +1,363 @@
+use crate::io::{Error, Result};
+use std::fmt::Write;
+
+// JSON value. Numbers keep their text so integers don't lose precision and objects
+// keep order of their members.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn as_usize(&self) -> Option<usize> {
+        match self {
+            Json::Number(number) => number.parse().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Json]> {
+        match self {
+            Json::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&[(String, Json)]> {
+        match self {
+            Json::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+
+    // Returns value of an object's member.
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        self.as_object()?
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value)
+    }
+}
+
+// Maximum nesting depth of arrays and objects, so malformed documents can't
+// overflow the stack of the recursive parser.
+const MAX_DEPTH: usize = 128;
+
+fn invalid(message: &str, position: usize) -> Error {
+    Error::Format(format!("invalid JSON: {} at byte {}", message, position))
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    position: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.position).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<()> {
+        self.skip_whitespace();
+        if self.peek() != Some(byte) {
+            return Err(invalid(
+                &format!("expected '{}'", byte as char),
+                self.position,
+            ));
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn literal(&mut self, literal: &str, value: Json) -> Result<Json> {
+        if self.text[self.position..].starts_with(literal.as_bytes()) {
+            self.position += literal.len();
+            Ok(value)
+        } else {
+            Err(invalid("unexpected token", self.position))
+        }
+    }
+
+    fn value(&mut self) -> Result<Json> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.nested(Parser::object),
+            Some(b'[') => self.nested(Parser::array),
+            Some(b'"') => Ok(Json::String(self.string()?)),
+            Some(b't') => self.literal("true", Json::Bool(true)),
+            Some(b'f') => self.literal("false", Json::Bool(false)),
+            Some(b'n') => self.literal("null", Json::Null),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            _ => Err(invalid("unexpected token", self.position)),
+        }
+    }
+
+    // Parses an array or an object, keeping track of the nesting depth.
+    fn nested(&mut self, parse: fn(&mut Parser<'a>) -> Result<Json>) -> Result<Json> {
+        if self.depth == MAX_DEPTH {
+            return Err(invalid("too deeply nested", self.position));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn object(&mut self) -> Result<Json> {
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.expect(b':')?;
+            members.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b'}') => {
+                    self.position += 1;
+                    return Ok(Json::Object(members));
+                }
+                _ => return Err(invalid("expected ',' or '}'", self.position)),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json> {
+        self.expect(b'[')?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(Json::Array(values));
+                }
+                _ => return Err(invalid("expected ',' or ']'", self.position)),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Json> {
+        let start = self.position;
+        while matches!(
+            self.peek(),
+            Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+        ) {
+            self.position += 1;
+        }
+        let number = std::str::from_utf8(&self.text[start..self.position]).unwrap();
+        if number.parse::<f64>().is_err() {
+            return Err(invalid("invalid number", start));
+        }
+        Ok(Json::Number(number.to_owned()))
+    }
+
+    fn hex_escape(&mut self) -> Result<u32> {
+        let digits = self
+            .text
+            .get(self.position..self.position + 4)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| invalid("invalid unicode escape", self.position))?;
+        self.position += 4;
+        Ok(digits)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+        loop {
+            let byte = self
+                .peek()
+                .ok_or_else(|| invalid("unterminated string", self.position))?;
+            self.position += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    let escaped = self
+                        .peek()
+                        .ok_or_else(|| invalid("unterminated string", self.position))?;
+                    self.position += 1;
+                    let character = match escaped {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let mut code = self.hex_escape()?;
+                            // surrogate pair
+                            if (0xd800..0xdc00).contains(&code)
+                                && self.text[self.position..].starts_with(b"\\u")
+                            {
+                                self.position += 2;
+                                let low = self.hex_escape()?;
+                                if !(0xdc00..0xe000).contains(&low) {
+                                    return Err(invalid("invalid unicode escape", self.position));
+                                }
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                            }
+                            char::from_u32(code)
+                                .ok_or_else(|| invalid("invalid unicode escape", self.position))?
+                        }
+                        _ => return Err(invalid("invalid escape", self.position)),
+                    };
+                    bytes.extend_from_slice(character.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                _ => bytes.push(byte),
+            }
+        }
+        String::from_utf8(bytes).map_err(|_| invalid("string is not valid UTF-8", self.position))
+    }
+}
+
+// Parses a JSON document.
+pub(crate) fn parse_json(text: &[u8]) -> Result<Json> {
+    let mut parser = Parser {
+        text,
+        position: 0,
+        depth: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.position != text.len() {
+        return Err(invalid("trailing characters", parser.position));
+    }
+    Ok(value)
+}
+
+// Formats a string as a JSON string literal.
+pub(crate) fn quote_json(string: &str) -> String {
+    let mut quoted = String::with_capacity(string.len() + 2);
+    quoted.push('"');
+    for character in string.chars() {
+        match character {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            character if (character as u32) < 0x20 => {
+                write!(quoted, "\\u{:04x}", character as u32).unwrap()
+            }
+            character => quoted.push(character),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_json() {
+        let json =
+            parse_json(br#" {"a": [1, -2.5e3, true, null], "b": {"c": "x\"A\n"}, "d": []} "#)
+                .unwrap();
+
+        assert_eq!(
+            json,
+            Json::Object(vec![
+                (
+                    "a".to_owned(),
+                    Json::Array(vec![
+                        Json::Number("1".to_owned()),
+                        Json::Number("-2.5e3".to_owned()),
+                        Json::Bool(true),
+                        Json::Null
+                    ])
+                ),
+                (
+                    "b".to_owned(),
+                    Json::Object(vec![("c".to_owned(), Json::String("x\"A\n".to_owned()))])
+                ),
+                ("d".to_owned(), Json::Array(vec![]))
+            ])
+        );
+        assert_eq!(
+            json.get("a").unwrap().as_array().unwrap()[0].as_usize(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_json() {
+        for text in [&b"{"[..], b"{\"a\" 1}", b"[1,]", b"\"abc", b"{} x", b"tru"] {
+            assert!(parse_json(text).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_nesting_depth() {
+        let nested = |depth| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+        assert!(parse_json(nested(MAX_DEPTH).as_bytes()).is_ok());
+        assert!(matches!(
+            parse_json(nested(MAX_DEPTH + 1).as_bytes()),
+            Err(Error::Format(_))
+        ));
+        assert!(matches!(
+            parse_json("[".repeat(200_000).as_bytes()),
+            Err(Error::Format(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_surrogate_pairs() {
+        assert_eq!(
+            parse_json(br#""\ud83d\ude00""#).unwrap(),
+            Json::String("\u{1f600}".to_owned())
+        );
+        for text in [&br#""\ud800\u0041""#[..], br#""\ud800""#, br#""\udc00""#] {
+            assert!(matches!(parse_json(text), Err(Error::Format(_))));
+        }
+    }
+
+    #[test]
+    fn test_quote_json() {
+        let string = "a\"b\\c\n\u{1}ż";
+
+        assert_eq!(quote_json(string), r#""a\"b\\c\n\u0001ż""#);
+        assert_eq!(
+            parse_json(quote_json(string).as_bytes()).unwrap(),
+            Json::String(string.to_owned())
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! Reading and writing arrays and variables from and to files.
+mod binary;
+mod checkpoint;
+mod csv;
+mod idx;
+mod image;
+mod inflate;
+mod json;
+mod npy;
+mod npz;
+mod safetensors;
+mod zip;
+
+pub use checkpoint::Checkpoint;
+pub use csv::{CsvColumns, CsvOptions, MissingValues};
+pub use idx::{load_idx_images, load_idx_labels, read_idx_images, read_idx_labels, LabelEncoding};
+pub use image::ImageFormat;
+pub use npz::{load_npz, read_npz, save_npz, write_npz};
+pub use safetensors::{
+    load_safetensors, save_safetensors, write_safetensors, SafeTensors, TensorInfo,
+};
+
+use crate::linalg::Numeric;
+use std::any::TypeId;
+use std::fmt;
+
+/// Error returned by reading and writing functions.
+///
+/// * `Io` - Underlying reader or writer failed.
+/// * `Format` - Data is malformed or uses an unsupported feature.
+/// * `ShapeMismatch` - Shape of a stored array differs from the expected one.
+/// * `DtypeMismatch` - Stored data type differs from the requested one.
+/// * `Missing` - Entry with a given name doesn't exist.
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    Format(String),
+    ShapeMismatch {
+        name: String,
+        expected: Vec<usize>,
+        found: Vec<usize>,
+    },
+    DtypeMismatch {
+        expected: Dtype,
+        found: Dtype,
+    },
+    Missing(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "I/O error: {}", error),
+            Error::Format(message) => write!(f, "Invalid format: {}", message),
+            Error::ShapeMismatch {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Shape mismatch of '{}': expected {:?}, found {:?}",
+                name, expected, found
+            ),
+            Error::DtypeMismatch { expected, found } => write!(
+                f,
+                "Data type mismatch: expected {:?}, found {:?}",
+                expected, found
+            ),
+            Error::Missing(name) => write!(f, "Missing entry '{}'", name),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Error {
+        Error::Io(error)
+    }
+}
+
+/// Result of reading and writing functions.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Data type of stored array elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dtype {
+    F32,
+    F64,
+}
+
+impl Dtype {
+    /// Returns data type corresponding to `T`.
+    ///
+    /// **Panics** if `T` is neither `f32` nor `f64`.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::io::Dtype;
+    ///
+    /// assert_eq!(Dtype::of::<f32>(), Dtype::F32);
+    /// ```
+    pub fn of<T: Numeric>() -> Dtype {
+        if TypeId::of::<T>() == TypeId::of::<f32>() {
+            Dtype::F32
+        } else if TypeId::of::<T>() == TypeId::of::<f64>() {
+            Dtype::F64
+        } else {
+            panic!("Only f32 and f64 arrays can be stored.")
+        }
+    }
+
+    /// Returns size of a single element in bytes.
+    pub fn size(self) -> usize {
+        match self {
+            Dtype::F32 => 4,
+            Dtype::F64 => 8,
+        }
+    }
+}
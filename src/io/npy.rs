@@ -0,0 +1,224 @@
+use crate::io::binary::{decode_values, encode_values, read_bytes};
+use crate::io::{Dtype, Error, Result};
+use crate::linalg::{Array, Numeric};
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+// Total length of the preamble and header is padded to a multiple of this value.
+const HEADER_ALIGNMENT: usize = 64;
+
+// Returns text following `key` and a colon in a header dictionary.
+fn get_header_value<'a>(header: &'a str, key: &str) -> Result<&'a str> {
+    [format!("'{}'", key), format!("\"{}\"", key)]
+        .iter()
+        .find_map(|quoted_key| {
+            header
+                .find(quoted_key.as_str())
+                .map(|i| i + quoted_key.len())
+        })
+        .and_then(|start| header[start..].trim_start().strip_prefix(':'))
+        .map(str::trim_start)
+        .ok_or_else(|| Error::Format(format!("missing '{}' in npy header", key)))
+}
+
+// Parses a header dictionary, e.g. `{'descr': '<f8', 'fortran_order': False, 'shape': (2, 3), }`,
+// into data type and shape.
+fn parse_header(header: &str) -> Result<(Dtype, Vec<usize>)> {
+    let descr = get_header_value(header, "descr")?;
+    let quote = descr.chars().next().unwrap_or(' ');
+    let descr = descr
+        .get(1..)
+        .filter(|_| quote == '\'' || quote == '"')
+        .and_then(|rest| rest.split(quote).next())
+        .ok_or_else(|| Error::Format("invalid 'descr' in npy header".to_owned()))?;
+    let dtype = match descr {
+        "<f4" | "=f4" => Dtype::F32,
+        "<f8" | "=f8" => Dtype::F64,
+        _ => {
+            return Err(Error::Format(format!(
+                "unsupported data type '{}', only little-endian f4 and f8 are supported",
+                descr
+            )))
+        }
+    };
+
+    let fortran_order = get_header_value(header, "fortran_order")?;
+    if fortran_order.starts_with("True") {
+        return Err(Error::Format(
+            "Fortran order isn't supported, only C order".to_owned(),
+        ));
+    } else if !fortran_order.starts_with("False") {
+        return Err(Error::Format(
+            "invalid 'fortran_order' in npy header".to_owned(),
+        ));
+    }
+
+    let shape = get_header_value(header, "shape")?;
+    let shape = shape
+        .strip_prefix('(')
+        .and_then(|rest| rest.split(')').next())
+        .ok_or_else(|| Error::Format("invalid 'shape' in npy header".to_owned()))?;
+    let mut dims = Vec::new();
+    for dim in shape
+        .split(',')
+        .map(str::trim)
+        .filter(|dim| !dim.is_empty())
+    {
+        dims.push(
+            dim.trim_end_matches('L')
+                .parse()
+                .map_err(|_| Error::Format(format!("invalid dimension '{}'", dim)))?,
+        );
+    }
+    if dims.contains(&0) {
+        return Err(Error::Format(format!("empty npy data of shape {:?}", dims)));
+    }
+    // scalars are stored with an empty shape
+    if dims.is_empty() {
+        dims.push(1);
+    }
+    Ok((dtype, dims))
+}
+
+// Creates a header dictionary padded with spaces and terminated with a new line.
+fn format_header(dtype: Dtype, shape: &[usize], preamble_len: usize) -> String {
+    let dims: Vec<String> = shape.iter().map(|dim| dim.to_string()).collect();
+    let shape = if dims.len() == 1 {
+        format!("({},)", dims[0])
+    } else {
+        format!("({})", dims.join(", "))
+    };
+    let descr = match dtype {
+        Dtype::F32 => "<f4",
+        Dtype::F64 => "<f8",
+    };
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}",
+        descr, shape
+    );
+    let len = preamble_len + header.len() + 1;
+    let padded_len = len.div_ceil(HEADER_ALIGNMENT) * HEADER_ALIGNMENT;
+    header.push_str(&" ".repeat(padded_len - len));
+    header.push('\n');
+    header
+}
+
+impl<T: Numeric> Array<T> {
+    /// Reads an array in NumPy `.npy` format.
+    ///
+    /// Only non-empty little-endian `float32` and `float64` arrays in C order are supported.
+    /// Scalars (arrays with empty shape) are read as arrays of shape `[1]`.
+    ///
+    /// * `reader` - Source of the data.
+    ///
+    /// Returns `Error::DtypeMismatch` if stored data type differs from `T` and
+    /// `Error::Format` if data is malformed or unsupported.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::Array;
+    ///
+    /// let a = Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![2, 3]);
+    /// let mut buffer = Vec::new();
+    /// a.write_npy(&mut buffer).unwrap();
+    ///
+    /// assert_eq!(Array::<f64>::read_npy(&mut buffer.as_slice()).unwrap(), a);
+    /// ```
+    pub fn read_npy(reader: &mut impl Read) -> Result<Array<T>> {
+        if read_bytes(reader, MAGIC.len())? != MAGIC {
+            return Err(Error::Format("not a npy file".to_owned()));
+        }
+        let version = read_bytes(reader, 2)?;
+        let header_len = match version[0] {
+            1 => u16::from_le_bytes(read_bytes(reader, 2)?.try_into().unwrap()) as usize,
+            2 | 3 => u32::from_le_bytes(read_bytes(reader, 4)?.try_into().unwrap()) as usize,
+            major => {
+                return Err(Error::Format(format!(
+                    "unsupported npy version {}.{}",
+                    major, version[1]
+                )))
+            }
+        };
+        let header = String::from_utf8(read_bytes(reader, header_len)?)
+            .map_err(|_| Error::Format("npy header is not valid UTF-8".to_owned()))?;
+        let (found, shape) = parse_header(&header)?;
+        let expected = Dtype::of::<T>();
+        if found != expected {
+            return Err(Error::DtypeMismatch { expected, found });
+        }
+        let len = shape
+            .iter()
+            .try_fold(expected.size(), |acc, &dim| acc.checked_mul(dim))
+            .ok_or_else(|| Error::Format("array is too large".to_owned()))?;
+        let data = decode_values(&read_bytes(reader, len)?, expected);
+        Ok(Array::from_vec(data, shape))
+    }
+
+    /// Writes the array in NumPy `.npy` format (version 1.0, little-endian, C order).
+    ///
+    /// * `writer` - Destination of the data.
+    pub fn write_npy(&self, writer: &mut impl Write) -> Result<()> {
+        let dtype = Dtype::of::<T>();
+        let preamble_len = MAGIC.len() + 4;
+        let header = format_header(dtype, &self.shape, preamble_len);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[1, 0])?;
+        writer.write_all(&(header.len() as u16).to_le_bytes())?;
+        writer.write_all(header.as_bytes())?;
+        writer.write_all(&encode_values(&self.data, dtype))?;
+        Ok(())
+    }
+
+    /// Loads an array from a `.npy` file, see `Array::read_npy`.
+    ///
+    /// * `path` - Path of the file.
+    pub fn load_npy(path: impl AsRef<Path>) -> Result<Array<T>> {
+        Array::read_npy(&mut BufReader::new(File::open(path)?))
+    }
+
+    /// Saves the array to a `.npy` file, see `Array::write_npy`.
+    ///
+    /// * `path` - Path of the file, it's created or truncated.
+    pub fn save_npy(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_npy(&mut writer)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_header() {
+        assert_eq!(
+            parse_header("{'descr': '<f8', 'fortran_order': False, 'shape': (2, 3), }").unwrap(),
+            (Dtype::F64, vec![2, 3])
+        );
+        assert_eq!(
+            parse_header("{\"shape\": (4,), \"fortran_order\": False, \"descr\": \"<f4\"}")
+                .unwrap(),
+            (Dtype::F32, vec![4])
+        );
+        assert_eq!(
+            parse_header("{'descr': '<f8', 'fortran_order': False, 'shape': (), }").unwrap(),
+            (Dtype::F64, vec![1])
+        );
+        assert!(parse_header("{'descr': '>f8', 'fortran_order': False, 'shape': (2,), }").is_err());
+        assert!(parse_header("{'descr': '<f8', 'fortran_order': True, 'shape': (2,), }").is_err());
+    }
+
+    #[test]
+    fn test_format_header_alignment() {
+        let header = format_header(Dtype::F32, &[3], 10);
+
+        assert_eq!((header.len() + 10) % HEADER_ALIGNMENT, 0);
+        assert!(header.starts_with("{'descr': '<f4', 'fortran_order': False, 'shape': (3,), }"));
+        assert!(header.ends_with(" \n"));
+    }
+}
@@ -0,0 +1,85 @@
+use crate::io::zip::{read_zip, write_zip};
+use crate::io::{Error, Result};
+use crate::linalg::{Array, Numeric};
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+/// Reads named arrays from a NumPy `.npz` archive.
+///
+/// Arrays are returned in the order they are stored in the archive, named after their
+/// files without the `.npy` extension. Archives created by both `numpy.savez` and
+/// `numpy.savez_compressed` are supported, see `Array::read_npy` for supported arrays.
+///
+/// * `reader` - Source of the data.
+///
+/// Returns `Error::DtypeMismatch` if any array has different data type than `T` and
+/// `Error::Format` if data is malformed or unsupported.
+///
+/// # Examples
+/// ```
+/// use neurust::io::{read_npz, write_npz};
+/// use neurust::Array;
+///
+/// let x = Array::from_vec(vec![1., 2., 3., 4.], vec![2, 2]);
+/// let y = Array::from_vec(vec![0., 1.], vec![2]);
+/// let mut buffer = Vec::new();
+/// write_npz(&mut buffer, &[("x", &x), ("y", &y)]).unwrap();
+///
+/// let arrays = read_npz::<f64>(&mut buffer.as_slice()).unwrap();
+///
+/// assert_eq!(arrays, vec![("x".to_owned(), x), ("y".to_owned(), y)]);
+/// ```
+pub fn read_npz<T: Numeric>(reader: &mut impl Read) -> Result<Vec<(String, Array<T>)>> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data)?;
+    read_zip(&data)?
+        .into_iter()
+        .map(|(name, content)| {
+            let name = name.strip_suffix(".npy").map(str::to_owned).unwrap_or(name);
+            let array = Array::read_npy(&mut content.as_slice()).map_err(|error| match error {
+                Error::Format(message) => Error::Format(format!("{} in '{}'", message, name)),
+                error => error,
+            })?;
+            Ok((name, array))
+        })
+        .collect()
+}
+
+/// Writes named arrays as an uncompressed NumPy `.npz` archive, like `numpy.savez`.
+///
+/// * `writer` - Destination of the data.
+/// * `arrays` - Arrays together with their names, stored as `<name>.npy` files.
+///
+/// Returns `Error::Format` if names are repeated or the archive would exceed 4 GiB.
+pub fn write_npz<T: Numeric>(writer: &mut impl Write, arrays: &[(&str, &Array<T>)]) -> Result<()> {
+    let mut files: Vec<(String, Vec<u8>)> = Vec::with_capacity(arrays.len());
+    for (name, array) in arrays {
+        let file_name = format!("{}.npy", name);
+        if files.iter().any(|(other, _)| *other == file_name) {
+            return Err(Error::Format(format!("duplicated array name '{}'", name)));
+        }
+        let mut content = Vec::new();
+        array.write_npy(&mut content)?;
+        files.push((file_name, content));
+    }
+    write_zip(writer, &files)
+}
+
+/// Loads named arrays from a `.npz` file, see `read_npz`.
+///
+/// * `path` - Path of the file.
+pub fn load_npz<T: Numeric>(path: impl AsRef<Path>) -> Result<Vec<(String, Array<T>)>> {
+    read_npz(&mut File::open(path)?)
+}
+
+/// Saves named arrays to a `.npz` file, see `write_npz`.
+///
+/// * `path` - Path of the file, it's created or truncated.
+/// * `arrays` - Arrays together with their names.
+pub fn save_npz<T: Numeric>(path: impl AsRef<Path>, arrays: &[(&str, &Array<T>)]) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_npz(&mut writer, arrays)?;
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,345 @@
+use crate::io::binary::{decode_values, encode_values};
+use crate::io::json::{parse_json, quote_json, Json};
+use crate::io::{Dtype, Error, Result};
+use crate::linalg::{Array, Numeric};
+use std::convert::{TryFrom, TryInto};
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+const METADATA_KEY: &str = "__metadata__";
+// Header is padded with spaces, so the data buffer is aligned to this number of bytes.
+const HEADER_ALIGNMENT: usize = 8;
+// Maximum length of the header, the same as in the reference implementation.
+const MAX_HEADER_LEN: u64 = 100_000_000;
+
+/// Description of a single array stored in a safetensors file.
+///
+/// * `dtype` - Data type of elements.
+/// * `shape` - Shape of the array.
+/// * `data_offsets` - Range of the array's bytes, relative to the start of the data buffer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TensorInfo {
+    pub dtype: Dtype,
+    pub shape: Vec<usize>,
+    pub data_offsets: (usize, usize),
+}
+
+/// Arrays in safetensors format borrowed from a byte buffer.
+///
+/// Safetensors files consist of an 8-byte little-endian header length, a JSON header
+/// describing arrays and a buffer with raw little-endian data. Only the header is parsed
+/// and validated on creation, arrays are decoded on request, so a memory-mapped file can
+/// be passed without reading it whole.
+///
+/// # Examples
+/// ```
+/// use neurust::io::{write_safetensors, Dtype, SafeTensors};
+/// use neurust::Array;
+///
+/// let weights = Array::from_vec(vec![1f32, 2., 3., 4.], vec![2, 2]);
+/// let mut buffer = Vec::new();
+/// write_safetensors(&mut buffer, &[("weights", &weights)], &[("format", "pt")]).unwrap();
+///
+/// let tensors = SafeTensors::parse(&buffer).unwrap();
+///
+/// assert_eq!(tensors.names(), vec!["weights"]);
+/// assert_eq!(tensors.info("weights").unwrap().dtype, Dtype::F32);
+/// assert_eq!(tensors.array::<f32>("weights").unwrap(), weights);
+/// assert_eq!(tensors.metadata(), &[("format".to_owned(), "pt".to_owned())]);
+/// ```
+#[derive(Debug)]
+pub struct SafeTensors<'a> {
+    tensors: Vec<(String, TensorInfo)>,
+    metadata: Vec<(String, String)>,
+    data: &'a [u8],
+}
+
+impl<'a> SafeTensors<'a> {
+    /// Parses and validates the header of safetensors data.
+    ///
+    /// Validation checks that the header isn't longer than 100 MB, data types are supported,
+    /// sizes of arrays match their shapes and arrays exactly cover the data buffer without
+    /// overlapping.
+    ///
+    /// * `bytes` - Whole content of a safetensors file, e.g. memory-mapped.
+    ///
+    /// Returns `Error::Format` if data is malformed or unsupported.
+    pub fn parse(bytes: &'a [u8]) -> Result<SafeTensors<'a>> {
+        let header_len = bytes
+            .get(..8)
+            .map(|len| u64::from_le_bytes(len.try_into().unwrap()))
+            .ok_or_else(|| Error::Format("missing safetensors header length".to_owned()))?;
+        if header_len > MAX_HEADER_LEN {
+            return Err(Error::Format(format!(
+                "safetensors header of {} bytes exceeds the limit of {} bytes",
+                header_len, MAX_HEADER_LEN
+            )));
+        }
+        let header = usize::try_from(header_len)
+            .ok()
+            .and_then(|len| bytes.get(8..len.checked_add(8)?))
+            .ok_or_else(|| Error::Format("safetensors header exceeds data".to_owned()))?;
+        let header = parse_json(header)?;
+        let members = header
+            .as_object()
+            .ok_or_else(|| Error::Format("safetensors header must be an object".to_owned()))?;
+        let data = &bytes[8 + header_len as usize..];
+
+        let mut tensors = Vec::with_capacity(members.len());
+        let mut metadata = Vec::new();
+        for (name, value) in members {
+            if name == METADATA_KEY {
+                metadata = parse_metadata(value)?;
+            } else if tensors.iter().any(|(other, _)| other == name) {
+                return Err(Error::Format(format!("duplicated tensor '{}'", name)));
+            } else {
+                tensors.push((name.clone(), parse_tensor_info(name, value)?));
+            }
+        }
+
+        let mut ranges: Vec<(usize, usize)> =
+            tensors.iter().map(|(_, info)| info.data_offsets).collect();
+        ranges.sort_unstable();
+        let mut end = 0;
+        for (begin, range_end) in ranges {
+            if begin != end {
+                return Err(Error::Format(
+                    "tensors must cover the data buffer without gaps or overlaps".to_owned(),
+                ));
+            }
+            end = range_end;
+        }
+        if end != data.len() {
+            return Err(Error::Format(format!(
+                "tensors occupy {} bytes of {} bytes data buffer",
+                end,
+                data.len()
+            )));
+        }
+        Ok(SafeTensors {
+            tensors,
+            metadata,
+            data,
+        })
+    }
+
+    /// Returns names of arrays in order of the header.
+    pub fn names(&self) -> Vec<&str> {
+        self.tensors.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Returns description of an array.
+    pub fn info(&self, name: &str) -> Option<&TensorInfo> {
+        self.tensors
+            .iter()
+            .find(|(tensor_name, _)| tensor_name == name)
+            .map(|(_, info)| info)
+    }
+
+    /// Returns free-form string metadata of the file.
+    pub fn metadata(&self) -> &[(String, String)] {
+        &self.metadata
+    }
+
+    /// Decodes an array with a given name.
+    ///
+    /// * `name` - Name of the array.
+    ///
+    /// Returns `Error::Missing` if there is no such array, `Error::DtypeMismatch`
+    /// if the array has different data type than `T` and `Error::Format` if the array
+    /// is empty (has a zero dimension), as such arrays can't be represented by `Array`.
+    pub fn array<T: Numeric>(&self, name: &str) -> Result<Array<T>> {
+        let info = self
+            .info(name)
+            .ok_or_else(|| Error::Missing(name.to_owned()))?;
+        let expected = Dtype::of::<T>();
+        if info.dtype != expected {
+            return Err(Error::DtypeMismatch {
+                expected,
+                found: info.dtype,
+            });
+        }
+        if info.shape.contains(&0) {
+            return Err(Error::Format(format!(
+                "tensor '{}' of shape {:?} is empty",
+                name, info.shape
+            )));
+        }
+        let (begin, end) = info.data_offsets;
+        Ok(Array::from_vec(
+            decode_values(&self.data[begin..end], expected),
+            array_shape(&info.shape),
+        ))
+    }
+
+    /// Decodes all arrays in order of the header, see `SafeTensors::array`.
+    pub fn arrays<T: Numeric>(&self) -> Result<Vec<(String, Array<T>)>> {
+        self.tensors
+            .iter()
+            .map(|(name, _)| Ok((name.clone(), self.array(name)?)))
+            .collect()
+    }
+}
+
+// Arrays have at least one dimension, so scalars are read as arrays of shape `[1]`.
+fn array_shape(shape: &[usize]) -> Vec<usize> {
+    if shape.is_empty() {
+        vec![1]
+    } else {
+        shape.to_vec()
+    }
+}
+
+fn parse_tensor_info(name: &str, value: &Json) -> Result<TensorInfo> {
+    let invalid = |field: &str| Error::Format(format!("invalid '{}' of tensor '{}'", field, name));
+    let dtype = match value.get("dtype").and_then(Json::as_str) {
+        Some("F32") => Dtype::F32,
+        Some("F64") => Dtype::F64,
+        Some(dtype) => {
+            return Err(Error::Format(format!(
+                "unsupported data type '{}' of tensor '{}', only F32 and F64 are supported",
+                dtype, name
+            )))
+        }
+        None => return Err(invalid("dtype")),
+    };
+    let shape = value
+        .get("shape")
+        .and_then(Json::as_array)
+        .and_then(|dims| {
+            dims.iter()
+                .map(Json::as_usize)
+                .collect::<Option<Vec<usize>>>()
+        })
+        .ok_or_else(|| invalid("shape"))?;
+    let offsets = value
+        .get("data_offsets")
+        .and_then(Json::as_array)
+        .and_then(|offsets| {
+            offsets
+                .iter()
+                .map(Json::as_usize)
+                .collect::<Option<Vec<usize>>>()
+        })
+        .filter(|offsets| offsets.len() == 2 && offsets[0] <= offsets[1])
+        .ok_or_else(|| invalid("data_offsets"))?;
+    let len = shape
+        .iter()
+        .try_fold(dtype.size(), |acc, &dim| acc.checked_mul(dim));
+    if len != Some(offsets[1] - offsets[0]) {
+        return Err(Error::Format(format!(
+            "size of tensor '{}' doesn't match its shape {:?}",
+            name, shape
+        )));
+    }
+    Ok(TensorInfo {
+        dtype,
+        shape,
+        data_offsets: (offsets[0], offsets[1]),
+    })
+}
+
+fn parse_metadata(value: &Json) -> Result<Vec<(String, String)>> {
+    value
+        .as_object()
+        .ok_or_else(|| Error::Format("metadata must be an object".to_owned()))?
+        .iter()
+        .map(|(key, value)| {
+            value
+                .as_str()
+                .map(|value| (key.clone(), value.to_owned()))
+                .ok_or_else(|| Error::Format(format!("metadata '{}' must be a string", key)))
+        })
+        .collect()
+}
+
+/// Writes named arrays in safetensors format.
+///
+/// Arrays are stored in the given order. The header is padded with spaces, so the data
+/// buffer starts at an offset aligned to 8 bytes.
+///
+/// * `writer` - Destination of the data.
+/// * `arrays` - Arrays together with their names.
+/// * `metadata` - Free-form string metadata stored under `__metadata__` key.
+///
+/// Returns `Error::Format` if names are repeated or equal to `__metadata__`.
+pub fn write_safetensors<T: Numeric>(
+    writer: &mut impl Write,
+    arrays: &[(&str, &Array<T>)],
+    metadata: &[(&str, &str)],
+) -> Result<()> {
+    let dtype = Dtype::of::<T>();
+    let dtype_name = match dtype {
+        Dtype::F32 => "F32",
+        Dtype::F64 => "F64",
+    };
+    let mut entries = Vec::with_capacity(arrays.len() + 1);
+    if !metadata.is_empty() {
+        let members: Vec<String> = metadata
+            .iter()
+            .map(|(key, value)| format!("{}:{}", quote_json(key), quote_json(value)))
+            .collect();
+        entries.push(format!(
+            "{}:{{{}}}",
+            quote_json(METADATA_KEY),
+            members.join(",")
+        ));
+    }
+    let mut offset = 0;
+    for (i, (name, array)) in arrays.iter().enumerate() {
+        if *name == METADATA_KEY || arrays[..i].iter().any(|(other, _)| other == name) {
+            return Err(Error::Format(format!(
+                "invalid or duplicated name '{}'",
+                name
+            )));
+        }
+        let len = array.data.len() * dtype.size();
+        let shape: Vec<String> = array.shape.iter().map(|dim| dim.to_string()).collect();
+        entries.push(format!(
+            "{}:{{\"dtype\":\"{}\",\"shape\":[{}],\"data_offsets\":[{},{}]}}",
+            quote_json(name),
+            dtype_name,
+            shape.join(","),
+            offset,
+            offset + len
+        ));
+        offset += len;
+    }
+    let mut header = format!("{{{}}}", entries.join(","));
+    header.push_str(
+        &" ".repeat((HEADER_ALIGNMENT - header.len() % HEADER_ALIGNMENT) % HEADER_ALIGNMENT),
+    );
+
+    writer.write_all(&(header.len() as u64).to_le_bytes())?;
+    writer.write_all(header.as_bytes())?;
+    for (_, array) in arrays {
+        writer.write_all(&encode_values(&array.data, dtype))?;
+    }
+    Ok(())
+}
+
+/// Loads all arrays from a safetensors file, see `SafeTensors`.
+///
+/// * `path` - Path of the file.
+pub fn load_safetensors<T: Numeric>(path: impl AsRef<Path>) -> Result<Vec<(String, Array<T>)>> {
+    let mut bytes = Vec::new();
+    File::open(path)?.read_to_end(&mut bytes)?;
+    SafeTensors::parse(&bytes)?.arrays()
+}
+
+/// Saves named arrays to a safetensors file, see `write_safetensors`.
+///
+/// * `path` - Path of the file, it's created or truncated.
+/// * `arrays` - Arrays together with their names.
+/// * `metadata` - Free-form string metadata.
+pub fn save_safetensors<T: Numeric>(
+    path: impl AsRef<Path>,
+    arrays: &[(&str, &Array<T>)],
+    metadata: &[(&str, &str)],
+) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_safetensors(&mut writer, arrays, metadata)?;
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,290 @@
+use crate::io::inflate::inflate;
+use crate::io::{Error, Result};
+use std::convert::{TryFrom, TryInto};
+use std::io::Write;
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+const ZIP64_EXTRA_FIELD_ID: u16 = 0x0001;
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+const VERSION: u16 = 20;
+
+// Computes CRC-32 checksum (IEEE 802.3 polynomial) used by zip archives.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut table = [0u32; 256];
+    for (i, entry) in table.iter_mut().enumerate() {
+        let mut value = i as u32;
+        for _ in 0..8 {
+            value = if value & 1 == 1 {
+                0xedb8_8320 ^ (value >> 1)
+            } else {
+                value >> 1
+            };
+        }
+        *entry = value;
+    }
+    !data.iter().fold(!0u32, |crc, &byte| {
+        table[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+fn unexpected_end() -> Error {
+    Error::Format("unexpected end of zip archive".to_owned())
+}
+
+// Adds offsets and sizes read from an archive, which may be arbitrarily large.
+fn add(a: usize, b: usize) -> Result<usize> {
+    a.checked_add(b).ok_or_else(unexpected_end)
+}
+
+fn to_usize(value: u64) -> Result<usize> {
+    usize::try_from(value).map_err(|_| unexpected_end())
+}
+
+fn bytes_at(data: &[u8], offset: usize, len: usize) -> Result<&[u8]> {
+    data.get(offset..add(offset, len)?)
+        .ok_or_else(unexpected_end)
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Result<u16> {
+    bytes_at(data, offset, 2).map(|bytes| u16::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Result<u32> {
+    bytes_at(data, offset, 4).map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn u64_at(data: &[u8], offset: usize) -> Result<u64> {
+    bytes_at(data, offset, 8).map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+// Replaces sizes and offset saturated at `u32::MAX` with values from a zip64 extra field.
+fn apply_zip64_extra_field(extra: &[u8], values: &mut [&mut u64]) -> Result<()> {
+    let mut offset = 0;
+    while offset + 4 <= extra.len() {
+        let id = u16_at(extra, offset)?;
+        let len = u16_at(extra, offset + 2)? as usize;
+        if id == ZIP64_EXTRA_FIELD_ID {
+            let mut field_offset = offset + 4;
+            for value in values
+                .iter_mut()
+                .filter(|value| ***value == u32::MAX as u64)
+            {
+                **value = u64_at(extra, field_offset)?;
+                field_offset += 8;
+            }
+            return Ok(());
+        }
+        offset += 4 + len;
+    }
+    Ok(())
+}
+
+// Reads all files of a zip archive. Stored and deflated files are supported.
+pub(crate) fn read_zip(data: &[u8]) -> Result<Vec<(String, Vec<u8>)>> {
+    let end = (0..data.len().saturating_sub(21))
+        .rev()
+        .find(|&offset| u32_at(data, offset).ok() == Some(END_OF_CENTRAL_DIRECTORY_SIGNATURE))
+        .ok_or_else(|| Error::Format("not a zip archive".to_owned()))?;
+    let count = u16_at(data, end + 10)? as usize;
+    let mut offset = to_usize(u32_at(data, end + 16)? as u64)?;
+
+    let mut files = Vec::with_capacity(count);
+    for _ in 0..count {
+        if u32_at(data, offset)? != CENTRAL_HEADER_SIGNATURE {
+            return Err(Error::Format("invalid zip central directory".to_owned()));
+        }
+        let method = u16_at(data, add(offset, 10)?)?;
+        let crc = u32_at(data, add(offset, 16)?)?;
+        let mut compressed_size = u32_at(data, add(offset, 20)?)? as u64;
+        let mut size = u32_at(data, add(offset, 24)?)? as u64;
+        let name_len = u16_at(data, add(offset, 28)?)? as usize;
+        let extra_len = u16_at(data, add(offset, 30)?)? as usize;
+        let comment_len = u16_at(data, add(offset, 32)?)? as usize;
+        let mut header_offset = u32_at(data, add(offset, 42)?)? as u64;
+        let name_start = add(offset, 46)?;
+        let name = String::from_utf8(bytes_at(data, name_start, name_len)?.to_vec())
+            .map_err(|_| Error::Format("invalid zip file name".to_owned()))?;
+        let extra_start = add(name_start, name_len)?;
+        let extra = bytes_at(data, extra_start, extra_len)?;
+        apply_zip64_extra_field(
+            extra,
+            &mut [&mut size, &mut compressed_size, &mut header_offset],
+        )?;
+        offset = add(add(extra_start, extra_len)?, comment_len)?;
+
+        let header_offset = to_usize(header_offset)?;
+        if u32_at(data, header_offset)? != LOCAL_HEADER_SIGNATURE {
+            return Err(Error::Format("invalid zip local header".to_owned()));
+        }
+        let local_name_len = u16_at(data, add(header_offset, 26)?)? as usize;
+        let local_extra_len = u16_at(data, add(header_offset, 28)?)? as usize;
+        let data_start = add(
+            add(add(header_offset, 30)?, local_name_len)?,
+            local_extra_len,
+        )?;
+        let compressed = bytes_at(data, data_start, to_usize(compressed_size)?)?;
+        let content = match method {
+            STORED => compressed.to_vec(),
+            DEFLATED => inflate(compressed, to_usize(size)?)?,
+            _ => {
+                return Err(Error::Format(format!(
+                    "unsupported zip compression method {} of '{}'",
+                    method, name
+                )))
+            }
+        };
+        if content.len() as u64 != size || crc32(&content) != crc {
+            return Err(Error::Format(format!("corrupted zip file '{}'", name)));
+        }
+        files.push((name, content));
+    }
+    Ok(files)
+}
+
+// Writes files as an uncompressed zip archive.
+pub(crate) fn write_zip(writer: &mut impl Write, files: &[(String, Vec<u8>)]) -> Result<()> {
+    let too_large = || Error::Format("zip archives larger than 4 GiB aren't supported".to_owned());
+    let mut central_directory = Vec::new();
+    let mut offset = 0usize;
+    for (name, content) in files {
+        let crc = crc32(content);
+        let size: u32 = content.len().try_into().map_err(|_| too_large())?;
+        let header_offset: u32 = offset.try_into().map_err(|_| too_large())?;
+        let mut header = Vec::with_capacity(30 + name.len());
+        header.extend_from_slice(&LOCAL_HEADER_SIGNATURE.to_le_bytes());
+        // version, flags, compression method, modification time and date
+        for value in [VERSION, 0, STORED, 0, 0x21] {
+            header.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in [crc, size, size] {
+            header.extend_from_slice(&value.to_le_bytes());
+        }
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(name.as_bytes());
+        writer.write_all(&header)?;
+        writer.write_all(content)?;
+
+        central_directory.extend_from_slice(&CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+        central_directory.extend_from_slice(&VERSION.to_le_bytes());
+        // fields shared with the local header, from version needed to extra field length
+        central_directory.extend_from_slice(&header[4..30]);
+        // comment length, disk number, internal and external attributes
+        central_directory.extend_from_slice(&[0; 10]);
+        central_directory.extend_from_slice(&header_offset.to_le_bytes());
+        central_directory.extend_from_slice(name.as_bytes());
+        offset += header.len() + content.len();
+    }
+    let count: u16 = files.len().try_into().map_err(|_| too_large())?;
+    let directory_offset: u32 = offset.try_into().map_err(|_| too_large())?;
+    writer.write_all(&central_directory)?;
+    writer.write_all(&END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes())?;
+    for value in [0, 0, count, count] {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    writer.write_all(&(central_directory.len() as u32).to_le_bytes())?;
+    writer.write_all(&directory_offset.to_le_bytes())?;
+    writer.write_all(&0u16.to_le_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn test_zip_roundtrip() {
+        let files = vec![
+            ("a.txt".to_owned(), b"first".to_vec()),
+            ("b.txt".to_owned(), Vec::new()),
+        ];
+        let mut buffer = Vec::new();
+        write_zip(&mut buffer, &files).unwrap();
+
+        assert_eq!(read_zip(&buffer).unwrap(), files);
+    }
+
+    fn single_file_archive() -> Vec<u8> {
+        let mut buffer = Vec::new();
+        write_zip(&mut buffer, &[("a.txt".to_owned(), b"first".to_vec())]).unwrap();
+        buffer
+    }
+
+    // Offset of the only central directory header in `single_file_archive`.
+    const CENTRAL_HEADER: usize = 30 + 5 + 5;
+
+    fn zip64_archive(size: u64, compressed_size: u64, header_offset: u64) -> Vec<u8> {
+        let buffer = single_file_archive();
+        let mut archive = buffer[..CENTRAL_HEADER].to_vec();
+        let mut central_header = buffer[CENTRAL_HEADER..CENTRAL_HEADER + 46].to_vec();
+        for range in [20..24, 24..28, 42..46] {
+            central_header[range].copy_from_slice(&u32::MAX.to_le_bytes());
+        }
+        central_header[30..32].copy_from_slice(&28u16.to_le_bytes());
+        archive.extend_from_slice(&central_header);
+        archive.extend_from_slice(b"a.txt");
+        archive.extend_from_slice(&ZIP64_EXTRA_FIELD_ID.to_le_bytes());
+        archive.extend_from_slice(&24u16.to_le_bytes());
+        for value in [size, compressed_size, header_offset] {
+            archive.extend_from_slice(&value.to_le_bytes());
+        }
+        let mut end = buffer[buffer.len() - 22..].to_vec();
+        end[12..16].copy_from_slice(&(46 + 5 + 28u32).to_le_bytes());
+        archive.extend_from_slice(&end);
+        archive
+    }
+
+    #[test]
+    fn test_read_zip64() {
+        let archive = zip64_archive(5, 5, 0);
+
+        assert_eq!(
+            read_zip(&archive).unwrap(),
+            vec![("a.txt".to_owned(), b"first".to_vec())]
+        );
+    }
+
+    #[test]
+    fn test_read_zip_huge_values() {
+        for archive in [
+            zip64_archive(5, 5, u64::MAX),
+            zip64_archive(5, u64::MAX, 0),
+            zip64_archive(u64::MAX, 5, 0),
+            zip64_archive(5, u64::MAX - 10, 0),
+        ] {
+            assert!(matches!(read_zip(&archive), Err(Error::Format(_))));
+        }
+
+        let mut archive = single_file_archive();
+        let end = archive.len() - 22;
+        archive[end + 16..end + 20].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(read_zip(&archive), Err(Error::Format(_))));
+    }
+
+    #[test]
+    fn test_read_zip_inflate_limited_to_declared_size() {
+        // zlib.compress(b"abcabcabc")[2:-4] declared as 3 bytes large
+        let compressed = [0x4b, 0x4c, 0x4a, 0x4e, 0x04, 0x23, 0x00];
+        let mut buffer = Vec::new();
+        write_zip(&mut buffer, &[("a.txt".to_owned(), compressed.to_vec())]).unwrap();
+        let central_header = 30 + 5 + compressed.len();
+        for offset in [8, central_header + 10] {
+            buffer[offset..offset + 2].copy_from_slice(&DEFLATED.to_le_bytes());
+        }
+        buffer[central_header + 24..central_header + 28].copy_from_slice(&3u32.to_le_bytes());
+
+        match read_zip(&buffer) {
+            Err(Error::Format(message)) => assert!(message.contains("declared size")),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+}
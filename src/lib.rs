@@ -1,5 +1,8 @@
+pub mod data;
 pub(crate) mod graph;
+pub mod io;
 pub mod linalg;
+pub mod nn;
 pub mod prelude;
 pub mod tensor;
 
@@ -1087,6 +1087,102 @@ impl<T: Numeric> Array<T> {
         self.data = data;
         self.shape = self.get_transposed_shape();
     }
+
+    /// Permutes dimensions of an array.
+    ///
+    /// `i`-th dimension of the resulting array corresponds to `axes[i]`-th dimension
+    /// of the original array, i.e. given an array of shape `[a, b, c]` and `axes = [2, 0, 1]`
+    /// the resulting array will have shape `[c, a, b]`.
+    ///
+    /// * `axes` - Permutation of dimensions.
+    ///
+    /// **Panics** if `axes` is not a permutation of `0..n`, where `n` is the length
+    /// of array's shape vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Array;
+    ///
+    /// let a = Array::from_vec(
+    ///     vec![
+    ///         1., 2., 3.,
+    ///         4., 5., 6.,
+    ///     ],
+    ///     vec![1, 2, 3]
+    /// );
+    ///
+    /// assert_eq!(
+    ///     a.permute(&[2, 0, 1]),
+    ///     Array::from_vec(
+    ///         vec![
+    ///             1., 4.,
+    ///
+    ///             2., 5.,
+    ///
+    ///             3., 6.,
+    ///         ],
+    ///         vec![3, 1, 2]
+    ///     )
+    /// );
+    /// ```
+    pub fn permute(&self, axes: &[usize]) -> Array<T> {
+        let mut sorted_axes = axes.to_vec();
+        sorted_axes.sort_unstable();
+        if sorted_axes != (0..self.shape.len()).collect::<Vec<usize>>() {
+            panic!(
+                "Invalid permutation of axes. Got: {:?} for shape {:?}",
+                axes, self.shape
+            )
+        }
+
+        let new_shape: Vec<usize> = axes.iter().map(|&axis| self.shape[axis]).collect();
+        let mut strides = vec![1; self.shape.len()];
+        for i in (0..self.shape.len().saturating_sub(1)).rev() {
+            strides[i] = strides[i + 1] * self.shape[i + 1];
+        }
+        let new_strides: Vec<usize> = axes.iter().map(|&axis| strides[axis]).collect();
+
+        let mut data = Vec::with_capacity(self.data.len());
+        let mut index = vec![0; new_shape.len()];
+        let mut src_idx = 0;
+        for _ in 0..self.data.len() {
+            data.push(self.data[src_idx]);
+            for i in (0..new_shape.len()).rev() {
+                index[i] += 1;
+                src_idx += new_strides[i];
+                if index[i] < new_shape[i] {
+                    break;
+                }
+                src_idx -= new_strides[i] * new_shape[i];
+                index[i] = 0;
+            }
+        }
+        Array {
+            data,
+            shape: new_shape,
+        }
+    }
+
+    /// Returns an array with the same data and a new shape.
+    ///
+    /// * `shape` - New shape with the same number of elements as the array.
+    ///
+    /// **Panics** if `shape` contains zero or is incompatible with array's data.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Array;
+    ///
+    /// let a = Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![2, 3]);
+    ///
+    /// assert_eq!(
+    ///     a.reshape(vec![3, 1, 2]),
+    ///     Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![3, 1, 2])
+    /// );
+    /// ```
+    pub fn reshape(&self, shape: Vec<usize>) -> Array<T> {
+        Array::from_vec(self.data.clone(), shape)
+    }
 }
 
 impl<T: Numeric> fmt::Display for Array<T> {
@@ -0,0 +1,724 @@
+use crate::linalg::matmul::matmul_2d_matrix_slices;
+use crate::linalg::utils::transpose_2d_matrix_slices;
+use crate::linalg::{Array, Numeric};
+
+/// Memory layout of 4-dimensional image arrays.
+///
+/// * `Nchw` - `[batch, channels, height, width]`.
+/// * `Nhwc` - `[batch, height, width, channels]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DataFormat {
+    Nchw,
+    Nhwc,
+}
+
+impl DataFormat {
+    // Transforms an array of this format to the `NCHW` one.
+    pub(crate) fn array_to_nchw<T: Numeric>(self, array: &Array<T>) -> Array<T> {
+        match self {
+            DataFormat::Nchw => array.clone(),
+            DataFormat::Nhwc => array.permute(&[0, 3, 1, 2]),
+        }
+    }
+
+    // Transforms an array of the `NCHW` format to this format.
+    pub(crate) fn array_from_nchw<T: Numeric>(self, array: &Array<T>) -> Array<T> {
+        match self {
+            DataFormat::Nchw => array.clone(),
+            DataFormat::Nhwc => array.permute(&[0, 2, 3, 1]),
+        }
+    }
+
+    // Transforms a shape of this format to the `NCHW` one.
+    pub(crate) fn shape_to_nchw(self, shape: &[usize]) -> Vec<usize> {
+        match self {
+            DataFormat::Nchw => shape.to_vec(),
+            DataFormat::Nhwc => vec![shape[0], shape[3], shape[1], shape[2]],
+        }
+    }
+
+    // Transforms a shape of the `NCHW` format to this format.
+    pub(crate) fn shape_from_nchw(self, shape: &[usize]) -> Vec<usize> {
+        match self {
+            DataFormat::Nchw => shape.to_vec(),
+            DataFormat::Nhwc => vec![shape[0], shape[2], shape[3], shape[1]],
+        }
+    }
+}
+
+// Hyper-parameters of a 2D convolution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Conv2dParams {
+    pub stride: (usize, usize),
+    pub padding: (usize, usize),
+    pub dilation: (usize, usize),
+    pub groups: usize,
+}
+
+impl Conv2dParams {
+    // Computes spatial size of the convolution's output.
+    // Panics if the kernel doesn't fit into the padded input.
+    pub fn output_size(
+        &self,
+        input_hw: (usize, usize),
+        kernel_hw: (usize, usize),
+    ) -> (usize, usize) {
+        let compute =
+            |size: usize, kernel: usize, stride: usize, padding: usize, dilation: usize| {
+                let padded = size + 2 * padding;
+                let effective_kernel = dilation * (kernel - 1) + 1;
+                if effective_kernel > padded {
+                    panic!(
+                        "Kernel with effective size {} doesn't fit into padded input of size {}.",
+                        effective_kernel, padded
+                    )
+                }
+                (padded - effective_kernel) / stride + 1
+            };
+        (
+            compute(
+                input_hw.0,
+                kernel_hw.0,
+                self.stride.0,
+                self.padding.0,
+                self.dilation.0,
+            ),
+            compute(
+                input_hw.1,
+                kernel_hw.1,
+                self.stride.1,
+                self.padding.1,
+                self.dilation.1,
+            ),
+        )
+    }
+}
+
+impl Conv2dParams {
+    // Computes spatial size of the transposed convolution's output.
+    // Panics if the output would be empty or `output_padding` isn't smaller than stride.
+    pub fn transposed_output_size(
+        &self,
+        input_hw: (usize, usize),
+        kernel_hw: (usize, usize),
+        output_padding: (usize, usize),
+    ) -> (usize, usize) {
+        let compute = |size: usize,
+                       kernel: usize,
+                       stride: usize,
+                       padding: usize,
+                       dilation: usize,
+                       output_padding: usize| {
+            if output_padding >= stride {
+                panic!(
+                    "Output padding must be smaller than stride. Got: {} and {}",
+                    output_padding, stride
+                )
+            }
+            let full = (size - 1) * stride + dilation * (kernel - 1) + output_padding + 1;
+            if full <= 2 * padding {
+                panic!(
+                    "Padding {} is too big for transposed convolution with output of size {}.",
+                    padding, full
+                )
+            }
+            full - 2 * padding
+        };
+        (
+            compute(
+                input_hw.0,
+                kernel_hw.0,
+                self.stride.0,
+                self.padding.0,
+                self.dilation.0,
+                output_padding.0,
+            ),
+            compute(
+                input_hw.1,
+                kernel_hw.1,
+                self.stride.1,
+                self.padding.1,
+                self.dilation.1,
+                output_padding.1,
+            ),
+        )
+    }
+}
+
+// Checks if `NCHW` input shape and `OIHW` kernel shape are valid for a convolution.
+// Panics if not.
+pub(crate) fn check_conv2d_shapes(
+    input_shape: &[usize],
+    kernel_shape: &[usize],
+    params: &Conv2dParams,
+) {
+    if input_shape.len() != 4 || kernel_shape.len() != 4 {
+        panic!(
+            "Convolution requires 4-dimensional input and kernel. Got shapes: {:?} and {:?}",
+            input_shape, kernel_shape
+        )
+    }
+    if params.stride.0 == 0 || params.stride.1 == 0 {
+        panic!("Stride must be positive. Got: {:?}", params.stride)
+    }
+    if params.dilation.0 == 0 || params.dilation.1 == 0 {
+        panic!("Dilation must be positive. Got: {:?}", params.dilation)
+    }
+    if params.groups == 0
+        || !input_shape[1].is_multiple_of(params.groups)
+        || !kernel_shape[0].is_multiple_of(params.groups)
+    {
+        panic!(
+            "Number of input channels ({}) and output channels ({}) must be divisible by groups ({}).",
+            input_shape[1], kernel_shape[0], params.groups
+        )
+    }
+    if input_shape[1] / params.groups != kernel_shape[1] {
+        panic!(
+            "Kernel's input channels don't match input's channels. Got shapes: {:?} and {:?} with {} groups",
+            input_shape, kernel_shape, params.groups
+        )
+    }
+}
+
+// Unfolds image patches of a `[channels, height, width]` slice into a matrix
+// of shape `[channels * kernel_h * kernel_w, out_h * out_w]`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn im2col<T: Numeric>(
+    image: &[T],
+    channels: usize,
+    input_hw: (usize, usize),
+    kernel_hw: (usize, usize),
+    output_hw: (usize, usize),
+    params: &Conv2dParams,
+    output_buffer: &mut [T],
+) {
+    let (height, width) = input_hw;
+    let (out_h, out_w) = output_hw;
+    let mut row = 0;
+    for c in 0..channels {
+        for ki in 0..kernel_hw.0 {
+            for kj in 0..kernel_hw.1 {
+                let row_buffer = &mut output_buffer[row * out_h * out_w..(row + 1) * out_h * out_w];
+                for oi in 0..out_h {
+                    let i = (oi * params.stride.0 + ki * params.dilation.0) as isize
+                        - params.padding.0 as isize;
+                    for oj in 0..out_w {
+                        let j = (oj * params.stride.1 + kj * params.dilation.1) as isize
+                            - params.padding.1 as isize;
+                        row_buffer[oi * out_w + oj] =
+                            if i >= 0 && j >= 0 && (i as usize) < height && (j as usize) < width {
+                                image[(c * height + i as usize) * width + j as usize]
+                            } else {
+                                T::zero()
+                            };
+                    }
+                }
+                row += 1;
+            }
+        }
+    }
+}
+
+// Folds a matrix produced by `im2col` back into a `[channels, height, width]` slice
+// summing overlapping values.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn col2im<T: Numeric>(
+    cols: &[T],
+    channels: usize,
+    input_hw: (usize, usize),
+    kernel_hw: (usize, usize),
+    output_hw: (usize, usize),
+    params: &Conv2dParams,
+    output_buffer: &mut [T],
+) {
+    let (height, width) = input_hw;
+    let (out_h, out_w) = output_hw;
+    let mut row = 0;
+    for c in 0..channels {
+        for ki in 0..kernel_hw.0 {
+            for kj in 0..kernel_hw.1 {
+                let row_data = &cols[row * out_h * out_w..(row + 1) * out_h * out_w];
+                for oi in 0..out_h {
+                    let i = (oi * params.stride.0 + ki * params.dilation.0) as isize
+                        - params.padding.0 as isize;
+                    if i < 0 || i as usize >= height {
+                        continue;
+                    }
+                    for oj in 0..out_w {
+                        let j = (oj * params.stride.1 + kj * params.dilation.1) as isize
+                            - params.padding.1 as isize;
+                        if j >= 0 && (j as usize) < width {
+                            let idx = (c * height + i as usize) * width + j as usize;
+                            output_buffer[idx] = output_buffer[idx] + row_data[oi * out_w + oj];
+                        }
+                    }
+                }
+                row += 1;
+            }
+        }
+    }
+}
+
+// Computes 2D convolution of a `NCHW` input with an `OIHW` kernel.
+pub(crate) fn conv2d_nchw<T: Numeric>(
+    input: &Array<T>,
+    kernel: &Array<T>,
+    params: &Conv2dParams,
+) -> Array<T> {
+    check_conv2d_shapes(&input.shape, &kernel.shape, params);
+    let (batch, channels, height, width) = (
+        input.shape[0],
+        input.shape[1],
+        input.shape[2],
+        input.shape[3],
+    );
+    let (out_channels, kernel_h, kernel_w) = (kernel.shape[0], kernel.shape[2], kernel.shape[3]);
+    let (out_h, out_w) = params.output_size((height, width), (kernel_h, kernel_w));
+
+    let group_channels = channels / params.groups;
+    let group_out_channels = out_channels / params.groups;
+    let col_rows = group_channels * kernel_h * kernel_w;
+    let col_cols = out_h * out_w;
+    let image_size = group_channels * height * width;
+    let group_output_size = group_out_channels * col_cols;
+
+    let mut cols = vec![T::zero(); col_rows * col_cols];
+    let mut data = vec![T::zero(); batch * out_channels * col_cols];
+    for n in 0..batch {
+        for g in 0..params.groups {
+            let image_offset = (n * params.groups + g) * image_size;
+            im2col(
+                &input.data[image_offset..image_offset + image_size],
+                group_channels,
+                (height, width),
+                (kernel_h, kernel_w),
+                (out_h, out_w),
+                params,
+                &mut cols,
+            );
+            let output_offset = (n * params.groups + g) * group_output_size;
+            matmul_2d_matrix_slices(
+                &kernel.data
+                    [g * group_out_channels * col_rows..(g + 1) * group_out_channels * col_rows],
+                group_out_channels,
+                col_rows,
+                &cols,
+                col_rows,
+                col_cols,
+                &mut data[output_offset..output_offset + group_output_size],
+            );
+        }
+    }
+    Array {
+        data,
+        shape: vec![batch, out_channels, out_h, out_w],
+    }
+}
+
+// Computes shape of a `NCHW` transposed convolution's output for a `NCHW` input shape
+// and `[C_in, C_out / groups, kernel_h, kernel_w]` kernel shape. Panics if shapes
+// or parameters are invalid.
+pub(crate) fn get_conv2d_transpose_shape(
+    input_shape: &[usize],
+    kernel_shape: &[usize],
+    params: &Conv2dParams,
+    output_padding: (usize, usize),
+) -> Vec<usize> {
+    if input_shape.len() != 4 || kernel_shape.len() != 4 {
+        panic!(
+            "Transposed convolution requires 4-dimensional input and kernel. Got shapes: {:?} and {:?}",
+            input_shape, kernel_shape
+        )
+    }
+    if input_shape[1] != kernel_shape[0] {
+        panic!(
+            "Kernel's input channels don't match input's channels. Got shapes: {:?} and {:?}",
+            input_shape, kernel_shape
+        )
+    }
+    let output_channels = kernel_shape[1] * params.groups;
+    let (out_h, out_w) = params.transposed_output_size(
+        (input_shape[2], input_shape[3]),
+        (kernel_shape[2], kernel_shape[3]),
+        output_padding,
+    );
+    let output_shape = vec![input_shape[0], output_channels, out_h, out_w];
+    // transposed convolution is a gradient of a regular convolution mapping
+    // the output back to the input
+    check_conv2d_shapes(&output_shape, kernel_shape, params);
+    output_shape
+}
+
+// Computes transposed 2D convolution of a `NCHW` input with
+// a `[C_in, C_out / groups, kernel_h, kernel_w]` kernel.
+pub(crate) fn conv2d_transpose_nchw<T: Numeric>(
+    input: &Array<T>,
+    kernel: &Array<T>,
+    params: &Conv2dParams,
+    output_padding: (usize, usize),
+) -> Array<T> {
+    let output_shape =
+        get_conv2d_transpose_shape(&input.shape, &kernel.shape, params, output_padding);
+    conv2d_backward_input(input, kernel, &output_shape, params)
+}
+
+// Computes gradient of a `NCHW` convolution w.r.t. its input given gradient
+// w.r.t. its output.
+pub(crate) fn conv2d_backward_input<T: Numeric>(
+    grad: &Array<T>,
+    kernel: &Array<T>,
+    input_shape: &[usize],
+    params: &Conv2dParams,
+) -> Array<T> {
+    let (batch, channels, height, width) = (
+        input_shape[0],
+        input_shape[1],
+        input_shape[2],
+        input_shape[3],
+    );
+    let (out_channels, kernel_h, kernel_w) = (kernel.shape[0], kernel.shape[2], kernel.shape[3]);
+    let (out_h, out_w) = (grad.shape[2], grad.shape[3]);
+
+    let group_channels = channels / params.groups;
+    let group_out_channels = out_channels / params.groups;
+    let col_rows = group_channels * kernel_h * kernel_w;
+    let col_cols = out_h * out_w;
+    let image_size = group_channels * height * width;
+    let group_output_size = group_out_channels * col_cols;
+
+    let mut kernels_transposed = vec![T::zero(); out_channels * col_rows];
+    for g in 0..params.groups {
+        let range = g * group_out_channels * col_rows..(g + 1) * group_out_channels * col_rows;
+        transpose_2d_matrix_slices(
+            &kernel.data[range.clone()],
+            group_out_channels,
+            col_rows,
+            &mut kernels_transposed[range],
+        );
+    }
+
+    let mut cols = vec![T::zero(); col_rows * col_cols];
+    let mut data = vec![T::zero(); batch * channels * height * width];
+    for n in 0..batch {
+        for g in 0..params.groups {
+            let output_offset = (n * params.groups + g) * group_output_size;
+            cols.iter_mut().for_each(|x| *x = T::zero());
+            matmul_2d_matrix_slices(
+                &kernels_transposed
+                    [g * group_out_channels * col_rows..(g + 1) * group_out_channels * col_rows],
+                col_rows,
+                group_out_channels,
+                &grad.data[output_offset..output_offset + group_output_size],
+                group_out_channels,
+                col_cols,
+                &mut cols,
+            );
+            let image_offset = (n * params.groups + g) * image_size;
+            col2im(
+                &cols,
+                group_channels,
+                (height, width),
+                (kernel_h, kernel_w),
+                (out_h, out_w),
+                params,
+                &mut data[image_offset..image_offset + image_size],
+            );
+        }
+    }
+    Array {
+        data,
+        shape: input_shape.to_vec(),
+    }
+}
+
+// Computes gradient of a `NCHW` convolution w.r.t. its kernel given gradient
+// w.r.t. its output.
+pub(crate) fn conv2d_backward_kernel<T: Numeric>(
+    grad: &Array<T>,
+    input: &Array<T>,
+    kernel_shape: &[usize],
+    params: &Conv2dParams,
+) -> Array<T> {
+    let (batch, channels, height, width) = (
+        input.shape[0],
+        input.shape[1],
+        input.shape[2],
+        input.shape[3],
+    );
+    let (out_channels, kernel_h, kernel_w) = (kernel_shape[0], kernel_shape[2], kernel_shape[3]);
+    let (out_h, out_w) = (grad.shape[2], grad.shape[3]);
+
+    let group_channels = channels / params.groups;
+    let group_out_channels = out_channels / params.groups;
+    let col_rows = group_channels * kernel_h * kernel_w;
+    let col_cols = out_h * out_w;
+    let image_size = group_channels * height * width;
+    let group_output_size = group_out_channels * col_cols;
+    let group_kernel_size = group_out_channels * col_rows;
+
+    let mut cols = vec![T::zero(); col_rows * col_cols];
+    let mut cols_transposed = vec![T::zero(); col_rows * col_cols];
+    let mut group_grad = vec![T::zero(); group_kernel_size];
+    let mut data = vec![T::zero(); out_channels * col_rows];
+    for n in 0..batch {
+        for g in 0..params.groups {
+            let image_offset = (n * params.groups + g) * image_size;
+            im2col(
+                &input.data[image_offset..image_offset + image_size],
+                group_channels,
+                (height, width),
+                (kernel_h, kernel_w),
+                (out_h, out_w),
+                params,
+                &mut cols,
+            );
+            transpose_2d_matrix_slices(&cols, col_rows, col_cols, &mut cols_transposed);
+            let output_offset = (n * params.groups + g) * group_output_size;
+            group_grad.iter_mut().for_each(|x| *x = T::zero());
+            matmul_2d_matrix_slices(
+                &grad.data[output_offset..output_offset + group_output_size],
+                group_out_channels,
+                col_cols,
+                &cols_transposed,
+                col_cols,
+                col_rows,
+                &mut group_grad,
+            );
+            for (x, &y) in data[g * group_kernel_size..(g + 1) * group_kernel_size]
+                .iter_mut()
+                .zip(group_grad.iter())
+            {
+                *x = *x + y;
+            }
+        }
+    }
+    Array {
+        data,
+        shape: kernel_shape.to_vec(),
+    }
+}
+
+/// Computes 2D convolution (cross-correlation) of an image batch with a kernel.
+///
+/// Convolution is computed by unfolding image patches into a matrix (*im2col*) which is
+/// then multiplied with the kernel, so *BLAS* is used for `f32` and `f64` arrays.
+///
+/// * `input` - Array of shape `[N, C, H, W]` or `[N, H, W, C]` depending on `data_format`.
+/// * `kernel` - Array of shape `[C_out, C / groups, kernel_h, kernel_w]`.
+/// * `stride` - Step of the sliding window along height and width.
+/// * `padding` - Number of zeros added to both sides of height and width.
+/// * `dilation` - Spacing between kernel elements along height and width.
+/// * `groups` - Number of blocked connections from input channels to output channels.
+/// * `data_format` - Layout of `input` and the output array.
+///
+/// The output has `C_out` channels and spatial size of
+/// `(H + 2 * padding - dilation * (kernel_h - 1) - 1) / stride + 1` (analogously for width).
+///
+/// **Panics** if shapes of `input` and `kernel` are incompatible, `stride`, `dilation` or
+/// `groups` contain zero or kernel doesn't fit into the padded input.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::{conv2d, Array, DataFormat};
+///
+/// let input = Array::from_vec(
+///     vec![
+///         1., 2., 3.,
+///         4., 5., 6.,
+///         7., 8., 9.,
+///     ],
+///     vec![1, 1, 3, 3]
+/// );
+/// let kernel = Array::from_vec(vec![1., 0., 0., 1.], vec![1, 1, 2, 2]);
+///
+/// assert_eq!(
+///     conv2d(&input, &kernel, (1, 1), (0, 0), (1, 1), 1, DataFormat::Nchw),
+///     Array::from_vec(vec![6., 8., 12., 14.], vec![1, 1, 2, 2])
+/// );
+/// ```
+pub fn conv2d<T: Numeric>(
+    input: &Array<T>,
+    kernel: &Array<T>,
+    stride: (usize, usize),
+    padding: (usize, usize),
+    dilation: (usize, usize),
+    groups: usize,
+    data_format: DataFormat,
+) -> Array<T> {
+    if input.shape.len() != 4 {
+        panic!(
+            "Convolution requires 4-dimensional input. Got shape: {:?}",
+            input.shape
+        )
+    }
+    let params = Conv2dParams {
+        stride,
+        padding,
+        dilation,
+        groups,
+    };
+    data_format.array_from_nchw(&conv2d_nchw(
+        &data_format.array_to_nchw(input),
+        kernel,
+        &params,
+    ))
+}
+
+/// Computes transposed 2D convolution (*fractionally-strided* convolution) of an image batch.
+///
+/// This is the gradient of `conv2d` w.r.t. its input, so it maps arrays of the
+/// convolution's output shape to arrays of its input shape. It's commonly used
+/// to increase spatial resolution with learnable upsampling.
+///
+/// * `input` - Array of shape `[N, C_in, H, W]` or `[N, H, W, C_in]` depending on `data_format`.
+/// * `kernel` - Array of shape `[C_in, C_out / groups, kernel_h, kernel_w]`.
+/// * `stride` - Stride of the corresponding convolution along height and width.
+/// * `padding` - Padding of the corresponding convolution, which is cropped from the output.
+/// * `output_padding` - Additional size added to one side of the output, used to
+///   resolve ambiguity of the output's size when `stride` is greater than one.
+/// * `dilation` - Spacing between kernel elements along height and width.
+/// * `groups` - Number of blocked connections from input channels to output channels.
+/// * `data_format` - Layout of `input` and the output array.
+///
+/// The output has `C_out` channels and spatial size of
+/// `(H - 1) * stride - 2 * padding + dilation * (kernel_h - 1) + output_padding + 1`
+/// (analogously for width).
+///
+/// **Panics** if shapes of `input` and `kernel` are incompatible, `stride`, `dilation` or
+/// `groups` contain zero, `output_padding` isn't smaller than `stride` or the output
+/// would be empty.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::{conv2d_transpose, Array, DataFormat};
+///
+/// let input = Array::from_vec(vec![1., 2., 3., 4.], vec![1, 1, 2, 2]);
+/// let kernel = Array::new(1., vec![1, 1, 2, 2]);
+///
+/// assert_eq!(
+///     conv2d_transpose(&input, &kernel, (2, 2), (0, 0), (0, 0), (1, 1), 1, DataFormat::Nchw),
+///     Array::from_vec(
+///         vec![
+///             1., 1., 2., 2.,
+///             1., 1., 2., 2.,
+///             3., 3., 4., 4.,
+///             3., 3., 4., 4.,
+///         ],
+///         vec![1, 1, 4, 4]
+///     )
+/// );
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn conv2d_transpose<T: Numeric>(
+    input: &Array<T>,
+    kernel: &Array<T>,
+    stride: (usize, usize),
+    padding: (usize, usize),
+    output_padding: (usize, usize),
+    dilation: (usize, usize),
+    groups: usize,
+    data_format: DataFormat,
+) -> Array<T> {
+    if input.shape.len() != 4 {
+        panic!(
+            "Transposed convolution requires 4-dimensional input. Got shape: {:?}",
+            input.shape
+        )
+    }
+    let params = Conv2dParams {
+        stride,
+        padding,
+        dilation,
+        groups,
+    };
+    data_format.array_from_nchw(&conv2d_transpose_nchw(
+        &data_format.array_to_nchw(input),
+        kernel,
+        &params,
+        output_padding,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_im2col() {
+        let image: Vec<f64> = vec![1., 2., 3., 4., 5., 6., 7., 8., 9.];
+        let params = Conv2dParams {
+            stride: (1, 1),
+            padding: (0, 0),
+            dilation: (1, 1),
+            groups: 1,
+        };
+        let mut cols = vec![0.; 16];
+
+        im2col(&image, 1, (3, 3), (2, 2), (2, 2), &params, &mut cols);
+
+        assert_eq!(
+            cols,
+            vec![1., 2., 4., 5., 2., 3., 5., 6., 4., 5., 7., 8., 5., 6., 8., 9.]
+        );
+    }
+
+    #[test]
+    fn test_im2col_padding_stride() {
+        let image: Vec<f64> = vec![1., 2., 3., 4.];
+        let params = Conv2dParams {
+            stride: (2, 2),
+            padding: (1, 1),
+            dilation: (1, 1),
+            groups: 1,
+        };
+        let mut cols = vec![0.; 4];
+
+        im2col(&image, 1, (2, 2), (1, 1), (2, 2), &params, &mut cols);
+
+        assert_eq!(cols, vec![0., 0., 0., 4.]);
+    }
+
+    #[test]
+    fn test_col2im_sums_overlaps() {
+        let params = Conv2dParams {
+            stride: (1, 1),
+            padding: (0, 0),
+            dilation: (1, 1),
+            groups: 1,
+        };
+        let cols: Vec<f64> = vec![1.; 16];
+        let mut image = vec![0.; 9];
+
+        col2im(&cols, 1, (3, 3), (2, 2), (2, 2), &params, &mut image);
+
+        assert_eq!(image, vec![1., 2., 1., 2., 4., 2., 1., 2., 1.]);
+    }
+
+    #[test]
+    fn test_output_size() {
+        let params = Conv2dParams {
+            stride: (2, 1),
+            padding: (1, 0),
+            dilation: (1, 2),
+            groups: 1,
+        };
+
+        assert_eq!(params.output_size((7, 7), (3, 3)), (4, 3));
+    }
+
+    #[test]
+    fn test_transposed_output_size() {
+        let params = Conv2dParams {
+            stride: (2, 1),
+            padding: (1, 0),
+            dilation: (1, 2),
+            groups: 1,
+        };
+
+        assert_eq!(
+            params.transposed_output_size((4, 3), (3, 3), (1, 0)),
+            (8, 7)
+        );
+    }
+}
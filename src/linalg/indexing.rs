@@ -0,0 +1,390 @@
+use crate::linalg::{Array, Numeric};
+
+// Checks if all indices are smaller than a size of an indexed axis. Panics if not.
+pub(crate) fn check_indices(indices: &[usize], size: usize) {
+    if let Some(index) = indices.iter().find(|&&index| index >= size) {
+        panic!(
+            "Index {} is out of bounds for axis of size {}.",
+            index, size
+        )
+    }
+}
+
+// Gathers rows (sub-arrays along the first axis) of an array.
+// The output has shape `[rows.len(), ...]`.
+pub(crate) fn gather_rows<T: Numeric>(array: &Array<T>, rows: &[usize]) -> Array<T> {
+    check_indices(rows, array.shape[0]);
+    let row_size: usize = array.shape[1..].iter().product();
+    let mut data = Vec::with_capacity(rows.len() * row_size);
+    for &row in rows {
+        data.extend_from_slice(&array.data[row * row_size..(row + 1) * row_size]);
+    }
+    let mut shape = vec![rows.len()];
+    shape.extend_from_slice(&array.shape[1..]);
+    Array { data, shape }
+}
+
+// Adds rows of `updates` to the rows of `array` given by `rows`.
+// Repeated rows are accumulated.
+pub(crate) fn scatter_add_rows<T: Numeric>(
+    array: &mut Array<T>,
+    rows: &[usize],
+    updates: &Array<T>,
+) {
+    check_indices(rows, array.shape[0]);
+    let row_size: usize = array.shape[1..].iter().product();
+    if updates.data.len() != rows.len() * row_size {
+        panic!(
+            "Updates of shape {:?} don't match {} rows of an array of shape {:?}.",
+            updates.shape,
+            rows.len(),
+            array.shape
+        )
+    }
+    for (&row, update) in rows.iter().zip(updates.data.chunks(row_size)) {
+        for (x, &y) in array.data[row * row_size..(row + 1) * row_size]
+            .iter_mut()
+            .zip(update.iter())
+        {
+            *x = *x + y;
+        }
+    }
+}
+
+// Computes strides of an array with a given shape.
+fn get_strides(shape: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; shape.len()];
+    for i in (0..shape.len().saturating_sub(1)).rev() {
+        strides[i] = strides[i + 1] * shape[i + 1];
+    }
+    strides
+}
+
+// Maps every element of `indices` (of shape `indices_shape`) to a flat index of an array
+// with shape `shape`, replacing its coordinate along `axis` with the index value.
+// Panics if arguments are invalid.
+pub(crate) fn get_gather_data_indices(
+    shape: &[usize],
+    axis: usize,
+    indices: &[usize],
+    indices_shape: &[usize],
+) -> Vec<usize> {
+    if axis >= shape.len() {
+        panic!("Axis {} is out of bounds for shape {:?}.", axis, shape)
+    }
+    let is_shape_valid = indices_shape.len() == shape.len()
+        && indices_shape
+            .iter()
+            .zip(shape.iter())
+            .enumerate()
+            .all(|(i, (&index_size, &size))| i == axis || index_size <= size);
+    if !is_shape_valid || indices_shape.iter().product::<usize>() != indices.len() {
+        panic!(
+            "Indices of shape {:?} are invalid for an array of shape {:?} along axis {}.",
+            indices_shape, shape, axis
+        )
+    }
+    check_indices(indices, shape[axis]);
+
+    let strides = get_strides(shape);
+    let mut data_indices = Vec::with_capacity(indices.len());
+    let mut position = vec![0; indices_shape.len()];
+    for &index in indices {
+        let data_index: usize = position
+            .iter()
+            .zip(strides.iter())
+            .enumerate()
+            .map(|(i, (&p, &stride))| if i == axis { index } else { p } * stride)
+            .sum();
+        data_indices.push(data_index);
+        for i in (0..indices_shape.len()).rev() {
+            position[i] += 1;
+            if position[i] < indices_shape[i] {
+                break;
+            }
+            position[i] = 0;
+        }
+    }
+    data_indices
+}
+
+// Adds `updates` to elements of an array at given flat indices.
+pub(crate) fn scatter_add_data<T: Numeric>(
+    array: &mut Array<T>,
+    data_indices: &[usize],
+    updates: &[T],
+) {
+    for (&idx, &value) in data_indices.iter().zip(updates.iter()) {
+        array.data[idx] = array.data[idx] + value;
+    }
+}
+
+// Computes gradient of `index_select()` w.r.t. its input given gradient w.r.t. its output.
+pub(crate) fn index_select_backward<T: Numeric>(
+    grad: &Array<T>,
+    input_shape: &[usize],
+    axis: usize,
+    indices: &[usize],
+) -> Array<T> {
+    let inner: usize = input_shape[axis + 1..].iter().product();
+    let mut input_grad = Array::new(T::zero(), input_shape.to_vec());
+    for (i, grad_slice) in grad.data.chunks(inner).enumerate() {
+        let (o, index) = (i / indices.len(), indices[i % indices.len()]);
+        let start = (o * input_shape[axis] + index) * inner;
+        for (x, &y) in input_grad.data[start..start + inner]
+            .iter_mut()
+            .zip(grad_slice.iter())
+        {
+            *x = *x + y;
+        }
+    }
+    input_grad
+}
+
+impl<T: Numeric> Array<T> {
+    /// Gathers values along an axis.
+    ///
+    /// For a 3-dimensional array and `axis = 1` the output is computed as
+    /// `out[i][j][k] = self[i][indices[i][j][k]][k]`.
+    ///
+    /// * `axis` - Axis to gather values along.
+    /// * `indices` - Indices in row-major order, each smaller than `shape[axis]`.
+    /// * `indices_shape` - Shape of the indices and the output. It must have the same
+    ///   length as array's shape and must not exceed it outside of `axis`.
+    ///
+    /// **Panics** if `axis` is out of bounds, `indices_shape` is invalid or any index
+    /// is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Array;
+    ///
+    /// let a = Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![2, 3]);
+    ///
+    /// assert_eq!(
+    ///     a.gather(1, &[2, 0], &[2, 1]),
+    ///     Array::from_vec(vec![3., 4.], vec![2, 1])
+    /// );
+    /// ```
+    pub fn gather(&self, axis: usize, indices: &[usize], indices_shape: &[usize]) -> Array<T> {
+        let data = get_gather_data_indices(&self.shape, axis, indices, indices_shape)
+            .into_iter()
+            .map(|idx| self.data[idx])
+            .collect();
+        Array {
+            data,
+            shape: indices_shape.to_vec(),
+        }
+    }
+
+    /// Returns a copy of the array with `updates` added along an axis.
+    ///
+    /// This is the inverse operation of `gather()`. For a 3-dimensional array and
+    /// `axis = 1` every update is added as
+    /// `out[i][indices[i][j][k]][k] += updates[i][j][k]`. Repeated indices are accumulated.
+    ///
+    /// * `axis` - Axis to scatter values along.
+    /// * `indices` - Indices in row-major order with the shape of `updates`,
+    ///   each smaller than `shape[axis]`.
+    /// * `updates` - Values to be added. Its shape must have the same length as array's
+    ///   shape and must not exceed it outside of `axis`.
+    ///
+    /// **Panics** if `axis` is out of bounds, shapes are invalid or any index is
+    /// out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Array;
+    ///
+    /// let a = Array::new(0., vec![2, 3]);
+    /// let updates = Array::from_vec(vec![1., 2., 3., 4.], vec![2, 2]);
+    ///
+    /// assert_eq!(
+    ///     a.scatter_add(1, &[2, 2, 0, 1], &updates),
+    ///     Array::from_vec(vec![0., 0., 3., 3., 4., 0.], vec![2, 3])
+    /// );
+    /// ```
+    pub fn scatter_add(&self, axis: usize, indices: &[usize], updates: &Array<T>) -> Array<T> {
+        let data_indices = get_gather_data_indices(&self.shape, axis, indices, &updates.shape);
+        let mut output = self.clone();
+        scatter_add_data(&mut output, &data_indices, &updates.data);
+        output
+    }
+
+    /// Selects sub-arrays along an axis.
+    ///
+    /// The output has the same shape as the array, except for `axis` of size
+    /// `indices.len()`. Indices may repeat.
+    ///
+    /// * `axis` - Axis to select along.
+    /// * `indices` - Non-empty indices, each smaller than `shape[axis]`.
+    ///
+    /// **Panics** if `axis` is out of bounds, `indices` is empty or any index is
+    /// out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Array;
+    ///
+    /// let a = Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![2, 3]);
+    ///
+    /// assert_eq!(
+    ///     a.index_select(1, &[2, 0, 2]),
+    ///     Array::from_vec(vec![3., 1., 3., 6., 4., 6.], vec![2, 3])
+    /// );
+    /// ```
+    pub fn index_select(&self, axis: usize, indices: &[usize]) -> Array<T> {
+        if axis >= self.shape.len() || indices.is_empty() {
+            panic!(
+                "Invalid axis {} or empty indices for shape {:?}.",
+                axis, self.shape
+            )
+        }
+        check_indices(indices, self.shape[axis]);
+        let outer: usize = self.shape[..axis].iter().product();
+        let inner: usize = self.shape[axis + 1..].iter().product();
+        let mut data = Vec::with_capacity(outer * indices.len() * inner);
+        for o in 0..outer {
+            for &index in indices {
+                let start = (o * self.shape[axis] + index) * inner;
+                data.extend_from_slice(&self.data[start..start + inner]);
+            }
+        }
+        let mut shape = self.shape.clone();
+        shape[axis] = indices.len();
+        Array { data, shape }
+    }
+
+    /// Selects elements for which `mask` is true.
+    ///
+    /// Returns a 1-dimensional array with selected elements in row-major order or
+    /// `None` if no element is selected.
+    ///
+    /// * `mask` - Mask in row-major order with a length equal to the number of elements.
+    ///
+    /// **Panics** if `mask` has invalid length.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Array;
+    ///
+    /// let a = Array::from_vec(vec![1., 2., 3., 4.], vec![2, 2]);
+    ///
+    /// assert_eq!(
+    ///     a.masked_select(&[true, false, false, true]),
+    ///     Some(Array::from_vec(vec![1., 4.], vec![2]))
+    /// );
+    /// assert_eq!(a.masked_select(&[false; 4]), None);
+    /// ```
+    pub fn masked_select(&self, mask: &[bool]) -> Option<Array<T>> {
+        self.check_mask(mask);
+        let data: Vec<T> = self
+            .data
+            .iter()
+            .zip(mask.iter())
+            .filter(|(_, &selected)| selected)
+            .map(|(&x, _)| x)
+            .collect();
+        if data.is_empty() {
+            None
+        } else {
+            let shape = vec![data.len()];
+            Some(Array { data, shape })
+        }
+    }
+
+    /// Returns a copy of the array with elements for which `mask` is true set to `value`.
+    ///
+    /// * `mask` - Mask in row-major order with a length equal to the number of elements.
+    /// * `value` - Value to be set.
+    ///
+    /// **Panics** if `mask` has invalid length.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Array;
+    ///
+    /// let a = Array::from_vec(vec![1., 2., 3., 4.], vec![2, 2]);
+    ///
+    /// assert_eq!(
+    ///     a.masked_fill(&[true, false, false, true], 0.),
+    ///     Array::from_vec(vec![0., 2., 3., 0.], vec![2, 2])
+    /// );
+    /// ```
+    pub fn masked_fill(&self, mask: &[bool], value: T) -> Array<T> {
+        self.check_mask(mask);
+        let data = self
+            .data
+            .iter()
+            .zip(mask.iter())
+            .map(|(&x, &selected)| if selected { value } else { x })
+            .collect();
+        Array {
+            data,
+            shape: self.shape.clone(),
+        }
+    }
+
+    /// Returns a mask with `predicate` evaluated for every element in row-major order.
+    ///
+    /// * `predicate` - Function deciding if an element is selected.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Array;
+    ///
+    /// let a = Array::from_vec(vec![1., -2., 3., -4.], vec![2, 2]);
+    ///
+    /// assert_eq!(a.mask(|x| x > 0.), vec![true, false, true, false]);
+    /// assert_eq!(
+    ///     a.masked_select(&a.mask(|x| x > 0.)),
+    ///     Some(Array::from_vec(vec![1., 3.], vec![2]))
+    /// );
+    /// ```
+    pub fn mask(&self, predicate: impl Fn(T) -> bool) -> Vec<bool> {
+        self.data.iter().map(|&x| predicate(x)).collect()
+    }
+
+    // Checks if mask has a length equal to the number of elements. Panics if not.
+    fn check_mask(&self, mask: &[bool]) {
+        if mask.len() != self.data.len() {
+            panic!(
+                "Mask of length {} is invalid for an array of shape {:?}.",
+                mask.len(),
+                self.shape
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gather_and_scatter_add_rows() {
+        let array = Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![3, 2]);
+        let gathered = gather_rows(&array, &[2, 0, 2]);
+
+        assert_eq!(
+            gathered,
+            Array::from_vec(vec![5., 6., 1., 2., 5., 6.], vec![3, 2])
+        );
+
+        let mut accumulated = Array::new(0., vec![3, 2]);
+        scatter_add_rows(&mut accumulated, &[2, 0, 2], &gathered);
+
+        assert_eq!(
+            accumulated,
+            Array::from_vec(vec![1., 2., 0., 0., 10., 12.], vec![3, 2])
+        );
+    }
+
+    #[test]
+    fn test_get_gather_data_indices() {
+        assert_eq!(
+            get_gather_data_indices(&[2, 3, 2], 1, &[2, 0, 1, 1], &[2, 1, 2]),
+            vec![4, 1, 8, 9]
+        );
+    }
+}
@@ -1,8 +1,17 @@
 mod array;
 mod array_view;
 mod broadcast;
+mod conv;
+mod indexing;
 mod matmul;
+mod normalization;
+mod pad;
+mod pool;
+mod random;
 mod reduce;
+mod shape;
+mod softmax;
+mod upsample;
 pub mod utils;
 
 use num::Float;
@@ -13,4 +22,35 @@ impl<T> Numeric for T where T: Float + fmt::Display + Copy + fmt::Debug + 'stati
 
 pub use array::*;
 pub use array_view::ArrayView;
+pub(crate) use conv::{
+    check_conv2d_shapes, conv2d_backward_input, conv2d_backward_kernel, conv2d_nchw,
+    conv2d_transpose_nchw, get_conv2d_transpose_shape, Conv2dParams,
+};
+pub use conv::{conv2d, conv2d_transpose, DataFormat};
+pub(crate) use indexing::{
+    check_indices, gather_rows, get_gather_data_indices, index_select_backward, scatter_add_data,
+    scatter_add_rows,
+};
+pub(crate) use normalization::{
+    batch_norm_backward, batch_norm_forward, channel_moments, check_channel_parameter_shape,
+    check_normalization_parameter_shape, normalization_backward, normalization_forward,
+    ChannelLayout, NormalizationLayout,
+};
+pub(crate) use pad::{check_pad_widths, get_padded_shape, pad_backward};
+pub use pad::{pad, PadMode};
+pub use pool::{avg_pool2d, global_avg_pool2d, global_max_pool2d, max_pool2d};
+pub(crate) use pool::{
+    avg_pool2d_nchw, avg_pool2d_nchw_backward, check_pool_input, get_pool2d_shape, max_pool2d_nchw,
+    max_pool2d_nchw_backward, Pool2dParams,
+};
+pub use random::Rng;
+pub(crate) use reduce::reduce_to_shape;
 pub use reduce::{reduce, reduce_max, reduce_mean, reduce_min, reduce_prod, reduce_sum};
+pub use shape::stack;
+pub(crate) use shape::{check_stack_shapes, get_selected_shape, get_stacked_shape};
+pub use softmax::softmax;
+pub(crate) use softmax::{
+    check_attention_mask_shape, get_attention_mask, masked_softmax, softmax_backward,
+};
+pub(crate) use upsample::{check_upsample2d_params, resize_nchw, resize_nchw_backward};
+pub use upsample::{upsample2d, Interpolation};
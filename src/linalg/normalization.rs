@@ -0,0 +1,416 @@
+use crate::linalg::{Array, Numeric};
+use num::cast;
+
+// Describes how elements of an array are assigned to channels along a given axis.
+// Element with flat index `(o * channels + c) * inner + i` belongs to channel `c`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ChannelLayout {
+    pub outer: usize,
+    pub channels: usize,
+    pub inner: usize,
+}
+
+impl ChannelLayout {
+    // Creates a layout for a given shape and channel axis.
+    // Panics if axis is out of bounds.
+    pub fn new(shape: &[usize], axis: usize) -> ChannelLayout {
+        if axis >= shape.len() {
+            panic!(
+                "Invalid channel axis! Got shape: {:?} and axis: {}.",
+                shape, axis
+            )
+        }
+        ChannelLayout {
+            outer: shape[..axis].iter().product(),
+            channels: shape[axis],
+            inner: shape[axis + 1..].iter().product(),
+        }
+    }
+
+    // Returns number of elements in every channel.
+    pub fn channel_size(&self) -> usize {
+        self.outer * self.inner
+    }
+
+    // Returns channel of an element with a given flat index.
+    pub fn channel(&self, idx: usize) -> usize {
+        (idx / self.inner) % self.channels
+    }
+}
+
+// Checks if a per-channel parameter has shape `[channels]`. Panics if not.
+pub(crate) fn check_channel_parameter_shape(shape: &[usize], channels: usize, name: &str) {
+    if shape != [channels] {
+        panic!("{} must have shape [{}]. Got: {:?}", name, channels, shape)
+    }
+}
+
+// Computes mean and (biased) variance of every channel.
+pub(crate) fn channel_moments<T: Numeric>(
+    input: &Array<T>,
+    layout: &ChannelLayout,
+) -> (Vec<T>, Vec<T>) {
+    let count: T = cast(layout.channel_size()).unwrap();
+    let mut mean = vec![T::zero(); layout.channels];
+    for (idx, &value) in input.data.iter().enumerate() {
+        let c = layout.channel(idx);
+        mean[c] = mean[c] + value;
+    }
+    mean.iter_mut().for_each(|x| *x = *x / count);
+
+    let mut variance = vec![T::zero(); layout.channels];
+    for (idx, &value) in input.data.iter().enumerate() {
+        let c = layout.channel(idx);
+        let diff = value - mean[c];
+        variance[c] = variance[c] + diff * diff;
+    }
+    variance.iter_mut().for_each(|x| *x = *x / count);
+    (mean, variance)
+}
+
+// Normalizes every channel with given statistics, then scales and shifts it.
+pub(crate) fn batch_norm_forward<T: Numeric>(
+    input: &Array<T>,
+    gamma: &Array<T>,
+    beta: &Array<T>,
+    mean: &[T],
+    variance: &[T],
+    layout: &ChannelLayout,
+    epsilon: T,
+) -> Array<T> {
+    let inv_std: Vec<T> = variance
+        .iter()
+        .map(|&v| T::one() / (v + epsilon).sqrt())
+        .collect();
+    let data = input
+        .data
+        .iter()
+        .enumerate()
+        .map(|(idx, &value)| {
+            let c = layout.channel(idx);
+            (value - mean[c]) * inv_std[c] * gamma.data[c] + beta.data[c]
+        })
+        .collect();
+    Array {
+        data,
+        shape: input.shape.clone(),
+    }
+}
+
+// Computes gradients of the batch normalization w.r.t. input, gamma and beta given gradient
+// w.r.t. its output. If `batch_statistics` is true, then `mean` and `variance` are treated
+// as functions of the input.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn batch_norm_backward<T: Numeric>(
+    grad: &Array<T>,
+    input: &Array<T>,
+    gamma: &Array<T>,
+    mean: &[T],
+    variance: &[T],
+    layout: &ChannelLayout,
+    epsilon: T,
+    batch_statistics: bool,
+) -> (Array<T>, Array<T>, Array<T>) {
+    let inv_std: Vec<T> = variance
+        .iter()
+        .map(|&v| T::one() / (v + epsilon).sqrt())
+        .collect();
+    let normalized: Vec<T> = input
+        .data
+        .iter()
+        .enumerate()
+        .map(|(idx, &value)| {
+            let c = layout.channel(idx);
+            (value - mean[c]) * inv_std[c]
+        })
+        .collect();
+
+    let mut grad_gamma = vec![T::zero(); layout.channels];
+    let mut grad_beta = vec![T::zero(); layout.channels];
+    for (idx, (&g, &x)) in grad.data.iter().zip(normalized.iter()).enumerate() {
+        let c = layout.channel(idx);
+        grad_gamma[c] = grad_gamma[c] + g * x;
+        grad_beta[c] = grad_beta[c] + g;
+    }
+
+    let count: T = cast(layout.channel_size()).unwrap();
+    let grad_input = grad
+        .data
+        .iter()
+        .zip(normalized.iter())
+        .enumerate()
+        .map(|(idx, (&g, &x))| {
+            let c = layout.channel(idx);
+            let scale = gamma.data[c] * inv_std[c];
+            if batch_statistics {
+                scale * (g - grad_beta[c] / count - x * grad_gamma[c] / count)
+            } else {
+                scale * g
+            }
+        })
+        .collect();
+    (
+        Array {
+            data: grad_input,
+            shape: input.shape.clone(),
+        },
+        Array {
+            data: grad_gamma,
+            shape: vec![layout.channels],
+        },
+        Array {
+            data: grad_beta,
+            shape: vec![layout.channels],
+        },
+    )
+}
+
+// Describes a normalization which computes statistics over groups of elements
+// and applies an affine transformation with per-element parameter indices.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct NormalizationLayout {
+    pub groups: Vec<usize>,
+    pub params: Vec<usize>,
+    pub n_groups: usize,
+    pub n_params: usize,
+}
+
+impl NormalizationLayout {
+    // Creates a layout normalizing over given axes. Parameters have shape
+    // of the normalized axes. Panics if axes are invalid.
+    pub fn layer_norm(shape: &[usize], axes: &[usize]) -> NormalizationLayout {
+        let mut sorted_axes = axes.to_vec();
+        sorted_axes.sort_unstable();
+        sorted_axes.dedup();
+        if sorted_axes.is_empty()
+            || sorted_axes.len() != axes.len()
+            || *sorted_axes.last().unwrap() >= shape.len()
+        {
+            panic!(
+                "Invalid normalization axes! Got shape: {:?} and axes: {:?}.",
+                shape, axes
+            )
+        }
+        let size: usize = shape.iter().product();
+        let mut groups = Vec::with_capacity(size);
+        let mut params = Vec::with_capacity(size);
+        let mut index = vec![0; shape.len()];
+        for _ in 0..size {
+            let (mut group, mut param) = (0, 0);
+            for (axis, (&i, &dim)) in index.iter().zip(shape.iter()).enumerate() {
+                if sorted_axes.contains(&axis) {
+                    param = param * dim + i;
+                } else {
+                    group = group * dim + i;
+                }
+            }
+            groups.push(group);
+            params.push(param);
+            for axis in (0..shape.len()).rev() {
+                index[axis] += 1;
+                if index[axis] < shape[axis] {
+                    break;
+                }
+                index[axis] = 0;
+            }
+        }
+        let n_params = sorted_axes.iter().map(|&axis| shape[axis]).product();
+        NormalizationLayout {
+            groups,
+            params,
+            n_groups: size / n_params,
+            n_params,
+        }
+    }
+
+    // Creates a layout normalizing every sample (index along the first axis) over groups
+    // of channels. Parameters have shape `[channels]`. Panics if the channel axis
+    // or number of groups are invalid.
+    pub fn group_norm(shape: &[usize], num_groups: usize, axis: usize) -> NormalizationLayout {
+        if axis == 0 || axis >= shape.len() {
+            panic!(
+                "Invalid channel axis! Got shape: {:?} and axis: {}.",
+                shape, axis
+            )
+        }
+        let channels = shape[axis];
+        if num_groups == 0 || !channels.is_multiple_of(num_groups) {
+            panic!(
+                "Number of channels ({}) must be divisible by number of groups ({}).",
+                channels, num_groups
+            )
+        }
+        let layout = ChannelLayout::new(shape, axis);
+        let sample_size: usize = shape[1..].iter().product();
+        let channels_per_group = channels / num_groups;
+        let size = shape[0] * sample_size;
+        let params: Vec<usize> = (0..size).map(|idx| layout.channel(idx)).collect();
+        let groups = params
+            .iter()
+            .enumerate()
+            .map(|(idx, &c)| (idx / sample_size) * num_groups + c / channels_per_group)
+            .collect();
+        NormalizationLayout {
+            groups,
+            params,
+            n_groups: shape[0] * num_groups,
+            n_params: channels,
+        }
+    }
+
+    // Computes mean and inverse standard deviation of every group.
+    fn statistics<T: Numeric>(&self, input: &Array<T>, epsilon: T) -> (Vec<T>, Vec<T>) {
+        let count: T = cast(input.data.len() / self.n_groups).unwrap();
+        let mut mean = vec![T::zero(); self.n_groups];
+        for (&group, &value) in self.groups.iter().zip(input.data.iter()) {
+            mean[group] = mean[group] + value;
+        }
+        mean.iter_mut().for_each(|x| *x = *x / count);
+
+        let mut variance = vec![T::zero(); self.n_groups];
+        for (&group, &value) in self.groups.iter().zip(input.data.iter()) {
+            let diff = value - mean[group];
+            variance[group] = variance[group] + diff * diff;
+        }
+        let inv_std = variance
+            .iter()
+            .map(|&v| T::one() / (v / count + epsilon).sqrt())
+            .collect();
+        (mean, inv_std)
+    }
+
+    // Returns normalized input before scaling and shifting.
+    fn normalize<T: Numeric>(&self, input: &Array<T>, epsilon: T) -> Vec<T> {
+        let (mean, inv_std) = self.statistics(input, epsilon);
+        self.groups
+            .iter()
+            .zip(input.data.iter())
+            .map(|(&group, &value)| (value - mean[group]) * inv_std[group])
+            .collect()
+    }
+}
+
+// Checks if normalization parameter has given shape. Panics if not.
+pub(crate) fn check_normalization_parameter_shape(shape: &[usize], expected: &[usize], name: &str) {
+    if shape != expected {
+        panic!("{} must have shape {:?}. Got: {:?}", name, expected, shape)
+    }
+}
+
+// Normalizes input within groups of a layout, then scales and shifts it.
+pub(crate) fn normalization_forward<T: Numeric>(
+    input: &Array<T>,
+    gamma: &Array<T>,
+    beta: &Array<T>,
+    layout: &NormalizationLayout,
+    epsilon: T,
+) -> Array<T> {
+    let data = layout
+        .normalize(input, epsilon)
+        .into_iter()
+        .zip(layout.params.iter())
+        .map(|(x, &p)| x * gamma.data[p] + beta.data[p])
+        .collect();
+    Array {
+        data,
+        shape: input.shape.clone(),
+    }
+}
+
+// Computes gradients of the normalization w.r.t. input, gamma and beta given gradient
+// w.r.t. its output.
+pub(crate) fn normalization_backward<T: Numeric>(
+    grad: &Array<T>,
+    input: &Array<T>,
+    gamma: &Array<T>,
+    layout: &NormalizationLayout,
+    epsilon: T,
+) -> (Array<T>, Array<T>, Array<T>) {
+    let (_, inv_std) = layout.statistics(input, epsilon);
+    let normalized = layout.normalize(input, epsilon);
+    let count: T = cast(input.data.len() / layout.n_groups).unwrap();
+
+    let mut grad_gamma = vec![T::zero(); layout.n_params];
+    let mut grad_beta = vec![T::zero(); layout.n_params];
+    // sums of gradient w.r.t. normalized input and its product with normalized input
+    let mut grad_normalized_sum = vec![T::zero(); layout.n_groups];
+    let mut grad_normalized_dot = vec![T::zero(); layout.n_groups];
+    for (i, (&g, &x)) in grad.data.iter().zip(normalized.iter()).enumerate() {
+        let (group, param) = (layout.groups[i], layout.params[i]);
+        grad_gamma[param] = grad_gamma[param] + g * x;
+        grad_beta[param] = grad_beta[param] + g;
+        let grad_normalized = g * gamma.data[param];
+        grad_normalized_sum[group] = grad_normalized_sum[group] + grad_normalized;
+        grad_normalized_dot[group] = grad_normalized_dot[group] + grad_normalized * x;
+    }
+
+    let grad_input = (0..grad.data.len())
+        .map(|i| {
+            let (group, param) = (layout.groups[i], layout.params[i]);
+            let grad_normalized = grad.data[i] * gamma.data[param];
+            inv_std[group]
+                * (grad_normalized
+                    - grad_normalized_sum[group] / count
+                    - normalized[i] * grad_normalized_dot[group] / count)
+        })
+        .collect();
+    let param_shape = gamma.shape.clone();
+    (
+        Array {
+            data: grad_input,
+            shape: input.shape.clone(),
+        },
+        Array {
+            data: grad_gamma,
+            shape: param_shape.clone(),
+        },
+        Array {
+            data: grad_beta,
+            shape: param_shape,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_layout() {
+        let layout = ChannelLayout::new(&[2, 3, 4], 1);
+
+        assert_eq!(layout.channel_size(), 8);
+        assert_eq!(layout.channel(0), 0);
+        assert_eq!(layout.channel(5), 1);
+        assert_eq!(layout.channel(12), 0);
+        assert_eq!(layout.channel(23), 2);
+    }
+
+    #[test]
+    fn test_channel_moments() {
+        let input = Array::from_vec(vec![1., 2., 3., 6., 5., 10.], vec![3, 2]);
+
+        let (mean, variance) = channel_moments(&input, &ChannelLayout::new(&[3, 2], 1));
+
+        assert_eq!(mean, vec![3., 6.]);
+        assert_eq!(variance, vec![8. / 3., 32. / 3.]);
+    }
+
+    #[test]
+    fn test_layer_norm_layout() {
+        let layout = NormalizationLayout::layer_norm(&[2, 3, 2], &[0, 2]);
+
+        assert_eq!(layout.groups, vec![0, 0, 1, 1, 2, 2, 0, 0, 1, 1, 2, 2]);
+        assert_eq!(layout.params, vec![0, 1, 0, 1, 0, 1, 2, 3, 2, 3, 2, 3]);
+        assert_eq!((layout.n_groups, layout.n_params), (3, 4));
+    }
+
+    #[test]
+    fn test_group_norm_layout() {
+        let layout = NormalizationLayout::group_norm(&[2, 1, 4], 2, 2);
+
+        assert_eq!(layout.groups, vec![0, 0, 1, 1, 2, 2, 3, 3]);
+        assert_eq!(layout.params, vec![0, 1, 2, 3, 0, 1, 2, 3]);
+        assert_eq!((layout.n_groups, layout.n_params), (4, 4));
+    }
+}
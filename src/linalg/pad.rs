@@ -0,0 +1,252 @@
+use crate::linalg::{Array, Numeric};
+
+/// Method of filling the padded region of an array.
+///
+/// * `Constant(value)` - Padded region is filled with `value`.
+/// * `Reflect` - Values are mirrored at the edge, without repeating the edge value.
+///   For example `[1, 2, 3]` padded with 2 on both sides is `[3, 2, 1, 2, 3, 2, 1]`.
+/// * `Replicate` - Edge values are repeated.
+///   For example `[1, 2, 3]` padded with 2 on both sides is `[1, 1, 1, 2, 3, 3, 3]`.
+/// * `Circular` - Array is wrapped around.
+///   For example `[1, 2, 3]` padded with 2 on both sides is `[2, 3, 1, 2, 3, 1, 2]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PadMode<T: Numeric> {
+    Constant(T),
+    Reflect,
+    Replicate,
+    Circular,
+}
+
+// Maps every position of a padded axis to a position in the original axis.
+// `None` means that a position is filled with a constant.
+fn get_source_indices<T: Numeric>(
+    size: usize,
+    pad_width: (usize, usize),
+    mode: PadMode<T>,
+) -> Vec<Option<usize>> {
+    let size_isize = size as isize;
+    (0..size + pad_width.0 + pad_width.1)
+        .map(|i| {
+            let i = i as isize - pad_width.0 as isize;
+            if (0..size_isize).contains(&i) {
+                return Some(i as usize);
+            }
+            match mode {
+                PadMode::Constant(_) => None,
+                PadMode::Reflect => {
+                    let period = 2 * (size_isize - 1);
+                    let i = i.rem_euclid(period.max(1));
+                    Some((if i < size_isize { i } else { period - i }) as usize)
+                }
+                PadMode::Replicate => Some(i.clamp(0, size_isize - 1) as usize),
+                PadMode::Circular => Some(i.rem_euclid(size_isize) as usize),
+            }
+        })
+        .collect()
+}
+
+// Checks if pad widths are valid for a given shape and mode. Panics if not.
+pub(crate) fn check_pad_widths<T: Numeric>(
+    shape: &[usize],
+    pad_widths: &[(usize, usize)],
+    mode: PadMode<T>,
+) {
+    if shape.len() != pad_widths.len() {
+        panic!(
+            "Pad widths must be given for every dimension. Got shape: {:?} and pad widths: {:?}",
+            shape, pad_widths
+        )
+    }
+    for (&size, &(before, after)) in shape.iter().zip(pad_widths.iter()) {
+        let is_valid = match mode {
+            PadMode::Constant(_) | PadMode::Replicate => true,
+            PadMode::Reflect => before < size && after < size,
+            PadMode::Circular => before <= size && after <= size,
+        };
+        if !is_valid {
+            panic!(
+                "Pad widths {:?} are too big for shape {:?} in {:?} mode.",
+                pad_widths, shape, mode
+            )
+        }
+    }
+}
+
+// Computes shape of a padded array.
+pub(crate) fn get_padded_shape(shape: &[usize], pad_widths: &[(usize, usize)]) -> Vec<usize> {
+    shape
+        .iter()
+        .zip(pad_widths.iter())
+        .map(|(&size, &(before, after))| size + before + after)
+        .collect()
+}
+
+// Maps every element of a padded array to a flat index of the original array.
+fn get_padded_data_indices<T: Numeric>(
+    shape: &[usize],
+    pad_widths: &[(usize, usize)],
+    mode: PadMode<T>,
+) -> Vec<Option<usize>> {
+    let source_indices: Vec<Vec<Option<usize>>> = shape
+        .iter()
+        .zip(pad_widths.iter())
+        .map(|(&size, &pad_width)| get_source_indices(size, pad_width, mode))
+        .collect();
+    let mut strides = vec![1; shape.len()];
+    for i in (0..shape.len().saturating_sub(1)).rev() {
+        strides[i] = strides[i + 1] * shape[i + 1];
+    }
+
+    // indices are built axis by axis, starting from the outermost one
+    let mut data_indices = vec![Some(0)];
+    for (axis_indices, stride) in source_indices.iter().zip(strides) {
+        data_indices = data_indices
+            .iter()
+            .flat_map(|&prefix| {
+                axis_indices.iter().map(move |&idx| match (prefix, idx) {
+                    (Some(prefix), Some(idx)) => Some(prefix + idx * stride),
+                    _ => None,
+                })
+            })
+            .collect();
+    }
+    data_indices
+}
+
+// Computes gradient of the padding w.r.t. its input given gradient w.r.t. its output.
+pub(crate) fn pad_backward<T: Numeric>(
+    grad: &Array<T>,
+    input_shape: &[usize],
+    pad_widths: &[(usize, usize)],
+    mode: PadMode<T>,
+) -> Array<T> {
+    let mut data = vec![T::zero(); input_shape.iter().product()];
+    for (idx, &value) in get_padded_data_indices(input_shape, pad_widths, mode)
+        .into_iter()
+        .zip(grad.data.iter())
+    {
+        if let Some(idx) = idx {
+            data[idx] = data[idx] + value;
+        }
+    }
+    Array {
+        data,
+        shape: input_shape.to_vec(),
+    }
+}
+
+/// Pads an array along its dimensions.
+///
+/// * `array` - Array to be padded.
+/// * `pad_widths` - Number of values added before and after every dimension.
+///   Its length must be equal to the length of array's shape vector.
+/// * `mode` - Method of filling the padded region.
+///
+/// **Panics** if `pad_widths` has wrong length or pad widths are too big for
+/// `Reflect` (must be smaller than dimension) or `Circular` (must not be greater
+/// than dimension) mode.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::{pad, Array, PadMode};
+///
+/// let a = Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![2, 3]);
+///
+/// assert_eq!(
+///     pad(&a, &[(1, 0), (0, 2)], PadMode::Constant(0.)),
+///     Array::from_vec(
+///         vec![
+///             0., 0., 0., 0., 0.,
+///             1., 2., 3., 0., 0.,
+///             4., 5., 6., 0., 0.,
+///         ],
+///         vec![3, 5]
+///     )
+/// );
+/// assert_eq!(
+///     pad(&a, &[(0, 0), (2, 1)], PadMode::Reflect),
+///     Array::from_vec(
+///         vec![
+///             3., 2., 1., 2., 3., 2.,
+///             6., 5., 4., 5., 6., 5.,
+///         ],
+///         vec![2, 6]
+///     )
+/// );
+/// ```
+pub fn pad<T: Numeric>(
+    array: &Array<T>,
+    pad_widths: &[(usize, usize)],
+    mode: PadMode<T>,
+) -> Array<T> {
+    check_pad_widths(&array.shape, pad_widths, mode);
+    let constant = match mode {
+        PadMode::Constant(value) => value,
+        _ => T::zero(),
+    };
+    let data = get_padded_data_indices(&array.shape, pad_widths, mode)
+        .into_iter()
+        .map(|idx| idx.map_or(constant, |idx| array.data[idx]))
+        .collect();
+    Array {
+        data,
+        shape: get_padded_shape(&array.shape, pad_widths),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_source_indices() {
+        assert_eq!(
+            get_source_indices(3, (2, 2), PadMode::Constant(0.)),
+            vec![None, None, Some(0), Some(1), Some(2), None, None]
+        );
+        assert_eq!(
+            get_source_indices::<f64>(3, (2, 2), PadMode::Reflect),
+            vec![
+                Some(2),
+                Some(1),
+                Some(0),
+                Some(1),
+                Some(2),
+                Some(1),
+                Some(0)
+            ]
+        );
+        assert_eq!(
+            get_source_indices::<f64>(3, (2, 2), PadMode::Replicate),
+            vec![
+                Some(0),
+                Some(0),
+                Some(0),
+                Some(1),
+                Some(2),
+                Some(2),
+                Some(2)
+            ]
+        );
+        assert_eq!(
+            get_source_indices::<f64>(3, (2, 2), PadMode::Circular),
+            vec![
+                Some(1),
+                Some(2),
+                Some(0),
+                Some(1),
+                Some(2),
+                Some(0),
+                Some(1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_source_indices_reflect_single_element() {
+        assert_eq!(
+            get_source_indices::<f64>(1, (0, 0), PadMode::Reflect),
+            vec![Some(0)]
+        );
+    }
+}
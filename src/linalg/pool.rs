@@ -0,0 +1,375 @@
+use crate::linalg::{Array, DataFormat, Numeric};
+use num::cast;
+
+// Hyper-parameters of a 2D pooling.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Pool2dParams {
+    pub kernel_size: (usize, usize),
+    pub stride: (usize, usize),
+    pub padding: (usize, usize),
+}
+
+impl Pool2dParams {
+    // Creates parameters of a pooling over whole spatial dimensions.
+    pub fn global(input_hw: (usize, usize)) -> Pool2dParams {
+        Pool2dParams {
+            kernel_size: input_hw,
+            stride: (1, 1),
+            padding: (0, 0),
+        }
+    }
+
+    // Computes spatial size of the pooling's output.
+    pub fn output_size(&self, input_hw: (usize, usize)) -> (usize, usize) {
+        (
+            (input_hw.0 + 2 * self.padding.0 - self.kernel_size.0) / self.stride.0 + 1,
+            (input_hw.1 + 2 * self.padding.1 - self.kernel_size.1) / self.stride.1 + 1,
+        )
+    }
+
+    // Iterates over input positions (as flat spatial indices) covered by the pooling
+    // window at a given output position. Padded positions are skipped.
+    fn window(
+        &self,
+        input_hw: (usize, usize),
+        output_position: (usize, usize),
+    ) -> impl Iterator<Item = usize> {
+        let (height, width) = input_hw;
+        let start_i = (output_position.0 * self.stride.0) as isize - self.padding.0 as isize;
+        let start_j = (output_position.1 * self.stride.1) as isize - self.padding.1 as isize;
+        let rows =
+            start_i.max(0) as usize..((start_i + self.kernel_size.0 as isize) as usize).min(height);
+        let cols =
+            start_j.max(0) as usize..((start_j + self.kernel_size.1 as isize) as usize).min(width);
+        rows.flat_map(move |i| cols.clone().map(move |j| i * width + j))
+    }
+}
+
+// Checks if pooling parameters are valid for a `NCHW` input shape. Panics if not.
+pub(crate) fn check_pool2d_params(input_shape: &[usize], params: &Pool2dParams) {
+    check_pool_input(input_shape);
+    if params.kernel_size.0 == 0 || params.kernel_size.1 == 0 {
+        panic!(
+            "Kernel size must be positive. Got: {:?}",
+            params.kernel_size
+        )
+    }
+    if params.stride.0 == 0 || params.stride.1 == 0 {
+        panic!("Stride must be positive. Got: {:?}", params.stride)
+    }
+    if params.padding.0 >= params.kernel_size.0 || params.padding.1 >= params.kernel_size.1 {
+        panic!(
+            "Padding must be smaller than kernel size. Got: {:?} and {:?}",
+            params.padding, params.kernel_size
+        )
+    }
+    if input_shape[2] + 2 * params.padding.0 < params.kernel_size.0
+        || input_shape[3] + 2 * params.padding.1 < params.kernel_size.1
+    {
+        panic!(
+            "Kernel of size {:?} doesn't fit into padded input of shape {:?}.",
+            params.kernel_size, input_shape
+        )
+    }
+}
+
+// Computes shape of a `NCHW` pooling's output.
+pub(crate) fn get_pool2d_shape(input_shape: &[usize], params: &Pool2dParams) -> Vec<usize> {
+    check_pool2d_params(input_shape, params);
+    let (out_h, out_w) = params.output_size((input_shape[2], input_shape[3]));
+    vec![input_shape[0], input_shape[1], out_h, out_w]
+}
+
+// Computes max pooling of a `NCHW` array. Returns pooled array and flat indices
+// of input's elements selected for every output position.
+pub(crate) fn max_pool2d_nchw<T: Numeric>(
+    input: &Array<T>,
+    params: &Pool2dParams,
+) -> (Array<T>, Vec<usize>) {
+    let shape = get_pool2d_shape(&input.shape, params);
+    let (height, width) = (input.shape[2], input.shape[3]);
+    let (out_h, out_w) = (shape[2], shape[3]);
+
+    let mut data = Vec::with_capacity(shape.iter().product());
+    let mut argmax = Vec::with_capacity(shape.iter().product());
+    for image in 0..shape[0] * shape[1] {
+        let offset = image * height * width;
+        for i in 0..out_h {
+            for j in 0..out_w {
+                let mut max_idx = None;
+                for idx in params.window((height, width), (i, j)) {
+                    match max_idx {
+                        Some(current) if input.data[offset + idx] <= input.data[current] => {}
+                        _ => max_idx = Some(offset + idx),
+                    }
+                }
+                let max_idx = max_idx.unwrap();
+                data.push(input.data[max_idx]);
+                argmax.push(max_idx);
+            }
+        }
+    }
+    (Array { data, shape }, argmax)
+}
+
+// Computes gradient of the max pooling w.r.t. its input given gradient w.r.t. its output
+// and indices returned by `max_pool2d_nchw`.
+pub(crate) fn max_pool2d_nchw_backward<T: Numeric>(
+    grad: &Array<T>,
+    argmax: &[usize],
+    input_shape: &[usize],
+) -> Array<T> {
+    let mut data = vec![T::zero(); input_shape.iter().product()];
+    for (&idx, &value) in argmax.iter().zip(grad.data.iter()) {
+        data[idx] = data[idx] + value;
+    }
+    Array {
+        data,
+        shape: input_shape.to_vec(),
+    }
+}
+
+// Computes average pooling of a `NCHW` array.
+pub(crate) fn avg_pool2d_nchw<T: Numeric>(input: &Array<T>, params: &Pool2dParams) -> Array<T> {
+    let shape = get_pool2d_shape(&input.shape, params);
+    let (height, width) = (input.shape[2], input.shape[3]);
+    let (out_h, out_w) = (shape[2], shape[3]);
+
+    let mut data = Vec::with_capacity(shape.iter().product());
+    for image in 0..shape[0] * shape[1] {
+        let input_image = &input.data[image * height * width..(image + 1) * height * width];
+        for i in 0..out_h {
+            for j in 0..out_w {
+                let (sum, count) = params
+                    .window((height, width), (i, j))
+                    .fold((T::zero(), 0), |(sum, count), idx| {
+                        (sum + input_image[idx], count + 1)
+                    });
+                data.push(sum / cast(count).unwrap());
+            }
+        }
+    }
+    Array { data, shape }
+}
+
+// Computes gradient of the average pooling w.r.t. its input given gradient
+// w.r.t. its output.
+pub(crate) fn avg_pool2d_nchw_backward<T: Numeric>(
+    grad: &Array<T>,
+    input_shape: &[usize],
+    params: &Pool2dParams,
+) -> Array<T> {
+    let (height, width) = (input_shape[2], input_shape[3]);
+    let (out_h, out_w) = (grad.shape[2], grad.shape[3]);
+
+    let mut data = vec![T::zero(); input_shape.iter().product()];
+    for image in 0..input_shape[0] * input_shape[1] {
+        let input_image = &mut data[image * height * width..(image + 1) * height * width];
+        for i in 0..out_h {
+            for j in 0..out_w {
+                let window: Vec<usize> = params.window((height, width), (i, j)).collect();
+                let value =
+                    grad.data[(image * out_h + i) * out_w + j] / cast(window.len()).unwrap();
+                for idx in window {
+                    input_image[idx] = input_image[idx] + value;
+                }
+            }
+        }
+    }
+    Array {
+        data,
+        shape: input_shape.to_vec(),
+    }
+}
+
+/// Computes 2D max pooling of an image batch.
+///
+/// Every output element is the maximum of a `kernel_size` window of the input.
+/// Padded positions are ignored.
+///
+/// * `input` - Array of shape `[N, C, H, W]` or `[N, H, W, C]` depending on `data_format`.
+/// * `kernel_size` - Height and width of the pooling window.
+/// * `stride` - Step of the sliding window along height and width.
+/// * `padding` - Number of positions added to both sides of height and width.
+/// * `data_format` - Layout of `input` and the output array.
+///
+/// **Panics** if `input` isn't 4-dimensional, `kernel_size` or `stride` contain zero,
+/// `padding` isn't smaller than `kernel_size` or the window doesn't fit into the padded input.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::{max_pool2d, Array, DataFormat};
+///
+/// let input = Array::from_vec(
+///     vec![
+///         1., 5., 2., 0.,
+///         3., 4., 8., 1.,
+///     ],
+///     vec![1, 1, 2, 4]
+/// );
+///
+/// assert_eq!(
+///     max_pool2d(&input, (2, 2), (2, 2), (0, 0), DataFormat::Nchw),
+///     Array::from_vec(vec![5., 8.], vec![1, 1, 1, 2])
+/// );
+/// ```
+pub fn max_pool2d<T: Numeric>(
+    input: &Array<T>,
+    kernel_size: (usize, usize),
+    stride: (usize, usize),
+    padding: (usize, usize),
+    data_format: DataFormat,
+) -> Array<T> {
+    check_pool_input(&input.shape);
+    let params = Pool2dParams {
+        kernel_size,
+        stride,
+        padding,
+    };
+    data_format.array_from_nchw(&max_pool2d_nchw(&data_format.array_to_nchw(input), &params).0)
+}
+
+/// Computes 2D average pooling of an image batch.
+///
+/// Every output element is the mean of a `kernel_size` window of the input.
+/// Padded positions are not included in the mean.
+///
+/// * `input` - Array of shape `[N, C, H, W]` or `[N, H, W, C]` depending on `data_format`.
+/// * `kernel_size` - Height and width of the pooling window.
+/// * `stride` - Step of the sliding window along height and width.
+/// * `padding` - Number of positions added to both sides of height and width.
+/// * `data_format` - Layout of `input` and the output array.
+///
+/// **Panics** if `input` isn't 4-dimensional, `kernel_size` or `stride` contain zero,
+/// `padding` isn't smaller than `kernel_size` or the window doesn't fit into the padded input.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::{avg_pool2d, Array, DataFormat};
+///
+/// let input = Array::from_vec(
+///     vec![
+///         1., 5., 2., 0.,
+///         3., 4., 8., 1.,
+///     ],
+///     vec![1, 1, 2, 4]
+/// );
+///
+/// assert_eq!(
+///     avg_pool2d(&input, (2, 2), (2, 2), (0, 0), DataFormat::Nchw),
+///     Array::from_vec(vec![3.25, 2.75], vec![1, 1, 1, 2])
+/// );
+/// ```
+pub fn avg_pool2d<T: Numeric>(
+    input: &Array<T>,
+    kernel_size: (usize, usize),
+    stride: (usize, usize),
+    padding: (usize, usize),
+    data_format: DataFormat,
+) -> Array<T> {
+    check_pool_input(&input.shape);
+    let params = Pool2dParams {
+        kernel_size,
+        stride,
+        padding,
+    };
+    data_format.array_from_nchw(&avg_pool2d_nchw(&data_format.array_to_nchw(input), &params))
+}
+
+/// Computes maximum over spatial dimensions of an image batch.
+///
+/// * `input` - Array of shape `[N, C, H, W]` or `[N, H, W, C]` depending on `data_format`.
+/// * `data_format` - Layout of `input` and the output array.
+///
+/// Spatial dimensions are kept, so the output has shape `[N, C, 1, 1]` or `[N, 1, 1, C]`.
+///
+/// **Panics** if `input` isn't 4-dimensional.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::{global_max_pool2d, Array, DataFormat};
+///
+/// let input = Array::from_vec(vec![1., 5., 2., 0., 3., 4.], vec![1, 2, 1, 3]);
+///
+/// assert_eq!(
+///     global_max_pool2d(&input, DataFormat::Nhwc),
+///     Array::from_vec(vec![1., 5., 4.], vec![1, 1, 1, 3])
+/// );
+/// ```
+pub fn global_max_pool2d<T: Numeric>(input: &Array<T>, data_format: DataFormat) -> Array<T> {
+    check_pool_input(&input.shape);
+    let input = data_format.array_to_nchw(input);
+    let params = Pool2dParams::global((input.shape[2], input.shape[3]));
+    data_format.array_from_nchw(&max_pool2d_nchw(&input, &params).0)
+}
+
+/// Computes mean over spatial dimensions of an image batch.
+///
+/// * `input` - Array of shape `[N, C, H, W]` or `[N, H, W, C]` depending on `data_format`.
+/// * `data_format` - Layout of `input` and the output array.
+///
+/// Spatial dimensions are kept, so the output has shape `[N, C, 1, 1]` or `[N, 1, 1, C]`.
+///
+/// **Panics** if `input` isn't 4-dimensional.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::{global_avg_pool2d, Array, DataFormat};
+///
+/// let input = Array::from_vec(vec![1., 5., 2., 0., 3., 4.], vec![1, 2, 3, 1]);
+///
+/// assert_eq!(
+///     global_avg_pool2d(&input, DataFormat::Nchw),
+///     Array::from_vec(vec![8. / 3., 7. / 3.], vec![1, 2, 1, 1])
+/// );
+/// ```
+pub fn global_avg_pool2d<T: Numeric>(input: &Array<T>, data_format: DataFormat) -> Array<T> {
+    check_pool_input(&input.shape);
+    let input = data_format.array_to_nchw(input);
+    let params = Pool2dParams::global((input.shape[2], input.shape[3]));
+    data_format.array_from_nchw(&avg_pool2d_nchw(&input, &params))
+}
+
+// Checks if input of a pooling is 4-dimensional. Panics if not.
+pub(crate) fn check_pool_input(shape: &[usize]) {
+    if shape.len() != 4 {
+        panic!(
+            "Pooling requires 4-dimensional input. Got shape: {:?}",
+            shape
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_skips_padding() {
+        let params = Pool2dParams {
+            kernel_size: (3, 3),
+            stride: (2, 2),
+            padding: (1, 1),
+        };
+
+        assert_eq!(
+            params.window((4, 4), (0, 0)).collect::<Vec<usize>>(),
+            vec![0, 1, 4, 5]
+        );
+        assert_eq!(
+            params.window((4, 4), (1, 1)).collect::<Vec<usize>>(),
+            vec![5, 6, 7, 9, 10, 11, 13, 14, 15]
+        );
+    }
+
+    #[test]
+    fn test_max_pool2d_nchw_argmax() {
+        let input = Array::from_vec(vec![1., 5., 2., 0., 3., 4., 8., 1.], vec![1, 2, 2, 2]);
+        let params = Pool2dParams::global((2, 2));
+
+        let (output, argmax) = max_pool2d_nchw(&input, &params);
+
+        assert_eq!(output, Array::from_vec(vec![5., 8.], vec![1, 2, 1, 1]));
+        assert_eq!(argmax, vec![1, 6]);
+    }
+}
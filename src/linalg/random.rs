@@ -0,0 +1,414 @@
+use crate::linalg::utils::check_shape_positive;
+use crate::linalg::Numeric;
+use crate::Array;
+use num::cast;
+
+/// Seedable pseudo-random number generator.
+///
+/// Implements *xoshiro256*** algorithm, which is fast and has good statistical
+/// properties, but is not cryptographically secure. Generators created with
+/// the same seed produce the same sequences of numbers, which makes experiments
+/// reproducible.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::Rng;
+///
+/// let mut rng1 = Rng::new(42);
+/// let mut rng2 = Rng::new(42);
+///
+/// assert_eq!(rng1.next_u64(), rng2.next_u64());
+/// assert!((0. ..1.).contains(&rng1.next_f64()));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    /// Creates a new generator from a seed.
+    ///
+    /// The internal state is expanded from `seed` with *SplitMix64* generator,
+    /// so even similar seeds result in unrelated sequences.
+    ///
+    /// * `seed` - Seed of the generator.
+    pub fn new(seed: u64) -> Rng {
+        let mut splitmix_state = seed;
+        let mut state = [0; 4];
+        for elem in state.iter_mut() {
+            splitmix_state = splitmix_state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = splitmix_state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            *elem = z ^ (z >> 31);
+        }
+        Rng { state }
+    }
+
+    /// Returns next random `u64` value.
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        result
+    }
+
+    /// Returns a random number uniformly distributed in `[0, 1)` interval.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1. / (1u64 << 53) as f64)
+    }
+
+    /// Returns a random number uniformly distributed in `[low, high)` interval.
+    ///
+    /// * `low` - Lower bound of the interval.
+    /// * `high` - Upper bound of the interval.
+    ///
+    /// **Panics** if `low` is greater than `high`.
+    pub fn uniform(&mut self, low: f64, high: f64) -> f64 {
+        if low > high {
+            panic!(
+                "Lower bound is greater than the upper one. Got: low={}, high={}",
+                low, high
+            )
+        }
+        low + (high - low) * self.next_f64()
+    }
+
+    /// Returns a normally distributed random number.
+    ///
+    /// Uses *Box-Muller* transform.
+    ///
+    /// * `mean` - Mean of the distribution.
+    /// * `std` - Standard deviation of the distribution.
+    ///
+    /// **Panics** if `std` is negative.
+    pub fn normal(&mut self, mean: f64, std: f64) -> f64 {
+        if std < 0. {
+            panic!("Standard deviation cannot be negative. Got: {}", std)
+        }
+        // `1 - x` belongs to (0, 1], so the logarithm is always finite.
+        let u1 = 1. - self.next_f64();
+        let u2 = self.next_f64();
+        mean + std * (-2. * u1.ln()).sqrt() * (2. * std::f64::consts::PI * u2).cos()
+    }
+
+    /// Returns a random integer uniformly distributed in `[0, n)` interval.
+    ///
+    /// * `n` - Upper bound of the interval.
+    ///
+    /// **Panics** if `n` is zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        if n == 0 {
+            panic!("Upper bound has to be positive.")
+        }
+        let n = n as u64;
+        // Rejection sampling avoids modulo bias.
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return (value % n) as usize;
+            }
+        }
+    }
+
+    /// Returns a random permutation of `0..n` integers.
+    ///
+    /// Uses *Fisher-Yates* shuffle.
+    ///
+    /// * `n` - Length of the permutation.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Rng;
+    ///
+    /// let mut permutation = Rng::new(0).permutation(5);
+    /// permutation.sort();
+    ///
+    /// assert_eq!(permutation, vec![0, 1, 2, 3, 4]);
+    /// ```
+    pub fn permutation(&mut self, n: usize) -> Vec<usize> {
+        let mut permutation: Vec<usize> = (0..n).collect();
+        for i in (1..n).rev() {
+            permutation.swap(i, self.below(i + 1));
+        }
+        permutation
+    }
+}
+
+impl<T: Numeric> Array<T> {
+    /// Creates a new `Array` with values sampled from uniform distribution on `[low, high)`.
+    ///
+    /// * `shape` - Non-zero shape of the array.
+    /// * `low` - Lower bound of the interval.
+    /// * `high` - Upper bound of the interval.
+    /// * `rng` - Random number generator used for sampling.
+    ///
+    /// **Panics** if `shape` contains zero or `low` is greater than `high`.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::{Array, Rng};
+    ///
+    /// let a = Array::random_uniform(vec![2, 3], 0., 1., &mut Rng::new(42));
+    /// let b = Array::random_uniform(vec![2, 3], 0., 1., &mut Rng::new(42));
+    ///
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn random_uniform(shape: Vec<usize>, low: T, high: T, rng: &mut Rng) -> Array<T> {
+        let (low, high) = (to_f64(low), to_f64(high));
+        Array::sample(shape, || rng.uniform(low, high))
+    }
+
+    /// Creates a new `Array` with values sampled from normal distribution.
+    ///
+    /// * `shape` - Non-zero shape of the array.
+    /// * `mean` - Mean of the distribution.
+    /// * `std` - Standard deviation of the distribution.
+    /// * `rng` - Random number generator used for sampling.
+    ///
+    /// **Panics** if `shape` contains zero or `std` is negative.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::{Array, Rng};
+    ///
+    /// let a = Array::random_normal(vec![2, 3], 0., 1., &mut Rng::new(42));
+    ///
+    /// assert_eq!(a.get_shape(), vec![2, 3]);
+    /// ```
+    pub fn random_normal(shape: Vec<usize>, mean: T, std: T, rng: &mut Rng) -> Array<T> {
+        let (mean, std) = (to_f64(mean), to_f64(std));
+        Array::sample(shape, || rng.normal(mean, std))
+    }
+
+    /// Creates a new `Array` with values sampled from truncated normal distribution.
+    ///
+    /// Values further than two standard deviations from the mean are discarded and re-drawn.
+    ///
+    /// * `shape` - Non-zero shape of the array.
+    /// * `mean` - Mean of the distribution.
+    /// * `std` - Standard deviation of the distribution before truncation.
+    /// * `rng` - Random number generator used for sampling.
+    ///
+    /// **Panics** if `shape` contains zero or `std` is negative.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::{Array, Rng};
+    ///
+    /// let a = Array::random_truncated_normal(vec![2, 3], 0., 1., &mut Rng::new(42));
+    ///
+    /// assert!(a.map(|x: f64| x.abs()).i(vec![1, 2]) <= 2.);
+    /// ```
+    pub fn random_truncated_normal(shape: Vec<usize>, mean: T, std: T, rng: &mut Rng) -> Array<T> {
+        let (mean, std) = (to_f64(mean), to_f64(std));
+        if std < 0. {
+            panic!("Standard deviation cannot be negative. Got: {}", std)
+        }
+        Array::sample(shape, || loop {
+            let value = rng.normal(0., 1.);
+            if value.abs() <= 2. {
+                return mean + std * value;
+            }
+        })
+    }
+
+    /// Creates a new `Array` with values sampled from *Bernoulli* distribution.
+    ///
+    /// Each element is equal to one with probability `p` and to zero otherwise.
+    ///
+    /// * `shape` - Non-zero shape of the array.
+    /// * `p` - Probability of sampling one.
+    /// * `rng` - Random number generator used for sampling.
+    ///
+    /// **Panics** if `shape` contains zero or `p` is not in `[0, 1]` interval.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::{Array, Rng};
+    ///
+    /// let mask = Array::random_bernoulli(vec![2, 2], 1., &mut Rng::new(0));
+    ///
+    /// assert_eq!(mask, Array::new(1., vec![2, 2]));
+    /// ```
+    pub fn random_bernoulli(shape: Vec<usize>, p: T, rng: &mut Rng) -> Array<T> {
+        let p = to_f64(p);
+        if !(0. ..=1.).contains(&p) {
+            panic!("Probability should be in [0, 1] interval. Got: {}", p)
+        }
+        Array::sample(shape, || if rng.next_f64() < p { 1. } else { 0. })
+    }
+
+    /// Creates a copy of an array with sub-arrays along the first axis randomly permuted.
+    ///
+    /// * `rng` - Random number generator used for sampling.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::{Array, Rng};
+    ///
+    /// let a = Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![3, 2]);
+    /// let permuted = a.random_permutation(&mut Rng::new(0));
+    ///
+    /// assert_eq!(permuted.get_shape(), vec![3, 2]);
+    /// ```
+    pub fn random_permutation(&self, rng: &mut Rng) -> Array<T> {
+        let mut result = self.clone();
+        result.shuffle_along_axis(0, rng);
+        result
+    }
+
+    /// Randomly shuffles sub-arrays along given axis in place.
+    ///
+    /// The same permutation is applied to every sub-array, e.g. for 2D array
+    /// and `axis = 1`, columns are shuffled.
+    ///
+    /// * `axis` - Axis to be shuffled.
+    /// * `rng` - Random number generator used for sampling.
+    ///
+    /// **Panics** if `axis` is more than or equal to the length of array's shape vector.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::{Array, Rng};
+    ///
+    /// let mut a = Array::from_vec(vec![1., 2., 3., 1., 2., 3.], vec![2, 3]);
+    /// a.shuffle_along_axis(1, &mut Rng::new(0));
+    ///
+    /// // both rows are permuted in the same way
+    /// assert_eq!(a.i(vec![0, 0]), a.i(vec![1, 0]));
+    /// ```
+    pub fn shuffle_along_axis(&mut self, axis: usize, rng: &mut Rng) {
+        if axis >= self.shape.len() {
+            panic!(
+                "Invalid axis! Got shape: {:?} and axis: {}.",
+                self.shape, axis
+            )
+        }
+        let axis_len = self.shape[axis];
+        let inner_len: usize = self.shape[axis + 1..].iter().product();
+        let permutation = rng.permutation(axis_len);
+
+        let mut data = Vec::with_capacity(self.data.len());
+        for outer_slice in self.data.chunks(axis_len * inner_len) {
+            for &src in permutation.iter() {
+                data.extend_from_slice(&outer_slice[src * inner_len..(src + 1) * inner_len]);
+            }
+        }
+        self.data = data;
+    }
+
+    // Creates a new array with values produced by a given sampler.
+    fn sample(shape: Vec<usize>, mut sampler: impl FnMut() -> f64) -> Array<T> {
+        check_shape_positive(&shape);
+        let size = shape.iter().product();
+        let data = (0..size)
+            .map(|_| cast::<_, T>(sampler()).unwrap())
+            .collect();
+        Array { shape, data }
+    }
+}
+
+fn to_f64<T: Numeric>(value: T) -> f64 {
+    cast::<_, f64>(value).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng_deterministic() {
+        let mut rng1 = Rng::new(7);
+        let mut rng2 = Rng::new(7);
+        let mut rng3 = Rng::new(8);
+
+        let seq1: Vec<u64> = (0..10).map(|_| rng1.next_u64()).collect();
+        let seq2: Vec<u64> = (0..10).map(|_| rng2.next_u64()).collect();
+        let seq3: Vec<u64> = (0..10).map(|_| rng3.next_u64()).collect();
+
+        assert_eq!(seq1, seq2);
+        assert_ne!(seq1, seq3);
+    }
+
+    #[test]
+    fn test_uniform_range() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..1000 {
+            let value = rng.uniform(-2., 3.);
+            assert!((-2. ..3.).contains(&value));
+        }
+    }
+
+    #[test]
+    fn test_normal_moments() {
+        let mut rng = Rng::new(0);
+        let n = 20000;
+
+        let samples: Vec<f64> = (0..n).map(|_| rng.normal(1., 2.)).collect();
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let var = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+
+        assert!((mean - 1.).abs() < 0.05);
+        assert!((var.sqrt() - 2.).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_below() {
+        let mut rng = Rng::new(3);
+        let mut counts = [0; 3];
+
+        for _ in 0..3000 {
+            counts[rng.below(3)] += 1;
+        }
+
+        assert!(counts.iter().all(|&count| count > 900));
+    }
+
+    #[test]
+    fn test_permutation() {
+        let mut permutation = Rng::new(5).permutation(100);
+
+        assert_ne!(permutation, (0..100).collect::<Vec<usize>>());
+        permutation.sort_unstable();
+        assert_eq!(permutation, (0..100).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn test_shuffle_along_axis() {
+        let mut arr = Array::from_vec((0..24).map(|x| x as f64).collect(), vec![2, 3, 4]);
+        let mut rng = Rng::new(1);
+        let permutation = rng.clone().permutation(3);
+
+        arr.shuffle_along_axis(1, &mut rng);
+
+        for i in 0..2 {
+            for (j, src) in permutation.iter().enumerate() {
+                for k in 0..4 {
+                    assert_eq!(arr.i(vec![i, j, k]), (i * 12 + src * 4 + k) as f64);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_shuffle_along_axis_invalid_axis() {
+        Array::new(1., vec![2, 2]).shuffle_along_axis(2, &mut Rng::new(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_below_zero() {
+        Rng::new(0).below(0);
+    }
+}
@@ -388,3 +388,68 @@ pub fn reduce_mean<T: Numeric>(array: &Array<T>, axis: Option<usize>, keep_dims:
     sum.div_assign_scalar(cast::<_, T>(count).unwrap());
     sum
 }
+
+// Sums array's elements, so the result has a given shape.
+// This reverts array broadcasting, i.e. summation is done over leading dimensions
+// missing in `shape` and over dimensions that are equal to 1 in `shape`.
+// Used to compute gradients of operators with broadcasted operands.
+pub(crate) fn reduce_to_shape<T: Numeric>(array: &Array<T>, shape: &[usize]) -> Array<T> {
+    if array.shape == shape {
+        return array.clone();
+    }
+    if array.shape.len() < shape.len() {
+        panic!(
+            "Array of shape {:?} can't be reduced to shape {:?}",
+            array.shape, shape
+        )
+    }
+    let mut result = array.clone();
+    for _ in 0..array.shape.len() - shape.len() {
+        result = reduce_sum(&result, Some(0), false);
+    }
+    for (i, &dim) in shape.iter().enumerate() {
+        if dim == 1 && result.shape[i] != 1 {
+            result = reduce_sum(&result, Some(i), true);
+        }
+    }
+    if result.shape != shape {
+        panic!(
+            "Array of shape {:?} can't be reduced to shape {:?}",
+            array.shape, shape
+        )
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reduce_to_shape() {
+        let arr = Array::from_vec(
+            vec![0., 1., 2., 3., 4., 5., 6., 7., 8., 9., 10., 11.],
+            vec![2, 3, 2],
+        );
+
+        assert_eq!(reduce_to_shape(&arr, &[2, 3, 2]), arr);
+        assert_eq!(
+            reduce_to_shape(&arr, &[3, 2]),
+            Array::from_vec(vec![6., 8., 10., 12., 14., 16.], vec![3, 2])
+        );
+        assert_eq!(
+            reduce_to_shape(&arr, &[1, 2]),
+            Array::from_vec(vec![30., 36.], vec![1, 2])
+        );
+        assert_eq!(
+            reduce_to_shape(&arr, &[2, 1, 1]),
+            Array::from_vec(vec![15., 51.], vec![2, 1, 1])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reduce_to_shape_incompatible() {
+        reduce_to_shape(&Array::new(1., vec![2, 3]), &[2, 2]);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::linalg::{Array, Numeric};
+
+/// Stacks arrays of the same shape along a new axis.
+///
+/// * `arrays` - Non-empty slice of arrays with equal shapes.
+/// * `axis` - Position of the new axis in the output shape, at most the length
+///   of arrays' shape.
+///
+/// **Panics** if `arrays` is empty, shapes of arrays differ or `axis` is out of bounds.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::{stack, Array};
+///
+/// let a = Array::from_vec(vec![1., 2.], vec![2]);
+/// let b = Array::from_vec(vec![3., 4.], vec![2]);
+///
+/// assert_eq!(stack(&[&a, &b], 0), Array::from_vec(vec![1., 2., 3., 4.], vec![2, 2]));
+/// assert_eq!(stack(&[&a, &b], 1), Array::from_vec(vec![1., 3., 2., 4.], vec![2, 2]));
+/// ```
+pub fn stack<T: Numeric>(arrays: &[&Array<T>], axis: usize) -> Array<T> {
+    let shapes: Vec<Vec<usize>> = arrays.iter().map(|array| array.get_shape()).collect();
+    check_stack_shapes(&shapes, axis);
+    let input_shape = &arrays[0].shape;
+    let outer: usize = input_shape[..axis].iter().product();
+    let inner: usize = input_shape[axis..].iter().product();
+    let mut data = Vec::with_capacity(outer * arrays.len() * inner);
+    for o in 0..outer {
+        for array in arrays {
+            data.extend_from_slice(&array.data[o * inner..(o + 1) * inner]);
+        }
+    }
+    Array {
+        data,
+        shape: get_stacked_shape(input_shape, arrays.len(), axis),
+    }
+}
+
+// Checks if arrays of given shapes can be stacked along `axis`. Panics if not.
+pub(crate) fn check_stack_shapes(shapes: &[Vec<usize>], axis: usize) {
+    if shapes.is_empty() {
+        panic!("At least one array is required for stacking.")
+    }
+    if shapes.iter().any(|shape| shape != &shapes[0]) {
+        panic!("Stacked arrays must have equal shapes. Got: {:?}", shapes)
+    }
+    if axis > shapes[0].len() {
+        panic!(
+            "Axis {} is out of bounds for stacking arrays of shape {:?}.",
+            axis, shapes[0]
+        )
+    }
+}
+
+// Computes shape of `count` stacked arrays of shape `shape`.
+pub(crate) fn get_stacked_shape(shape: &[usize], count: usize, axis: usize) -> Vec<usize> {
+    let mut stacked_shape = shape.to_vec();
+    stacked_shape.insert(axis, count);
+    stacked_shape
+}
+
+impl<T: Numeric> Array<T> {
+    /// Returns the sub-array at `index` along `axis`, with the axis removed.
+    ///
+    /// Selecting from a 1-dimensional array results in an array of shape `[1]`.
+    ///
+    /// * `axis` - Axis to select along.
+    /// * `index` - Index smaller than the size of `axis`.
+    ///
+    /// **Panics** if `axis` or `index` is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Array;
+    ///
+    /// let a = Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![2, 3]);
+    ///
+    /// assert_eq!(a.select(1, 2), Array::from_vec(vec![3., 6.], vec![2]));
+    /// ```
+    pub fn select(&self, axis: usize, index: usize) -> Array<T> {
+        let selected = self.index_select(axis, &[index]);
+        Array {
+            data: selected.data,
+            shape: get_selected_shape(&self.shape, axis),
+        }
+    }
+}
+
+// Computes shape of a sub-array selected along `axis`.
+pub(crate) fn get_selected_shape(shape: &[usize], axis: usize) -> Vec<usize> {
+    let mut selected_shape = shape.to_vec();
+    selected_shape.remove(axis);
+    if selected_shape.is_empty() {
+        selected_shape.push(1);
+    }
+    selected_shape
+}
@@ -0,0 +1,177 @@
+use crate::linalg::{Array, Numeric};
+
+// Splits a shape into number of slices before `axis`, size of `axis` and number
+// of elements after `axis`.
+fn get_axis_layout(shape: &[usize], axis: usize) -> (usize, usize, usize) {
+    if axis >= shape.len() {
+        panic!("Axis {} is out of bounds for shape {:?}.", axis, shape)
+    }
+    (
+        shape[..axis].iter().product(),
+        shape[axis],
+        shape[axis + 1..].iter().product(),
+    )
+}
+
+// Computes softmax along `axis` skipping elements for which `mask` is false.
+// Masked elements and all elements of fully masked slices are set to zero.
+pub(crate) fn masked_softmax<T: Numeric>(
+    array: &Array<T>,
+    axis: usize,
+    mask: Option<&[bool]>,
+) -> Array<T> {
+    let (outer, size, inner) = get_axis_layout(&array.shape, axis);
+    let is_kept = |i: usize| mask.is_none_or(|mask| mask[i]);
+    let mut data = vec![T::zero(); array.data.len()];
+    for o in 0..outer {
+        for i in 0..inner {
+            let indices: Vec<usize> = (0..size)
+                .map(|j| (o * size + j) * inner + i)
+                .filter(|&index| is_kept(index))
+                .collect();
+            let Some(max) = indices
+                .iter()
+                .map(|&index| array.data[index])
+                .reduce(T::max)
+            else {
+                continue;
+            };
+            let mut sum = T::zero();
+            for &index in indices.iter() {
+                data[index] = (array.data[index] - max).exp();
+                sum = sum + data[index];
+            }
+            for &index in indices.iter() {
+                data[index] = data[index] / sum;
+            }
+        }
+    }
+    Array {
+        data,
+        shape: array.shape.clone(),
+    }
+}
+
+// Computes gradient of softmax w.r.t. its input given its output and gradient
+// w.r.t. the output: `output * (grad - sum(grad * output, axis))`.
+pub(crate) fn softmax_backward<T: Numeric>(
+    grad: &Array<T>,
+    output: &Array<T>,
+    axis: usize,
+) -> Array<T> {
+    let (outer, size, inner) = get_axis_layout(&output.shape, axis);
+    let mut data = vec![T::zero(); output.data.len()];
+    for o in 0..outer {
+        for i in 0..inner {
+            let index = |j: usize| (o * size + j) * inner + i;
+            let dot = (0..size).fold(T::zero(), |acc, j| {
+                acc + grad.data[index(j)] * output.data[index(j)]
+            });
+            for j in 0..size {
+                data[index(j)] = output.data[index(j)] * (grad.data[index(j)] - dot);
+            }
+        }
+    }
+    Array {
+        data,
+        shape: output.shape.clone(),
+    }
+}
+
+// Checks if attention scores and padding mask shapes are valid. Panics if not.
+pub(crate) fn check_attention_mask_shape(scores_shape: &[usize], padding_mask_shape: &[usize]) {
+    if scores_shape.len() < 3 {
+        panic!(
+            "Attention scores must be of shape [batch, ..., query, key]. Got: {:?}",
+            scores_shape
+        )
+    }
+    let expected = [scores_shape[0], scores_shape[scores_shape.len() - 1]];
+    if padding_mask_shape != expected {
+        panic!(
+            "Padding mask must be of shape {:?}. Got: {:?}",
+            expected, padding_mask_shape
+        )
+    }
+}
+
+// Computes mask of attention scores of shape `[batch, ..., query, key]`. Keys for
+// which `padding_mask` of shape `[batch, key]` is zero are masked and, if `causal`
+// is true, so are keys with larger positions than a query.
+pub(crate) fn get_attention_mask<T: Numeric>(
+    scores_shape: &[usize],
+    padding_mask: Option<&Array<T>>,
+    causal: bool,
+) -> Option<Vec<bool>> {
+    if padding_mask.is_none() && !causal {
+        return None;
+    }
+    let rank = scores_shape.len();
+    let (queries, keys) = (scores_shape[rank - 2], scores_shape[rank - 1]);
+    let batch_size = scores_shape[0];
+    let batch_len: usize = scores_shape[1..].iter().product();
+    let mask = (0..batch_size * batch_len)
+        .map(|index| {
+            let key = index % keys;
+            let query = index / keys % queries;
+            let batch = index / batch_len;
+            let is_padding =
+                padding_mask.is_some_and(|mask| mask.data[batch * keys + key] == T::zero());
+            !is_padding && (!causal || key <= query)
+        })
+        .collect();
+    Some(mask)
+}
+
+/// Computes softmax of an array along a given axis.
+///
+/// Slices along `axis` are mapped to `exp(x - max(x)) / sum(exp(x - max(x)))`.
+///
+/// * `array` - Input array.
+/// * `axis` - Axis along which probabilities sum to one.
+///
+/// **Panics** if `axis` is out of bounds.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::{softmax, Array};
+///
+/// let a = Array::from_vec(vec![0., 1., 2., 0., 1., 2.], vec![2, 3]);
+///
+/// assert_eq!(
+///     softmax(&a, 0),
+///     Array::from_vec(vec![0.5, 0.5, 0.5, 0.5, 0.5, 0.5], vec![2, 3])
+/// );
+/// ```
+pub fn softmax<T: Numeric>(array: &Array<T>, axis: usize) -> Array<T> {
+    masked_softmax(array, axis, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_masked_softmax_fully_masked_slice() {
+        let a = Array::from_vec(vec![1., 2., 3., 4.], vec![2, 2]);
+        let mask = [true, false, false, false];
+
+        assert_eq!(
+            masked_softmax(&a, 1, Some(&mask)),
+            Array::from_vec(vec![1., 0., 0., 0.], vec![2, 2])
+        );
+    }
+
+    #[test]
+    fn test_get_attention_mask() {
+        let padding_mask = Array::from_vec(vec![1., 1., 0., 1., 1., 1.], vec![2, 3]);
+
+        assert_eq!(
+            get_attention_mask(&[2, 2, 3], Some(&padding_mask), true),
+            Some(vec![
+                true, false, false, true, true, false, true, false, false, true, true, false
+            ])
+        );
+        assert_eq!(get_attention_mask::<f64>(&[2, 2, 3], None, false), None);
+    }
+}
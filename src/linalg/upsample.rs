@@ -0,0 +1,214 @@
+use crate::linalg::{Array, DataFormat, Numeric};
+use num::cast;
+
+/// Interpolation method used when resizing images.
+///
+/// * `Nearest` - Value of the nearest source pixel.
+/// * `Bilinear` - Linear interpolation between four neighbouring source pixels.
+///   Pixels are treated as squares with centers at half-integer coordinates
+///   (corners are not aligned).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interpolation {
+    Nearest,
+    Bilinear,
+}
+
+// Computes, for every output position along one axis, source positions
+// with their interpolation weights.
+fn get_interpolation_weights<T: Numeric>(
+    input_size: usize,
+    output_size: usize,
+    mode: Interpolation,
+) -> Vec<Vec<(usize, T)>> {
+    let scale = input_size as f64 / output_size as f64;
+    (0..output_size)
+        .map(|i| match mode {
+            Interpolation::Nearest => {
+                let src = ((i as f64 * scale).floor() as usize).min(input_size - 1);
+                vec![(src, T::one())]
+            }
+            Interpolation::Bilinear => {
+                let src = ((i as f64 + 0.5) * scale - 0.5)
+                    .max(0.)
+                    .min((input_size - 1) as f64);
+                let low = (src.floor() as usize).min(input_size - 1);
+                let high = (low + 1).min(input_size - 1);
+                let weight_high: T = cast(src - low as f64).unwrap();
+                vec![(low, T::one() - weight_high), (high, weight_high)]
+            }
+        })
+        .collect()
+}
+
+// Resizes spatial dimensions of a `NCHW` array.
+pub(crate) fn resize_nchw<T: Numeric>(
+    input: &Array<T>,
+    output_hw: (usize, usize),
+    mode: Interpolation,
+) -> Array<T> {
+    let (batch, channels, height, width) = (
+        input.shape[0],
+        input.shape[1],
+        input.shape[2],
+        input.shape[3],
+    );
+    let (out_h, out_w) = output_hw;
+    let weights_h = get_interpolation_weights::<T>(height, out_h, mode);
+    let weights_w = get_interpolation_weights::<T>(width, out_w, mode);
+
+    let mut data = vec![T::zero(); batch * channels * out_h * out_w];
+    for image in 0..batch * channels {
+        let input_image = &input.data[image * height * width..(image + 1) * height * width];
+        let output_image = &mut data[image * out_h * out_w..(image + 1) * out_h * out_w];
+        for (i, row_weights) in weights_h.iter().enumerate() {
+            for (j, col_weights) in weights_w.iter().enumerate() {
+                let mut value = T::zero();
+                for &(src_i, weight_i) in row_weights {
+                    for &(src_j, weight_j) in col_weights {
+                        value = value + weight_i * weight_j * input_image[src_i * width + src_j];
+                    }
+                }
+                output_image[i * out_w + j] = value;
+            }
+        }
+    }
+    Array {
+        data,
+        shape: vec![batch, channels, out_h, out_w],
+    }
+}
+
+// Computes gradient of `resize_nchw` w.r.t. its input given gradient w.r.t. its output.
+pub(crate) fn resize_nchw_backward<T: Numeric>(
+    grad: &Array<T>,
+    input_shape: &[usize],
+    mode: Interpolation,
+) -> Array<T> {
+    let (batch, channels, height, width) = (
+        input_shape[0],
+        input_shape[1],
+        input_shape[2],
+        input_shape[3],
+    );
+    let (out_h, out_w) = (grad.shape[2], grad.shape[3]);
+    let weights_h = get_interpolation_weights::<T>(height, out_h, mode);
+    let weights_w = get_interpolation_weights::<T>(width, out_w, mode);
+
+    let mut data = vec![T::zero(); batch * channels * height * width];
+    for image in 0..batch * channels {
+        let grad_image = &grad.data[image * out_h * out_w..(image + 1) * out_h * out_w];
+        let input_image = &mut data[image * height * width..(image + 1) * height * width];
+        for (i, row_weights) in weights_h.iter().enumerate() {
+            for (j, col_weights) in weights_w.iter().enumerate() {
+                let value = grad_image[i * out_w + j];
+                for &(src_i, weight_i) in row_weights {
+                    for &(src_j, weight_j) in col_weights {
+                        let idx = src_i * width + src_j;
+                        input_image[idx] = input_image[idx] + weight_i * weight_j * value;
+                    }
+                }
+            }
+        }
+    }
+    Array {
+        data,
+        shape: input_shape.to_vec(),
+    }
+}
+
+// Checks if upsampling parameters are valid. Panics if not.
+pub(crate) fn check_upsample2d_params(input_shape: &[usize], scale_factor: (usize, usize)) {
+    if input_shape.len() != 4 {
+        panic!(
+            "Upsampling requires 4-dimensional input. Got shape: {:?}",
+            input_shape
+        )
+    }
+    if scale_factor.0 == 0 || scale_factor.1 == 0 {
+        panic!("Scale factor must be positive. Got: {:?}", scale_factor)
+    }
+}
+
+/// Upsamples spatial dimensions of an image batch by integer factors.
+///
+/// * `input` - Array of shape `[N, C, H, W]` or `[N, H, W, C]` depending on `data_format`.
+/// * `scale_factor` - Multipliers of height and width.
+/// * `mode` - Interpolation method.
+/// * `data_format` - Layout of `input` and the output array.
+///
+/// **Panics** if `input` isn't 4-dimensional or `scale_factor` contains zero.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::{upsample2d, Array, DataFormat, Interpolation};
+///
+/// let input = Array::from_vec(vec![1., 2., 3., 4.], vec![1, 1, 2, 2]);
+///
+/// assert_eq!(
+///     upsample2d(&input, (1, 2), Interpolation::Nearest, DataFormat::Nchw),
+///     Array::from_vec(vec![1., 1., 2., 2., 3., 3., 4., 4.], vec![1, 1, 2, 4])
+/// );
+/// assert_eq!(
+///     upsample2d(&input, (1, 2), Interpolation::Bilinear, DataFormat::Nchw),
+///     Array::from_vec(vec![1., 1.25, 1.75, 2., 3., 3.25, 3.75, 4.], vec![1, 1, 2, 4])
+/// );
+/// ```
+pub fn upsample2d<T: Numeric>(
+    input: &Array<T>,
+    scale_factor: (usize, usize),
+    mode: Interpolation,
+    data_format: DataFormat,
+) -> Array<T> {
+    check_upsample2d_params(&input.shape, scale_factor);
+    let input = data_format.array_to_nchw(input);
+    let output_hw = (
+        input.shape[2] * scale_factor.0,
+        input.shape[3] * scale_factor.1,
+    );
+    data_format.array_from_nchw(&resize_nchw(&input, output_hw, mode))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_interpolation_weights_nearest() {
+        let weights = get_interpolation_weights::<f64>(2, 5, Interpolation::Nearest);
+
+        assert_eq!(
+            weights,
+            vec![
+                vec![(0, 1.)],
+                vec![(0, 1.)],
+                vec![(0, 1.)],
+                vec![(1, 1.)],
+                vec![(1, 1.)]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_interpolation_weights_bilinear() {
+        let weights = get_interpolation_weights::<f64>(2, 4, Interpolation::Bilinear);
+
+        assert_eq!(
+            weights,
+            vec![
+                vec![(0, 1.), (1, 0.)],
+                vec![(0, 0.75), (1, 0.25)],
+                vec![(0, 0.25), (1, 0.75)],
+                vec![(1, 1.), (1, 0.)]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resize_nchw_backward_preserves_sum() {
+        let grad = Array::new(1., vec![1, 2, 6, 4]);
+
+        let input_grad = resize_nchw_backward(&grad, &[1, 2, 3, 2], Interpolation::Bilinear);
+
+        assert_eq!(input_grad.data.iter().sum::<f64>(), 48.);
+    }
+}
@@ -0,0 +1,166 @@
+use crate::linalg::Numeric;
+use crate::Array;
+
+/// Clips values of a gradient array to a given interval.
+///
+/// Values smaller than `min` become `min` and values larger than `max` become `max`.
+///
+/// * `grad` - Gradient array to be clipped.
+/// * `min` - Lower bound of the interval.
+/// * `max` - Upper bound of the interval.
+///
+/// **Panics** if `min` is greater than `max`.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::nn::clip::clip_by_value;
+///
+/// let grad = Array::from_vec(vec![-3., -0.5, 0.5, 3.], vec![2, 2]);
+///
+/// assert_eq!(
+///     clip_by_value(&grad, -1., 1.),
+///     Array::from_vec(vec![-1., -0.5, 0.5, 1.], vec![2, 2])
+/// );
+/// ```
+pub fn clip_by_value<T: Numeric>(grad: &Array<T>, min: T, max: T) -> Array<T> {
+    if min > max {
+        panic!(
+            "Lower clipping bound is greater than the upper one. Got: min={}, max={}",
+            min, max
+        )
+    }
+    grad.map(|x| x.max(min).min(max))
+}
+
+/// Rescales a gradient array so that its L2 norm does not exceed `max_norm`.
+///
+/// If the norm of `grad` is less than or equal to `max_norm`, the array is returned unchanged.
+/// Otherwise it is multiplied by `max_norm / norm`.
+///
+/// * `grad` - Gradient array to be clipped.
+/// * `max_norm` - Maximum allowed L2 norm.
+///
+/// **Panics** if `max_norm` is negative.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::nn::clip::clip_by_norm;
+///
+/// let grad = Array::from_vec(vec![6., 8.], vec![1, 2]);
+///
+/// assert_eq!(
+///     clip_by_norm(&grad, 5.),
+///     Array::from_vec(vec![3., 4.], vec![1, 2])
+/// );
+/// ```
+pub fn clip_by_norm<T: Numeric>(grad: &Array<T>, max_norm: T) -> Array<T> {
+    check_max_norm(max_norm);
+    let norm = l2_norm(grad);
+    if norm > max_norm {
+        grad * (max_norm / norm)
+    } else {
+        grad.clone()
+    }
+}
+
+/// Rescales a list of gradient arrays so that their global L2 norm does not exceed `max_norm`.
+///
+/// Global norm is the L2 norm of all elements of all arrays, as if they were
+/// concatenated into a single vector. If it is greater than `max_norm`, every array
+/// is multiplied by `max_norm / global_norm`, which preserves direction of the update.
+///
+/// Returns clipped arrays together with the global norm computed before clipping.
+///
+/// * `grads` - Gradient arrays to be clipped.
+/// * `max_norm` - Maximum allowed global L2 norm.
+///
+/// **Panics** if `max_norm` is negative.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::nn::clip::clip_by_global_norm;
+///
+/// let grad_1 = Array::from_vec(vec![3., 0.], vec![2]);
+/// let grad_2 = Array::from_vec(vec![0., 4.], vec![2]);
+///
+/// let (clipped, global_norm) = clip_by_global_norm(&[grad_1, grad_2], 2.5);
+///
+/// assert_eq!(global_norm, 5.);
+/// assert_eq!(clipped[0], Array::from_vec(vec![1.5, 0.], vec![2]));
+/// assert_eq!(clipped[1], Array::from_vec(vec![0., 2.], vec![2]));
+/// ```
+pub fn clip_by_global_norm<T: Numeric>(grads: &[Array<T>], max_norm: T) -> (Vec<Array<T>>, T) {
+    check_max_norm(max_norm);
+    let global_norm = grads
+        .iter()
+        .map(|grad| grad.data.iter().fold(T::zero(), |acc, &x| acc + x * x))
+        .fold(T::zero(), |acc, x| acc + x)
+        .sqrt();
+    let clipped = if global_norm > max_norm {
+        let scale = max_norm / global_norm;
+        grads.iter().map(|grad| grad * scale).collect()
+    } else {
+        grads.to_vec()
+    };
+    (clipped, global_norm)
+}
+
+// Computes L2 norm of all array's elements.
+fn l2_norm<T: Numeric>(array: &Array<T>) -> T {
+    array
+        .data
+        .iter()
+        .fold(T::zero(), |acc, &x| acc + x * x)
+        .sqrt()
+}
+
+// Checks if maximum norm is non-negative, panics if not.
+fn check_max_norm<T: Numeric>(max_norm: T) {
+    if max_norm < T::zero() {
+        panic!("Maximum norm cannot be negative. Got: {}", max_norm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_l2_norm() {
+        let arr = Array::from_vec(vec![1., 2., 2., 4.], vec![2, 2]);
+
+        assert_eq!(l2_norm(&arr), 5.);
+    }
+
+    #[test]
+    fn test_clip_by_norm_below_max() {
+        let grad = Array::from_vec(vec![3., 4.], vec![2]);
+
+        assert_eq!(clip_by_norm(&grad, 10.), grad);
+    }
+
+    #[test]
+    fn test_clip_by_global_norm_below_max() {
+        let grads = vec![Array::new(1., vec![2, 2]), Array::new(-1., vec![3])];
+
+        let (clipped, global_norm) = clip_by_global_norm(&grads, 10.);
+
+        assert_eq!(clipped, grads);
+        assert!((global_norm - 7f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_clip_by_value_invalid_bounds() {
+        clip_by_value(&Array::new(1., vec![2]), 1., -1.);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_clip_by_norm_negative_max_norm() {
+        clip_by_norm(&Array::new(1., vec![2]), -1.);
+    }
+}
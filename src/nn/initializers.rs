@@ -0,0 +1,424 @@
+use crate::linalg::{Numeric, Rng};
+use crate::Array;
+use num::cast;
+
+// Standard deviation of the standard normal distribution truncated to [-2, 2].
+// Used to correct truncated normal samples, so they have the requested variance.
+const TRUNCATED_NORMAL_STD: f64 = 0.879_625_661_034_239_8;
+
+/// Weight initialization scheme.
+///
+/// Allows to specify initialization method without knowing the exact shape of
+/// the initialized variable, e.g. when configuring layers. Fans are computed from the shape
+/// with `compute_fans`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Initializer<T: Numeric> {
+    /// Fills with zeros.
+    Zeros,
+    /// Fills with ones.
+    Ones,
+    /// Fills with given value.
+    Constant(T),
+    /// Uniform distribution on `[low, high)` interval.
+    Uniform(T, T),
+    /// Normal distribution with given mean and standard deviation.
+    Normal(T, T),
+    /// Truncated normal distribution with given mean and standard deviation.
+    TruncatedNormal(T, T),
+    /// See `glorot_uniform`.
+    GlorotUniform,
+    /// See `glorot_normal`.
+    GlorotNormal,
+    /// See `he_uniform`.
+    HeUniform,
+    /// See `he_normal`.
+    HeNormal,
+    /// See `lecun_normal`.
+    LecunNormal,
+    /// Orthogonal matrix multiplied by given gain, see `orthogonal`. Requires at least
+    /// 2 dimensions.
+    Orthogonal(T),
+}
+
+impl<T: Numeric> Initializer<T> {
+    /// Creates a new array of a given shape using the initialization scheme.
+    ///
+    /// * `shape` - Shape of the array.
+    /// * `rng` - Random number generator used for sampling.
+    ///
+    /// **Panics** if `shape` is empty or contains zero, or if the scheme is `Orthogonal`
+    /// and `shape` has less than 2 dimensions.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Rng;
+    /// use neurust::nn::initializers::Initializer;
+    ///
+    /// let mut rng = Rng::new(0);
+    /// let kernel = Initializer::<f32>::GlorotUniform.initialize(vec![3, 2], &mut rng);
+    ///
+    /// assert_eq!(kernel.get_shape(), vec![3, 2]);
+    /// ```
+    pub fn initialize(&self, shape: Vec<usize>, rng: &mut Rng) -> Array<T> {
+        let (fan_in, fan_out) = compute_fans(&shape);
+        match *self {
+            Initializer::Zeros => zeros(shape),
+            Initializer::Ones => ones(shape),
+            Initializer::Constant(value) => Array::new(value, shape),
+            Initializer::Uniform(low, high) => uniform(shape, low, high, rng),
+            Initializer::Normal(mean, std) => normal(shape, mean, std, rng),
+            Initializer::TruncatedNormal(mean, std) => truncated_normal(shape, mean, std, rng),
+            Initializer::GlorotUniform => glorot_uniform(shape, fan_in, fan_out, rng),
+            Initializer::GlorotNormal => glorot_normal(shape, fan_in, fan_out, rng),
+            Initializer::HeUniform => he_uniform(shape, fan_in, rng),
+            Initializer::HeNormal => he_normal(shape, fan_in, rng),
+            Initializer::LecunNormal => lecun_normal(shape, fan_in, rng),
+            Initializer::Orthogonal(gain) => orthogonal(shape, gain, rng),
+        }
+    }
+}
+
+/// Computes number of input and output units of a weight array.
+///
+/// One dimensional shapes have both fans equal to their length. Two dimensional shapes
+/// are treated as dense kernels of shape `[fan_in, fan_out]`. Longer shapes are treated as
+/// convolution kernels of shape `[out_channels, in_channels, ...]`, where trailing
+/// dimensions form a receptive field.
+///
+/// * `shape` - Shape of the weight array.
+///
+/// **Panics** if `shape` is empty.
+///
+/// # Examples
+/// ```
+/// use neurust::nn::initializers::compute_fans;
+///
+/// assert_eq!(compute_fans(&[4, 3]), (4, 3));
+/// assert_eq!(compute_fans(&[8, 2, 3, 3]), (18, 72));
+/// ```
+pub fn compute_fans(shape: &[usize]) -> (usize, usize) {
+    match shape.len() {
+        0 => panic!("Cannot compute fans of an empty shape."),
+        1 => (shape[0], shape[0]),
+        2 => (shape[0], shape[1]),
+        _ => {
+            let receptive_field: usize = shape[2..].iter().product();
+            (shape[1] * receptive_field, shape[0] * receptive_field)
+        }
+    }
+}
+
+/// Creates a new array filled with zeros.
+///
+/// * `shape` - Shape of the array.
+///
+/// **Panics** if `shape` contains zero.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::nn::initializers::zeros;
+///
+/// assert_eq!(zeros::<f32>(vec![2, 2]), Array::new(0., vec![2, 2]));
+/// ```
+pub fn zeros<T: Numeric>(shape: Vec<usize>) -> Array<T> {
+    Array::new(T::zero(), shape)
+}
+
+/// Creates a new array filled with ones.
+///
+/// * `shape` - Shape of the array.
+///
+/// **Panics** if `shape` contains zero.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::nn::initializers::ones;
+///
+/// assert_eq!(ones::<f32>(vec![2, 2]), Array::new(1., vec![2, 2]));
+/// ```
+pub fn ones<T: Numeric>(shape: Vec<usize>) -> Array<T> {
+    Array::new(T::one(), shape)
+}
+
+/// Creates a new array with values sampled from uniform distribution on `[low, high)`.
+///
+/// * `shape` - Shape of the array.
+/// * `low` - Lower bound of the interval.
+/// * `high` - Upper bound of the interval.
+/// * `rng` - Random number generator used for sampling.
+///
+/// **Panics** if `shape` contains zero or `low` is greater than `high`.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::Rng;
+/// use neurust::nn::initializers::uniform;
+///
+/// let arr = uniform(vec![2, 3], -1., 1., &mut Rng::new(0));
+///
+/// assert!(arr.map(|x: f64| x.abs()).i(vec![1, 2]) <= 1.);
+/// ```
+pub fn uniform<T: Numeric>(shape: Vec<usize>, low: T, high: T, rng: &mut Rng) -> Array<T> {
+    Array::random_uniform(shape, low, high, rng)
+}
+
+/// Creates a new array with values sampled from normal distribution.
+///
+/// * `shape` - Shape of the array.
+/// * `mean` - Mean of the distribution.
+/// * `std` - Standard deviation of the distribution.
+/// * `rng` - Random number generator used for sampling.
+///
+/// **Panics** if `shape` contains zero or `std` is negative.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::Rng;
+/// use neurust::nn::initializers::normal;
+///
+/// let arr = normal::<f64>(vec![2, 3], 0., 0.05, &mut Rng::new(0));
+///
+/// assert_eq!(arr.get_shape(), vec![2, 3]);
+/// ```
+pub fn normal<T: Numeric>(shape: Vec<usize>, mean: T, std: T, rng: &mut Rng) -> Array<T> {
+    Array::random_normal(shape, mean, std, rng)
+}
+
+/// Creates a new array with values sampled from truncated normal distribution.
+///
+/// Values further than two standard deviations from the mean are discarded and re-drawn.
+///
+/// * `shape` - Shape of the array.
+/// * `mean` - Mean of the distribution.
+/// * `std` - Standard deviation of the distribution before truncation.
+/// * `rng` - Random number generator used for sampling.
+///
+/// **Panics** if `shape` contains zero or `std` is negative.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::Rng;
+/// use neurust::nn::initializers::truncated_normal;
+///
+/// let arr = truncated_normal(vec![10, 10], 0., 1., &mut Rng::new(0));
+///
+/// assert!(arr.map(|x: f64| x.abs()).i(vec![5, 5]) <= 2.);
+/// ```
+pub fn truncated_normal<T: Numeric>(shape: Vec<usize>, mean: T, std: T, rng: &mut Rng) -> Array<T> {
+    Array::random_truncated_normal(shape, mean, std, rng)
+}
+
+/// Creates a new array using *Glorot* (*Xavier*) uniform initialization.
+///
+/// Values are sampled from uniform distribution on `[-limit, limit)`, where
+/// `limit = sqrt(6 / (fan_in + fan_out))`.
+///
+/// * `shape` - Shape of the array.
+/// * `fan_in` - Number of input units.
+/// * `fan_out` - Number of output units.
+/// * `rng` - Random number generator used for sampling.
+///
+/// **Panics** if `shape` contains zero.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::Rng;
+/// use neurust::nn::initializers::glorot_uniform;
+///
+/// let kernel = glorot_uniform::<f32>(vec![4, 2], 4, 2, &mut Rng::new(0));
+///
+/// assert_eq!(kernel.get_shape(), vec![4, 2]);
+/// ```
+pub fn glorot_uniform<T: Numeric>(
+    shape: Vec<usize>,
+    fan_in: usize,
+    fan_out: usize,
+    rng: &mut Rng,
+) -> Array<T> {
+    symmetric_uniform(shape, (6. / (fan_in + fan_out) as f64).sqrt(), rng)
+}
+
+/// Creates a new array using *Glorot* (*Xavier*) normal initialization.
+///
+/// Values are sampled from truncated normal distribution with zero mean and
+/// standard deviation equal to `sqrt(2 / (fan_in + fan_out))`.
+///
+/// * `shape` - Shape of the array.
+/// * `fan_in` - Number of input units.
+/// * `fan_out` - Number of output units.
+/// * `rng` - Random number generator used for sampling.
+///
+/// **Panics** if `shape` contains zero.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::Rng;
+/// use neurust::nn::initializers::glorot_normal;
+///
+/// let kernel = glorot_normal::<f32>(vec![4, 2], 4, 2, &mut Rng::new(0));
+///
+/// assert_eq!(kernel.get_shape(), vec![4, 2]);
+/// ```
+pub fn glorot_normal<T: Numeric>(
+    shape: Vec<usize>,
+    fan_in: usize,
+    fan_out: usize,
+    rng: &mut Rng,
+) -> Array<T> {
+    variance_scaling_normal(shape, 2. / (fan_in + fan_out) as f64, rng)
+}
+
+/// Creates a new array using *He* (*Kaiming*) uniform initialization.
+///
+/// Values are sampled from uniform distribution on `[-limit, limit)`, where
+/// `limit = sqrt(6 / fan_in)`.
+///
+/// * `shape` - Shape of the array.
+/// * `fan_in` - Number of input units.
+/// * `rng` - Random number generator used for sampling.
+///
+/// **Panics** if `shape` contains zero.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::Rng;
+/// use neurust::nn::initializers::he_uniform;
+///
+/// let kernel = he_uniform::<f32>(vec![4, 2], 4, &mut Rng::new(0));
+///
+/// assert_eq!(kernel.get_shape(), vec![4, 2]);
+/// ```
+pub fn he_uniform<T: Numeric>(shape: Vec<usize>, fan_in: usize, rng: &mut Rng) -> Array<T> {
+    symmetric_uniform(shape, (6. / fan_in as f64).sqrt(), rng)
+}
+
+/// Creates a new array using *He* (*Kaiming*) normal initialization.
+///
+/// Values are sampled from truncated normal distribution with zero mean and
+/// standard deviation equal to `sqrt(2 / fan_in)`.
+///
+/// * `shape` - Shape of the array.
+/// * `fan_in` - Number of input units.
+/// * `rng` - Random number generator used for sampling.
+///
+/// **Panics** if `shape` contains zero.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::Rng;
+/// use neurust::nn::initializers::he_normal;
+///
+/// let kernel = he_normal::<f32>(vec![4, 2], 4, &mut Rng::new(0));
+///
+/// assert_eq!(kernel.get_shape(), vec![4, 2]);
+/// ```
+pub fn he_normal<T: Numeric>(shape: Vec<usize>, fan_in: usize, rng: &mut Rng) -> Array<T> {
+    variance_scaling_normal(shape, 2. / fan_in as f64, rng)
+}
+
+/// Creates a new array using *LeCun* normal initialization.
+///
+/// Values are sampled from truncated normal distribution with zero mean and
+/// standard deviation equal to `sqrt(1 / fan_in)`.
+///
+/// * `shape` - Shape of the array.
+/// * `fan_in` - Number of input units.
+/// * `rng` - Random number generator used for sampling.
+///
+/// **Panics** if `shape` contains zero.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::Rng;
+/// use neurust::nn::initializers::lecun_normal;
+///
+/// let kernel = lecun_normal::<f32>(vec![4, 2], 4, &mut Rng::new(0));
+///
+/// assert_eq!(kernel.get_shape(), vec![4, 2]);
+/// ```
+pub fn lecun_normal<T: Numeric>(shape: Vec<usize>, fan_in: usize, rng: &mut Rng) -> Array<T> {
+    variance_scaling_normal(shape, 1. / fan_in as f64, rng)
+}
+
+/// Creates a new array using orthogonal initialization.
+///
+/// The array is treated as a matrix with the last dimension as columns and the
+/// rest of dimensions flattened into rows. The matrix is obtained by orthonormalizing
+/// a random normal matrix, so its rows or columns (whichever are fewer) are orthonormal.
+/// Then it is multiplied by `gain`.
+///
+/// * `shape` - Shape of the array.
+/// * `gain` - Multiplicative factor.
+/// * `rng` - Random number generator used for sampling.
+///
+/// **Panics** if `shape` contains zero or has less than 2 dimensions.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::{reduce_max, Rng};
+/// use neurust::nn::initializers::orthogonal;
+/// use neurust::prelude::*;
+///
+/// let q = orthogonal::<f64>(vec![3, 3], 1., &mut Rng::new(0));
+/// let identity = Array::from_vec(vec![1., 0., 0., 0., 1., 0., 0., 0., 1.], vec![3, 3]);
+/// let error = q.transpose().matmul(&q).sub(&identity).map(|x| x.abs());
+///
+/// assert!(reduce_max(&error, None, false).i(vec![0]) < 1e-10);
+/// ```
+pub fn orthogonal<T: Numeric>(shape: Vec<usize>, gain: T, rng: &mut Rng) -> Array<T> {
+    if shape.len() < 2 {
+        panic!(
+            "Orthogonal initialization requires at least 2 dimensions. Got shape: {:?}",
+            shape
+        )
+    }
+    let num_cols = *shape.last().unwrap();
+    let num_rows: usize = shape[..shape.len() - 1].iter().product();
+    let (n, m) = (num_rows.max(num_cols), num_rows.min(num_cols));
+
+    // Columns of `[n, m]` matrix are orthonormalized with modified Gram-Schmidt process.
+    let mut q = Array::<f64>::random_normal(vec![n, m], 0., 1., rng).data;
+    for j in 0..m {
+        for k in 0..j {
+            let dot: f64 = (0..n).map(|i| q[i * m + j] * q[i * m + k]).sum();
+            for i in 0..n {
+                q[i * m + j] -= dot * q[i * m + k];
+            }
+        }
+        let norm = (0..n).map(|i| q[i * m + j].powi(2)).sum::<f64>().sqrt();
+        for i in 0..n {
+            q[i * m + j] /= norm;
+        }
+    }
+
+    let mut data = Vec::with_capacity(n * m);
+    for i in 0..num_rows {
+        for j in 0..num_cols {
+            let value = if num_rows >= num_cols {
+                q[i * m + j]
+            } else {
+                q[j * m + i]
+            };
+            data.push(gain * cast::<_, T>(value).unwrap());
+        }
+    }
+    Array::from_vec(data, shape)
+}
+
+// Samples truncated normal values with zero mean and given variance.
+fn variance_scaling_normal<T: Numeric>(
+    shape: Vec<usize>,
+    variance: f64,
+    rng: &mut Rng,
+) -> Array<T> {
+    let std = variance.sqrt() / TRUNCATED_NORMAL_STD;
+    truncated_normal(shape, T::zero(), cast::<_, T>(std).unwrap(), rng)
+}
+
+// Samples uniform values from `[-limit, limit)` interval.
+fn symmetric_uniform<T: Numeric>(shape: Vec<usize>, limit: f64, rng: &mut Rng) -> Array<T> {
+    let limit = cast::<_, T>(limit).unwrap();
+    Array::random_uniform(shape, -limit, limit, rng)
+}
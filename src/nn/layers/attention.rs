@@ -0,0 +1,189 @@
+use crate::linalg::{Numeric, Rng};
+use crate::nn::initializers::Initializer;
+use crate::nn::layers::Dense;
+use crate::nn::Module;
+use crate::tensor::attention::scaled_dot_product_attention;
+use crate::tensor::shape::{permute, reshape};
+use crate::Tensor;
+
+/// Multi-head attention layer.
+///
+/// Queries, keys and values of shape `[batch, sequence, embed_dim]` are linearly
+/// projected and split into `num_heads` heads of size `embed_dim / num_heads`.
+/// Scaled dot-product attention is computed for every head separately, then heads are
+/// concatenated and linearly projected to `embed_dim`.
+///
+/// * `query`, `key`, `value` - Projections of queries, keys and values.
+/// * `output` - Projection of concatenated heads.
+/// * `num_heads` - Number of attention heads.
+pub struct MultiHeadAttention<T: Numeric> {
+    query: Dense<T>,
+    key: Dense<T>,
+    value: Dense<T>,
+    output: Dense<T>,
+    num_heads: usize,
+}
+
+impl<T: Numeric> MultiHeadAttention<T> {
+    /// Creates a new `MultiHeadAttention` layer.
+    ///
+    /// Kernels are initialized with Glorot uniform initializer and biases with zeros.
+    ///
+    /// * `embed_dim` - Size of the last dimension of inputs and outputs.
+    /// * `num_heads` - Number of attention heads, must divide `embed_dim`.
+    /// * `use_bias` - If true, projections have biases.
+    /// * `rng` - Random number generator used by initializers.
+    ///
+    /// **Panics** if `embed_dim` isn't divisible by `num_heads`.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Rng;
+    /// use neurust::nn::layers::MultiHeadAttention;
+    /// use neurust::nn::Module;
+    /// use neurust::prelude::*;
+    ///
+    /// let attention = MultiHeadAttention::new(8, 2, true, &mut Rng::new(0));
+    /// let input = Tensor::new_variable(Array::new(1., vec![3, 5, 8]));
+    ///
+    /// assert_eq!(attention.forward(&input).shape(), vec![3, 5, 8]);
+    /// assert_eq!(attention.parameters().len(), 8);
+    /// ```
+    pub fn new(
+        embed_dim: usize,
+        num_heads: usize,
+        use_bias: bool,
+        rng: &mut Rng,
+    ) -> MultiHeadAttention<T> {
+        if num_heads == 0 || !embed_dim.is_multiple_of(num_heads) {
+            panic!(
+                "Embedding dimension {} must be divisible by number of heads {}.",
+                embed_dim, num_heads
+            )
+        }
+        let mut projection = || {
+            Dense::new(
+                embed_dim,
+                embed_dim,
+                None,
+                Initializer::GlorotUniform,
+                use_bias,
+                rng,
+            )
+        };
+        MultiHeadAttention {
+            query: projection(),
+            key: projection(),
+            value: projection(),
+            output: projection(),
+            num_heads,
+        }
+    }
+
+    /// Returns number of attention heads.
+    pub fn num_heads(&self) -> usize {
+        self.num_heads
+    }
+
+    /// Creates a tensor that evaluates to attention of `query` to `key` and `value`.
+    ///
+    /// * `query` - Tensor of shape `[batch, query, embed_dim]`.
+    /// * `key` - Tensor of shape `[batch, key, embed_dim]`.
+    /// * `value` - Tensor of shape `[batch, key, embed_dim]`.
+    /// * `padding_mask` - Optional tensor of shape `[batch, key]`, zero for padding keys
+    ///   that shouldn't be attended to. It can be a placeholder.
+    /// * `causal` - If true, queries can't attend to keys at later positions.
+    ///
+    /// **Panics** if shapes of tensors are invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Rng;
+    /// use neurust::nn::layers::MultiHeadAttention;
+    /// use neurust::prelude::*;
+    ///
+    /// let attention = MultiHeadAttention::new(4, 2, false, &mut Rng::new(0));
+    /// let query = Tensor::new_variable(Array::new(1., vec![2, 3, 4]));
+    /// let memory = Tensor::new_variable(Array::new(1., vec![2, 6, 4]));
+    /// let padding_mask = Tensor::new_placeholder("mask".to_owned(), vec![2, 6]);
+    /// let output = attention.attend(&query, &memory, &memory, Some(&padding_mask), false);
+    ///
+    /// assert_eq!(output.shape(), vec![2, 3, 4]);
+    /// ```
+    pub fn attend(
+        &self,
+        query: &Tensor<T>,
+        key: &Tensor<T>,
+        value: &Tensor<T>,
+        padding_mask: Option<&Tensor<T>>,
+        causal: bool,
+    ) -> Tensor<T> {
+        let query_shape = query.shape();
+        let heads = scaled_dot_product_attention(
+            &self.split_heads(&self.query, query),
+            &self.split_heads(&self.key, key),
+            &self.split_heads(&self.value, value),
+            padding_mask,
+            causal,
+        );
+        // [batch, heads, sequence, head_dim] -> [batch, sequence, embed_dim]
+        let concatenated = reshape(&permute(&heads, &[0, 2, 1, 3]), query_shape);
+        apply_to_sequence(&self.output, &concatenated)
+    }
+
+    // Projects input of shape `[batch, sequence, embed_dim]` and splits the result into
+    // heads of shape `[batch, heads, sequence, head_dim]`.
+    fn split_heads(&self, projection: &Dense<T>, input: &Tensor<T>) -> Tensor<T> {
+        let shape = input.shape();
+        if shape.len() != 3 {
+            panic!(
+                "Attention requires inputs of shape [batch, sequence, embed_dim]. Got: {:?}",
+                shape
+            )
+        }
+        let projected = apply_to_sequence(projection, input);
+        let head_dim = shape[2] / self.num_heads;
+        permute(
+            &reshape(
+                &projected,
+                vec![shape[0], shape[1], self.num_heads, head_dim],
+            ),
+            &[0, 2, 1, 3],
+        )
+    }
+}
+
+impl<T: Numeric> Module<T> for MultiHeadAttention<T> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        self.attend(input, input, input, None, false)
+    }
+
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        [
+            ("query", &self.query),
+            ("key", &self.key),
+            ("value", &self.value),
+            ("output", &self.output),
+        ]
+        .iter()
+        .flat_map(|&(prefix, layer)| {
+            layer
+                .named_parameters()
+                .into_iter()
+                .map(move |(name, parameter)| (format!("{}_{}", prefix, name), parameter))
+        })
+        .collect()
+    }
+
+    fn name(&self) -> &str {
+        "MultiHeadAttention"
+    }
+}
+
+// Applies a layer to every element of a sequence of shape `[batch, sequence, features]`.
+pub(crate) fn apply_to_sequence<T: Numeric>(layer: &dyn Module<T>, input: &Tensor<T>) -> Tensor<T> {
+    let shape = input.shape();
+    let output = layer.forward(&reshape(input, vec![shape[0] * shape[1], shape[2]]));
+    let features = output.shape()[1];
+    reshape(&output, vec![shape[0], shape[1], features])
+}
@@ -0,0 +1,144 @@
+use crate::linalg::Numeric;
+use crate::nn::initializers::{ones, zeros};
+use crate::nn::Module;
+use crate::tensor::normalization::batch_norm;
+use crate::tensor::TrainingFlag;
+use crate::Tensor;
+
+/// Batch normalization layer.
+///
+/// Normalizes every channel over the batch (and spatial) dimensions, then scales it
+/// by `gamma` and shifts by `beta`. During training statistics of the current batch
+/// are used and running statistics are updated once per `Tensor::eval_and_grads()` call,
+/// during inference the running statistics are used. The layer starts in training mode.
+///
+/// * `gamma` - Trainable scale of shape `[num_features]`.
+/// * `beta` - Trainable shift of shape `[num_features]`.
+/// * `running_mean` - Non-trainable variable of shape `[num_features]`.
+/// * `running_variance` - Non-trainable variable of shape `[num_features]`.
+/// * `training` - Flag switching between training and inference mode.
+/// * `axis` - Channel axis of input tensors.
+/// * `momentum` - Momentum of running statistics.
+/// * `epsilon` - Small value added to variance for numerical stability.
+pub struct BatchNorm<T: Numeric> {
+    gamma: Tensor<T>,
+    beta: Tensor<T>,
+    running_mean: Tensor<T>,
+    running_variance: Tensor<T>,
+    training: TrainingFlag,
+    axis: usize,
+    momentum: T,
+    epsilon: T,
+}
+
+impl<T: Numeric> BatchNorm<T> {
+    /// Creates a new `BatchNorm` layer.
+    ///
+    /// `gamma` and running variance are initialized with ones, `beta` and running mean
+    /// with zeros.
+    ///
+    /// * `num_features` - Number of channels.
+    /// * `axis` - Channel axis of input tensors, e.g. `1` for `NCHW` images.
+    /// * `momentum` - Momentum of running statistics, from range `[0, 1]`, e.g. `0.99`.
+    /// * `epsilon` - Small value added to variance for numerical stability, e.g. `1e-5`.
+    ///
+    /// **Panics** if `num_features` is zero or `momentum` is out of range.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::nn::layers::BatchNorm;
+    /// use neurust::nn::Module;
+    /// use neurust::prelude::*;
+    ///
+    /// let batch_norm = BatchNorm::new(2, 1, 0.9, 1e-5);
+    /// let input = Tensor::new_variable(Array::from_vec(vec![1., 2., 3., 4.], vec![2, 2]));
+    /// let output = batch_norm.forward(&input);
+    ///
+    /// output.eval_and_grads(&[], None);
+    /// batch_norm.set_training(false);
+    ///
+    /// assert_eq!(batch_norm.parameters().len(), 2);
+    /// assert!(!batch_norm.is_training());
+    /// ```
+    pub fn new(num_features: usize, axis: usize, momentum: T, epsilon: T) -> BatchNorm<T> {
+        if momentum < T::zero() || momentum > T::one() {
+            panic!(
+                "BatchNorm momentum must be in range [0, 1]. Got: {}",
+                momentum
+            )
+        }
+        BatchNorm {
+            gamma: Tensor::new_variable(ones(vec![num_features])),
+            beta: Tensor::new_variable(zeros(vec![num_features])),
+            running_mean: Tensor::new_variable(zeros(vec![num_features])),
+            running_variance: Tensor::new_variable(ones(vec![num_features])),
+            training: TrainingFlag::new(true),
+            axis,
+            momentum,
+            epsilon,
+        }
+    }
+
+    /// Returns scale variable of shape `[num_features]`.
+    pub fn gamma(&self) -> &Tensor<T> {
+        &self.gamma
+    }
+
+    /// Returns shift variable of shape `[num_features]`.
+    pub fn beta(&self) -> &Tensor<T> {
+        &self.beta
+    }
+
+    /// Returns non-trainable variable with running mean.
+    pub fn running_mean(&self) -> &Tensor<T> {
+        &self.running_mean
+    }
+
+    /// Returns non-trainable variable with running variance.
+    pub fn running_variance(&self) -> &Tensor<T> {
+        &self.running_variance
+    }
+
+    /// Returns true if the layer is in training mode.
+    pub fn is_training(&self) -> bool {
+        self.training.is_training()
+    }
+}
+
+impl<T: Numeric> Module<T> for BatchNorm<T> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        batch_norm(
+            input,
+            &self.gamma,
+            &self.beta,
+            &self.running_mean,
+            &self.running_variance,
+            &self.training,
+            self.axis,
+            self.momentum,
+            self.epsilon,
+        )
+    }
+
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        vec![
+            ("gamma".to_owned(), &self.gamma),
+            ("beta".to_owned(), &self.beta),
+        ]
+    }
+
+    fn named_buffers(&self) -> Vec<(String, &Tensor<T>)> {
+        vec![
+            ("running_mean".to_owned(), &self.running_mean),
+            ("running_variance".to_owned(), &self.running_variance),
+        ]
+    }
+
+    fn name(&self) -> &str {
+        "BatchNorm"
+    }
+
+    fn set_training(&self, training: bool) {
+        self.training.set(training);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::linalg::{Numeric, Rng};
+use crate::nn::initializers::{zeros, Initializer};
+use crate::nn::layers::Activation;
+use crate::nn::Module;
+use crate::Tensor;
+
+/// Densely-connected layer.
+///
+/// Computes `activation(input x kernel + bias)`, where `x` is a matrix product.
+/// Input tensor should have shape `[..., input_dim]` and the output has shape `[..., units]`.
+///
+/// * `kernel` - Variable of shape `[input_dim, units]`.
+/// * `bias` - Variable of shape `[units]`. This is `None` for layers without bias.
+/// * `activation` - Function applied to the output. `None` means linear activation.
+pub struct Dense<T: Numeric> {
+    kernel: Tensor<T>,
+    bias: Option<Tensor<T>>,
+    activation: Option<Activation<T>>,
+}
+
+impl<T: Numeric> Dense<T> {
+    /// Creates a new `Dense` layer.
+    ///
+    /// Bias is initialized with zeros.
+    ///
+    /// * `input_dim` - Size of the last dimension of input tensors.
+    /// * `units` - Size of the last dimension of output tensors.
+    /// * `activation` - Function applied to the output. `None` means linear activation.
+    /// * `kernel_initializer` - Initialization scheme of the kernel.
+    /// * `use_bias` - If true, bias is added to the output.
+    /// * `rng` - Random number generator used by `kernel_initializer`.
+    ///
+    /// **Panics** if `input_dim` or `units` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Rng;
+    /// use neurust::nn::initializers::Initializer;
+    /// use neurust::nn::layers::Dense;
+    /// use neurust::nn::Module;
+    /// use neurust::prelude::*;
+    /// use neurust::tensor::math::relu;
+    ///
+    /// let dense = Dense::new(3, 2, Some(relu), Initializer::Ones, true, &mut Rng::new(0));
+    /// let input = Tensor::new_variable(Array::from_vec(vec![1., 2., -4.], vec![1, 3]));
+    ///
+    /// assert_eq!(dense.forward(&input).eval(None), Array::new(0., vec![1, 2]));
+    /// assert_eq!(dense.parameters().len(), 2);
+    /// ```
+    pub fn new(
+        input_dim: usize,
+        units: usize,
+        activation: Option<Activation<T>>,
+        kernel_initializer: Initializer<T>,
+        use_bias: bool,
+        rng: &mut Rng,
+    ) -> Dense<T> {
+        let kernel =
+            Tensor::new_variable(kernel_initializer.initialize(vec![input_dim, units], rng));
+        let bias = if use_bias {
+            Some(Tensor::new_variable(zeros(vec![units])))
+        } else {
+            None
+        };
+        Dense {
+            kernel,
+            bias,
+            activation,
+        }
+    }
+
+    /// Returns kernel variable of shape `[input_dim, units]`.
+    pub fn kernel(&self) -> &Tensor<T> {
+        &self.kernel
+    }
+
+    /// Returns bias variable of shape `[units]` or `None` if the layer doesn't use bias.
+    pub fn bias(&self) -> Option<&Tensor<T>> {
+        self.bias.as_ref()
+    }
+}
+
+impl<T: Numeric> Module<T> for Dense<T> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        let mut output = input.matmul(&self.kernel);
+        if let Some(bias) = &self.bias {
+            output = output + bias;
+        }
+        if let Some(activation) = self.activation {
+            output = activation(&output);
+        }
+        output
+    }
+
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        let mut parameters = vec![("kernel".to_owned(), &self.kernel)];
+        if let Some(bias) = &self.bias {
+            parameters.push(("bias".to_owned(), bias));
+        }
+        parameters
+    }
+
+    fn name(&self) -> &str {
+        "Dense"
+    }
+}
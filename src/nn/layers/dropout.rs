@@ -0,0 +1,81 @@
+use crate::linalg::{Numeric, Rng};
+use crate::nn::Module;
+use crate::tensor::dropout::dropout;
+use crate::tensor::TrainingFlag;
+use crate::Tensor;
+use std::cell::RefCell;
+
+/// Dropout layer.
+///
+/// During training randomly zeroes elements of the input with probability `rate`
+/// and scales the remaining ones by `1 / (1 - rate)`. During inference the input is
+/// passed through unchanged. The layer starts in training mode and has no trainable
+/// variables.
+///
+/// * `rate` - Probability of dropping an element.
+/// * `training` - Flag switching between training and inference mode.
+/// * `rng` - Random number generator seeding masks of created tensors.
+pub struct Dropout<T: Numeric> {
+    rate: T,
+    training: TrainingFlag,
+    rng: RefCell<Rng>,
+}
+
+impl<T: Numeric> Dropout<T> {
+    /// Creates a new `Dropout` layer.
+    ///
+    /// * `rate` - Probability of dropping an element, from range `[0, 1)`.
+    /// * `rng` - Random number generator used to seed the layer's masks.
+    ///
+    /// **Panics** if `rate` is out of range.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Rng;
+    /// use neurust::nn::layers::Dropout;
+    /// use neurust::nn::Module;
+    /// use neurust::prelude::*;
+    ///
+    /// let dropout = Dropout::new(0.5, &mut Rng::new(0));
+    /// let input = Tensor::new_variable(Array::new(1., vec![2, 2]));
+    /// let output = dropout.forward(&input);
+    ///
+    /// dropout.set_training(false);
+    ///
+    /// assert_eq!(output.eval(None), Array::new(1., vec![2, 2]));
+    /// assert!(dropout.parameters().is_empty());
+    /// ```
+    pub fn new(rate: T, rng: &mut Rng) -> Dropout<T> {
+        if rate < T::zero() || rate >= T::one() {
+            panic!("Dropout rate must be in range [0, 1). Got: {}", rate)
+        }
+        Dropout {
+            rate,
+            training: TrainingFlag::new(true),
+            rng: RefCell::new(Rng::new(rng.next_u64())),
+        }
+    }
+
+    /// Returns true if the layer is in training mode.
+    pub fn is_training(&self) -> bool {
+        self.training.is_training()
+    }
+}
+
+impl<T: Numeric> Module<T> for Dropout<T> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        dropout(input, self.rate, &self.training, &mut self.rng.borrow_mut())
+    }
+
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        vec![]
+    }
+
+    fn name(&self) -> &str {
+        "Dropout"
+    }
+
+    fn set_training(&self, training: bool) {
+        self.training.set(training);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::linalg::Numeric;
+use crate::nn::initializers::{ones, zeros};
+use crate::nn::Module;
+use crate::tensor::normalization::group_norm;
+use crate::Tensor;
+
+/// Group normalization layer.
+///
+/// Splits channels into groups and normalizes every group of every sample, then
+/// scales every channel by `gamma` and shifts it by `beta`. Unlike batch normalization
+/// it doesn't depend on the batch size and behaves identically during training and
+/// inference.
+///
+/// * `gamma` - Trainable scale of shape `[num_channels]`.
+/// * `beta` - Trainable shift of shape `[num_channels]`.
+/// * `num_groups` - Number of channel groups.
+/// * `axis` - Channel axis of input tensors.
+/// * `epsilon` - Small value added to variance for numerical stability.
+pub struct GroupNorm<T: Numeric> {
+    gamma: Tensor<T>,
+    beta: Tensor<T>,
+    num_groups: usize,
+    axis: usize,
+    epsilon: T,
+}
+
+impl<T: Numeric> GroupNorm<T> {
+    /// Creates a new `GroupNorm` layer.
+    ///
+    /// `gamma` is initialized with ones, `beta` with zeros.
+    ///
+    /// * `num_groups` - Number of channel groups, must divide `num_channels`.
+    /// * `num_channels` - Number of channels.
+    /// * `axis` - Channel axis of input tensors, e.g. `1` for `NCHW` images.
+    /// * `epsilon` - Small value added to variance for numerical stability, e.g. `1e-5`.
+    ///
+    /// **Panics** if `num_channels` isn't divisible by `num_groups`.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::nn::layers::GroupNorm;
+    /// use neurust::nn::Module;
+    /// use neurust::prelude::*;
+    ///
+    /// let group_norm = GroupNorm::new(2, 4, 1, 1e-5);
+    /// let input = Tensor::new_variable(Array::new(1., vec![3, 4, 5, 5]));
+    ///
+    /// assert_eq!(group_norm.forward(&input).shape(), vec![3, 4, 5, 5]);
+    /// assert_eq!(group_norm.parameters().len(), 2);
+    /// ```
+    pub fn new(num_groups: usize, num_channels: usize, axis: usize, epsilon: T) -> GroupNorm<T> {
+        if num_groups == 0 || !num_channels.is_multiple_of(num_groups) {
+            panic!(
+                "Number of channels ({}) must be divisible by number of groups ({}).",
+                num_channels, num_groups
+            )
+        }
+        GroupNorm {
+            gamma: Tensor::new_variable(ones(vec![num_channels])),
+            beta: Tensor::new_variable(zeros(vec![num_channels])),
+            num_groups,
+            axis,
+            epsilon,
+        }
+    }
+
+    /// Returns scale variable of shape `[num_channels]`.
+    pub fn gamma(&self) -> &Tensor<T> {
+        &self.gamma
+    }
+
+    /// Returns shift variable of shape `[num_channels]`.
+    pub fn beta(&self) -> &Tensor<T> {
+        &self.beta
+    }
+}
+
+impl<T: Numeric> Module<T> for GroupNorm<T> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        group_norm(
+            input,
+            &self.gamma,
+            &self.beta,
+            self.num_groups,
+            self.axis,
+            self.epsilon,
+        )
+    }
+
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        vec![
+            ("gamma".to_owned(), &self.gamma),
+            ("beta".to_owned(), &self.beta),
+        ]
+    }
+
+    fn name(&self) -> &str {
+        "GroupNorm"
+    }
+}
@@ -0,0 +1,115 @@
+use crate::linalg::{Numeric, Rng};
+use crate::nn::layers::recurrent::{Gate, Recurrent, RecurrentCell};
+use crate::tensor::math::{sigmoid, tanh};
+use crate::Tensor;
+
+/// Gated recurrent unit cell.
+///
+/// ```text
+/// z = sigmoid(x x W_z + h x U_z + b_z)
+/// r = sigmoid(x x W_r + h x U_r + b_r)
+/// n = tanh(x x W_n + (r * h) x U_n + b_n)
+/// h' = (1 - z) * n + z * h
+/// ```
+///
+/// * `update_gate`, `reset_gate`, `candidate_gate` - Variables of the gates, each with
+///   a kernel of shape `[input_dim, units]`, recurrent kernel of shape `[units, units]`
+///   and bias of shape `[units]`.
+pub struct GRUCell<T: Numeric> {
+    update_gate: Gate<T>,
+    reset_gate: Gate<T>,
+    candidate_gate: Gate<T>,
+    units: usize,
+}
+
+impl<T: Numeric> GRUCell<T> {
+    /// Creates a new `GRUCell`.
+    ///
+    /// Kernels are initialized with Glorot uniform initializer, recurrent kernels with
+    /// orthogonal matrices and biases with zeros.
+    ///
+    /// * `input_dim` - Size of the last dimension of inputs.
+    /// * `units` - Size of the state.
+    /// * `rng` - Random number generator used by initializers.
+    ///
+    /// **Panics** if `input_dim` or `units` is zero.
+    pub fn new(input_dim: usize, units: usize, rng: &mut Rng) -> GRUCell<T> {
+        GRUCell {
+            update_gate: Gate::new(input_dim, units, T::zero(), rng),
+            reset_gate: Gate::new(input_dim, units, T::zero(), rng),
+            candidate_gate: Gate::new(input_dim, units, T::zero(), rng),
+            units,
+        }
+    }
+}
+
+impl<T: Numeric> RecurrentCell<T> for GRUCell<T> {
+    fn step(&self, input: &Tensor<T>, states: Option<&[Tensor<T>]>) -> Vec<Tensor<T>> {
+        let h = states.map(|states| &states[0]);
+        let z = sigmoid(&self.update_gate.pre_activation(input, h));
+        let output = match h {
+            Some(h) => {
+                let r = sigmoid(&self.reset_gate.pre_activation(input, Some(h)));
+                let n = tanh(&self.candidate_gate.pre_activation(input, Some(&(&r * h))));
+                &n + &z * &(h - &n)
+            }
+            None => {
+                let n = tanh(&self.candidate_gate.pre_activation(input, None));
+                &n - &(&z * &n)
+            }
+        };
+        vec![output]
+    }
+
+    fn state_count(&self) -> usize {
+        1
+    }
+
+    fn units(&self) -> usize {
+        self.units
+    }
+
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        let mut parameters = self.update_gate.named_parameters("_z");
+        parameters.extend(self.reset_gate.named_parameters("_r"));
+        parameters.extend(self.candidate_gate.named_parameters("_n"));
+        parameters
+    }
+
+    fn name(&self) -> &str {
+        "GRU"
+    }
+}
+
+/// Gated recurrent unit layer, see `GRUCell` and `Recurrent`.
+#[allow(clippy::upper_case_acronyms)]
+pub type GRU<T> = Recurrent<GRUCell<T>>;
+
+impl<T: Numeric> GRU<T> {
+    /// Creates a new `GRU` layer.
+    ///
+    /// * `input_dim` - Size of the last dimension of inputs.
+    /// * `units` - Size of the state.
+    /// * `return_sequences` - If true, outputs of all time steps are returned,
+    ///   otherwise only the output of the last time step is returned.
+    /// * `rng` - Random number generator used by initializers.
+    ///
+    /// **Panics** if `input_dim` or `units` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Rng;
+    /// use neurust::nn::layers::GRU;
+    /// use neurust::nn::Module;
+    /// use neurust::prelude::*;
+    ///
+    /// let gru = GRU::new(3, 4, false, &mut Rng::new(0));
+    /// let input = Tensor::new_variable(Array::new(1., vec![2, 5, 3]));
+    ///
+    /// assert_eq!(gru.forward(&input).shape(), vec![2, 4]);
+    /// assert_eq!(gru.parameters().len(), 9);
+    /// ```
+    pub fn new(input_dim: usize, units: usize, return_sequences: bool, rng: &mut Rng) -> GRU<T> {
+        Recurrent::from_cell(GRUCell::new(input_dim, units, rng), return_sequences)
+    }
+}
@@ -0,0 +1,88 @@
+use crate::linalg::Numeric;
+use crate::nn::initializers::{ones, zeros};
+use crate::nn::Module;
+use crate::tensor::normalization::layer_norm;
+use crate::Tensor;
+
+/// Layer normalization layer.
+///
+/// Normalizes every input over its trailing dimensions, then scales it by `gamma`
+/// and shifts by `beta`. Behaves identically during training and inference.
+///
+/// * `gamma` - Trainable scale of shape `normalized_shape`.
+/// * `beta` - Trainable shift of shape `normalized_shape`.
+/// * `epsilon` - Small value added to variance for numerical stability.
+pub struct LayerNorm<T: Numeric> {
+    gamma: Tensor<T>,
+    beta: Tensor<T>,
+    epsilon: T,
+}
+
+impl<T: Numeric> LayerNorm<T> {
+    /// Creates a new `LayerNorm` layer.
+    ///
+    /// `gamma` is initialized with ones, `beta` with zeros.
+    ///
+    /// * `normalized_shape` - Shape of trailing input dimensions to normalize over,
+    ///   e.g. `vec![features]` for `[batch, features]` inputs.
+    /// * `epsilon` - Small value added to variance for numerical stability, e.g. `1e-5`.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::nn::layers::LayerNorm;
+    /// use neurust::nn::Module;
+    /// use neurust::prelude::*;
+    ///
+    /// let layer_norm = LayerNorm::new(vec![2], 0.);
+    /// let input = Tensor::new_variable(Array::from_vec(vec![1., 3., 2., 6.], vec![2, 2]));
+    ///
+    /// assert_eq!(
+    ///     layer_norm.forward(&input).eval(None),
+    ///     Array::from_vec(vec![-1., 1., -1., 1.], vec![2, 2])
+    /// );
+    /// ```
+    pub fn new(normalized_shape: Vec<usize>, epsilon: T) -> LayerNorm<T> {
+        LayerNorm {
+            gamma: Tensor::new_variable(ones(normalized_shape.clone())),
+            beta: Tensor::new_variable(zeros(normalized_shape)),
+            epsilon,
+        }
+    }
+
+    /// Returns scale variable.
+    pub fn gamma(&self) -> &Tensor<T> {
+        &self.gamma
+    }
+
+    /// Returns shift variable.
+    pub fn beta(&self) -> &Tensor<T> {
+        &self.beta
+    }
+}
+
+impl<T: Numeric> Module<T> for LayerNorm<T> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        let rank = input.shape().len();
+        let normalized_rank = self.gamma.shape().len();
+        if normalized_rank > rank {
+            panic!(
+                "Input of shape {:?} can't be normalized over {} trailing dimensions.",
+                input.shape(),
+                normalized_rank
+            )
+        }
+        let axes: Vec<usize> = (rank - normalized_rank..rank).collect();
+        layer_norm(input, &self.gamma, &self.beta, &axes, self.epsilon)
+    }
+
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        vec![
+            ("gamma".to_owned(), &self.gamma),
+            ("beta".to_owned(), &self.beta),
+        ]
+    }
+
+    fn name(&self) -> &str {
+        "LayerNorm"
+    }
+}
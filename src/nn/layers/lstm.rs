@@ -0,0 +1,120 @@
+use crate::linalg::{Numeric, Rng};
+use crate::nn::layers::recurrent::{Gate, Recurrent, RecurrentCell};
+use crate::tensor::math::{sigmoid, tanh};
+use crate::Tensor;
+
+/// Long short-term memory cell.
+///
+/// States are the hidden state `h` (the output) and the cell state `c`:
+/// ```text
+/// i = sigmoid(x x W_i + h x U_i + b_i)
+/// f = sigmoid(x x W_f + h x U_f + b_f)
+/// g = tanh(x x W_g + h x U_g + b_g)
+/// o = sigmoid(x x W_o + h x U_o + b_o)
+/// c' = f * c + i * g
+/// h' = o * tanh(c')
+/// ```
+///
+/// * `input_gate`, `forget_gate`, `cell_gate`, `output_gate` - Variables of the gates,
+///   each with a kernel of shape `[input_dim, units]`, recurrent kernel of shape
+///   `[units, units]` and bias of shape `[units]`.
+pub struct LSTMCell<T: Numeric> {
+    input_gate: Gate<T>,
+    forget_gate: Gate<T>,
+    cell_gate: Gate<T>,
+    output_gate: Gate<T>,
+    units: usize,
+}
+
+impl<T: Numeric> LSTMCell<T> {
+    /// Creates a new `LSTMCell`.
+    ///
+    /// Kernels are initialized with Glorot uniform initializer, recurrent kernels with
+    /// orthogonal matrices and biases with zeros, except for the forget gate bias
+    /// initialized with ones.
+    ///
+    /// * `input_dim` - Size of the last dimension of inputs.
+    /// * `units` - Size of the states.
+    /// * `rng` - Random number generator used by initializers.
+    ///
+    /// **Panics** if `input_dim` or `units` is zero.
+    pub fn new(input_dim: usize, units: usize, rng: &mut Rng) -> LSTMCell<T> {
+        LSTMCell {
+            input_gate: Gate::new(input_dim, units, T::zero(), rng),
+            forget_gate: Gate::new(input_dim, units, T::one(), rng),
+            cell_gate: Gate::new(input_dim, units, T::zero(), rng),
+            output_gate: Gate::new(input_dim, units, T::zero(), rng),
+            units,
+        }
+    }
+}
+
+impl<T: Numeric> RecurrentCell<T> for LSTMCell<T> {
+    fn step(&self, input: &Tensor<T>, states: Option<&[Tensor<T>]>) -> Vec<Tensor<T>> {
+        let h = states.map(|states| &states[0]);
+        let i = sigmoid(&self.input_gate.pre_activation(input, h));
+        let g = tanh(&self.cell_gate.pre_activation(input, h));
+        let o = sigmoid(&self.output_gate.pre_activation(input, h));
+        let c = match states {
+            Some(states) => {
+                let f = sigmoid(&self.forget_gate.pre_activation(input, h));
+                &f * &states[1] + &i * &g
+            }
+            None => &i * &g,
+        };
+        vec![&o * &tanh(&c), c]
+    }
+
+    fn state_count(&self) -> usize {
+        2
+    }
+
+    fn units(&self) -> usize {
+        self.units
+    }
+
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        let mut parameters = self.input_gate.named_parameters("_i");
+        parameters.extend(self.forget_gate.named_parameters("_f"));
+        parameters.extend(self.cell_gate.named_parameters("_g"));
+        parameters.extend(self.output_gate.named_parameters("_o"));
+        parameters
+    }
+
+    fn name(&self) -> &str {
+        "LSTM"
+    }
+}
+
+/// Long short-term memory layer, see `LSTMCell` and `Recurrent`.
+#[allow(clippy::upper_case_acronyms)]
+pub type LSTM<T> = Recurrent<LSTMCell<T>>;
+
+impl<T: Numeric> LSTM<T> {
+    /// Creates a new `LSTM` layer.
+    ///
+    /// * `input_dim` - Size of the last dimension of inputs.
+    /// * `units` - Size of the states.
+    /// * `return_sequences` - If true, outputs of all time steps are returned,
+    ///   otherwise only the output of the last time step is returned.
+    /// * `rng` - Random number generator used by initializers.
+    ///
+    /// **Panics** if `input_dim` or `units` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Rng;
+    /// use neurust::nn::layers::LSTM;
+    /// use neurust::nn::Module;
+    /// use neurust::prelude::*;
+    ///
+    /// let lstm = LSTM::new(3, 4, true, &mut Rng::new(0));
+    /// let input = Tensor::new_variable(Array::new(1., vec![2, 5, 3]));
+    ///
+    /// assert_eq!(lstm.forward(&input).shape(), vec![2, 5, 4]);
+    /// assert_eq!(lstm.parameters().len(), 12);
+    /// ```
+    pub fn new(input_dim: usize, units: usize, return_sequences: bool, rng: &mut Rng) -> LSTM<T> {
+        Recurrent::from_cell(LSTMCell::new(input_dim, units, rng), return_sequences)
+    }
+}
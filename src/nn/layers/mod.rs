@@ -0,0 +1,28 @@
+mod attention;
+mod batch_norm;
+mod dense;
+mod dropout;
+mod group_norm;
+mod gru;
+mod layer_norm;
+mod lstm;
+mod recurrent;
+mod rnn;
+mod transformer;
+
+use crate::Tensor;
+
+pub use attention::MultiHeadAttention;
+pub use batch_norm::BatchNorm;
+pub use dense::Dense;
+pub use dropout::Dropout;
+pub use group_norm::GroupNorm;
+pub use gru::{GRUCell, GRU};
+pub use layer_norm::LayerNorm;
+pub use lstm::{LSTMCell, LSTM};
+pub use recurrent::{Recurrent, RecurrentCell};
+pub use rnn::{RNNCell, RNN};
+pub use transformer::TransformerEncoderLayer;
+
+/// Activation function applied to layer's output, e.g. `neurust::tensor::math::relu`.
+pub type Activation<T> = fn(&Tensor<T>) -> Tensor<T>;
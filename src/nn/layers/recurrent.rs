@@ -0,0 +1,206 @@
+use crate::linalg::{Numeric, Rng};
+use crate::nn::initializers::{zeros, Initializer};
+use crate::nn::Module;
+use crate::tensor::shape::{select, stack};
+use crate::Tensor;
+
+/// Single time step of a recurrent layer.
+///
+/// A cell maps an input of shape `[batch, input_dim]` and states of shape
+/// `[batch, units]` from the previous time step to new states. The first state
+/// is the output of the step.
+pub trait RecurrentCell<T: Numeric> {
+    /// Creates tensors that evaluate to states after processing one time step.
+    ///
+    /// * `input` - Tensor of shape `[batch, input_dim]`.
+    /// * `states` - States of shape `[batch, units]` from the previous time step.
+    ///   `None` means zero initial states.
+    fn step(&self, input: &Tensor<T>, states: Option<&[Tensor<T>]>) -> Vec<Tensor<T>>;
+
+    /// Returns number of states, e.g. `2` for LSTM (hidden and cell states).
+    fn state_count(&self) -> usize;
+
+    /// Returns size of the last dimension of states.
+    fn units(&self) -> usize;
+
+    /// Returns trainable variables of the cell together with their names.
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)>;
+
+    /// Returns name of a layer built of the cell, e.g. `"LSTM"`.
+    fn name(&self) -> &str;
+}
+
+/// Recurrent layer applying a cell to consecutive time steps of a sequence.
+///
+/// Input tensors have shape `[batch, time, input_dim]`. The output has shape
+/// `[batch, time, units]` if full sequences are returned, otherwise it is the output
+/// of the last time step of shape `[batch, units]`. The graph is unrolled over
+/// the time axis, so sequences must have a fixed length.
+///
+/// * `cell` - Cell applied to every time step.
+/// * `return_sequences` - If true, outputs of all time steps are returned.
+pub struct Recurrent<C> {
+    cell: C,
+    return_sequences: bool,
+}
+
+impl<C> Recurrent<C> {
+    /// Creates a new recurrent layer from a cell.
+    ///
+    /// * `cell` - Cell applied to every time step.
+    /// * `return_sequences` - If true, outputs of all time steps are returned,
+    ///   otherwise only the output of the last time step is returned.
+    pub fn from_cell(cell: C, return_sequences: bool) -> Recurrent<C> {
+        Recurrent {
+            cell,
+            return_sequences,
+        }
+    }
+
+    /// Returns the cell of the layer.
+    pub fn cell(&self) -> &C {
+        &self.cell
+    }
+
+    /// Creates a tensor that evaluates to the layer's output for given initial states
+    /// together with tensors that evaluate to the states after the last time step.
+    ///
+    /// Initial states can be supplied via placeholders, e.g. to carry the states over
+    /// between consecutive chunks of a long sequence.
+    ///
+    /// * `input` - Tensor of shape `[batch, time, input_dim]`.
+    /// * `initial_states` - Tensors of shape `[batch, units]`, one for every state
+    ///   of the cell. `None` means zero initial states.
+    ///
+    /// **Panics** if `input` isn't 3-dimensional or `initial_states` have invalid
+    /// number or shapes.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Rng;
+    /// use neurust::nn::layers::LSTM;
+    /// use neurust::prelude::*;
+    /// use std::collections::HashMap;
+    ///
+    /// let lstm = LSTM::new(3, 4, true, &mut Rng::new(0));
+    /// let input = Tensor::new_variable(Array::new(1., vec![2, 5, 3]));
+    /// let initial_states = vec![
+    ///     Tensor::new_placeholder("h".to_owned(), vec![2, 4]),
+    ///     Tensor::new_placeholder("c".to_owned(), vec![2, 4]),
+    /// ];
+    /// let (output, states) = lstm.forward_with_state(&input, Some(&initial_states));
+    ///
+    /// let zeros = Array::new(0., vec![2, 4]);
+    /// let mut feed_dict = HashMap::new();
+    /// feed_dict.insert("h".to_owned(), &zeros);
+    /// feed_dict.insert("c".to_owned(), &zeros);
+    ///
+    /// assert_eq!(output.shape(), vec![2, 5, 4]);
+    /// assert_eq!(states.len(), 2);
+    /// assert_eq!(states[0].eval(Some(&feed_dict)).get_shape(), vec![2, 4]);
+    /// ```
+    pub fn forward_with_state<T: Numeric>(
+        &self,
+        input: &Tensor<T>,
+        initial_states: Option<&[Tensor<T>]>,
+    ) -> (Tensor<T>, Vec<Tensor<T>>)
+    where
+        C: RecurrentCell<T>,
+    {
+        let input_shape = input.shape();
+        if input_shape.len() != 3 {
+            panic!(
+                "Recurrent layers require input of shape [batch, time, features]. Got: {:?}",
+                input_shape
+            )
+        }
+        if let Some(states) = initial_states {
+            let state_shape = vec![input_shape[0], self.cell.units()];
+            if states.len() != self.cell.state_count()
+                || states.iter().any(|state| state.shape() != state_shape)
+            {
+                panic!(
+                    "Expected {} initial states of shape {:?}.",
+                    self.cell.state_count(),
+                    state_shape
+                )
+            }
+        }
+
+        let mut outputs = Vec::with_capacity(input_shape[1]);
+        let mut states: Option<Vec<Tensor<T>>> = None;
+        for t in 0..input_shape[1] {
+            let step_input = select(input, 1, t);
+            let previous_states = states.as_deref().or(initial_states);
+            let new_states = self.cell.step(&step_input, previous_states);
+            outputs.push(new_states[0].clone());
+            states = Some(new_states);
+        }
+        let states = states.unwrap();
+        let output = if self.return_sequences {
+            stack(&outputs.iter().collect::<Vec<&Tensor<T>>>(), 1)
+        } else {
+            states[0].clone()
+        };
+        (output, states)
+    }
+}
+
+impl<T: Numeric, C: RecurrentCell<T>> Module<T> for Recurrent<C> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        self.forward_with_state(input, None).0
+    }
+
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        self.cell.named_parameters()
+    }
+
+    fn name(&self) -> &str {
+        self.cell.name()
+    }
+}
+
+// Affine transformation of an input and a recurrent state computing pre-activation
+// of a single gate: `input x kernel + state x recurrent_kernel + bias`.
+pub(crate) struct Gate<T: Numeric> {
+    pub kernel: Tensor<T>,
+    pub recurrent_kernel: Tensor<T>,
+    pub bias: Tensor<T>,
+}
+
+impl<T: Numeric> Gate<T> {
+    // Creates a gate with Glorot uniform kernel, orthogonal recurrent kernel and
+    // constant bias.
+    pub fn new(input_dim: usize, units: usize, bias: T, rng: &mut Rng) -> Gate<T> {
+        Gate {
+            kernel: Tensor::new_variable(
+                Initializer::GlorotUniform.initialize(vec![input_dim, units], rng),
+            ),
+            recurrent_kernel: Tensor::new_variable(
+                Initializer::Orthogonal(T::one()).initialize(vec![units, units], rng),
+            ),
+            bias: Tensor::new_variable(zeros::<T>(vec![units]).map(|x| x + bias)),
+        }
+    }
+
+    // Creates a tensor with pre-activation of the gate. `None` state is treated as zeros.
+    pub fn pre_activation(&self, input: &Tensor<T>, state: Option<&Tensor<T>>) -> Tensor<T> {
+        let output = input.matmul(&self.kernel) + &self.bias;
+        match state {
+            Some(state) => output + state.matmul(&self.recurrent_kernel),
+            None => output,
+        }
+    }
+
+    // Returns variables of the gate named with a given suffix.
+    pub fn named_parameters(&self, suffix: &str) -> Vec<(String, &Tensor<T>)> {
+        vec![
+            (format!("kernel{}", suffix), &self.kernel),
+            (
+                format!("recurrent_kernel{}", suffix),
+                &self.recurrent_kernel,
+            ),
+            (format!("bias{}", suffix), &self.bias),
+        ]
+    }
+}
@@ -0,0 +1,111 @@
+use crate::linalg::{Numeric, Rng};
+use crate::nn::layers::recurrent::{Gate, Recurrent, RecurrentCell};
+use crate::nn::layers::Activation;
+use crate::Tensor;
+
+/// Fully-connected recurrent cell.
+///
+/// Computes `h' = activation(x x kernel + h x recurrent_kernel + bias)`, where `x`
+/// is a matrix product.
+///
+/// * `gate` - Kernel of shape `[input_dim, units]`, recurrent kernel of shape
+///   `[units, units]` and bias of shape `[units]`.
+/// * `activation` - Activation function.
+pub struct RNNCell<T: Numeric> {
+    gate: Gate<T>,
+    activation: Activation<T>,
+    units: usize,
+}
+
+impl<T: Numeric> RNNCell<T> {
+    /// Creates a new `RNNCell`.
+    ///
+    /// Kernel is initialized with Glorot uniform initializer, recurrent kernel with
+    /// an orthogonal matrix and bias with zeros.
+    ///
+    /// * `input_dim` - Size of the last dimension of inputs.
+    /// * `units` - Size of the state.
+    /// * `activation` - Activation function, e.g. `neurust::tensor::math::tanh`.
+    /// * `rng` - Random number generator used by initializers.
+    ///
+    /// **Panics** if `input_dim` or `units` is zero.
+    pub fn new(
+        input_dim: usize,
+        units: usize,
+        activation: Activation<T>,
+        rng: &mut Rng,
+    ) -> RNNCell<T> {
+        RNNCell {
+            gate: Gate::new(input_dim, units, T::zero(), rng),
+            activation,
+            units,
+        }
+    }
+}
+
+impl<T: Numeric> RecurrentCell<T> for RNNCell<T> {
+    fn step(&self, input: &Tensor<T>, states: Option<&[Tensor<T>]>) -> Vec<Tensor<T>> {
+        let state = states.map(|states| &states[0]);
+        vec![(self.activation)(&self.gate.pre_activation(input, state))]
+    }
+
+    fn state_count(&self) -> usize {
+        1
+    }
+
+    fn units(&self) -> usize {
+        self.units
+    }
+
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        self.gate.named_parameters("")
+    }
+
+    fn name(&self) -> &str {
+        "RNN"
+    }
+}
+
+/// Fully-connected recurrent layer, see `RNNCell` and `Recurrent`.
+#[allow(clippy::upper_case_acronyms)]
+pub type RNN<T> = Recurrent<RNNCell<T>>;
+
+impl<T: Numeric> RNN<T> {
+    /// Creates a new `RNN` layer.
+    ///
+    /// * `input_dim` - Size of the last dimension of inputs.
+    /// * `units` - Size of the state.
+    /// * `activation` - Activation function, e.g. `neurust::tensor::math::tanh`.
+    /// * `return_sequences` - If true, outputs of all time steps are returned,
+    ///   otherwise only the output of the last time step is returned.
+    /// * `rng` - Random number generator used by initializers.
+    ///
+    /// **Panics** if `input_dim` or `units` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Rng;
+    /// use neurust::nn::layers::RNN;
+    /// use neurust::nn::Module;
+    /// use neurust::prelude::*;
+    /// use neurust::tensor::math::tanh;
+    ///
+    /// let rnn = RNN::new(3, 4, tanh, false, &mut Rng::new(0));
+    /// let input = Tensor::new_variable(Array::new(1., vec![2, 5, 3]));
+    ///
+    /// assert_eq!(rnn.forward(&input).shape(), vec![2, 4]);
+    /// assert_eq!(rnn.parameters().len(), 3);
+    /// ```
+    pub fn new(
+        input_dim: usize,
+        units: usize,
+        activation: Activation<T>,
+        return_sequences: bool,
+        rng: &mut Rng,
+    ) -> RNN<T> {
+        Recurrent::from_cell(
+            RNNCell::new(input_dim, units, activation, rng),
+            return_sequences,
+        )
+    }
+}
@@ -0,0 +1,154 @@
+use crate::linalg::{Numeric, Rng};
+use crate::nn::initializers::Initializer;
+use crate::nn::layers::attention::apply_to_sequence;
+use crate::nn::layers::{Dense, Dropout, LayerNorm, MultiHeadAttention};
+use crate::nn::Module;
+use crate::tensor::math::relu;
+use crate::Tensor;
+
+/// Transformer encoder layer.
+///
+/// Applies self-attention and a position-wise feed-forward network, each followed
+/// by dropout, a residual connection and layer normalization:
+/// ```text
+/// x = norm_1(x + dropout(self_attention(x)))
+/// x = norm_2(x + dropout(feed_forward_2(dropout(relu(feed_forward_1(x))))))
+/// ```
+///
+/// * `self_attention` - Multi-head self-attention.
+/// * `feed_forward_1`, `feed_forward_2` - Layers of the feed-forward network.
+/// * `norm_1`, `norm_2` - Layer normalizations over the embedding dimension.
+/// * `dropout` - Dropout applied to outputs of sub-layers.
+pub struct TransformerEncoderLayer<T: Numeric> {
+    self_attention: MultiHeadAttention<T>,
+    feed_forward_1: Dense<T>,
+    feed_forward_2: Dense<T>,
+    norm_1: LayerNorm<T>,
+    norm_2: LayerNorm<T>,
+    dropout: Dropout<T>,
+}
+
+impl<T: Numeric> TransformerEncoderLayer<T> {
+    /// Creates a new `TransformerEncoderLayer`.
+    ///
+    /// * `embed_dim` - Size of the last dimension of inputs and outputs.
+    /// * `num_heads` - Number of attention heads, must divide `embed_dim`.
+    /// * `feed_forward_dim` - Size of the hidden layer of the feed-forward network.
+    /// * `dropout_rate` - Dropout rate, from range `[0, 1)`.
+    /// * `epsilon` - Small value added to variance in layer normalization, e.g. `1e-5`.
+    /// * `rng` - Random number generator used by initializers and dropout.
+    ///
+    /// **Panics** if `embed_dim` isn't divisible by `num_heads` or `dropout_rate`
+    /// is out of range.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Rng;
+    /// use neurust::nn::layers::TransformerEncoderLayer;
+    /// use neurust::nn::Module;
+    /// use neurust::prelude::*;
+    ///
+    /// let encoder = TransformerEncoderLayer::new(8, 2, 16, 0.1, 1e-5, &mut Rng::new(0));
+    /// let input = Tensor::new_variable(Array::new(1., vec![3, 5, 8]));
+    ///
+    /// assert_eq!(encoder.forward(&input).shape(), vec![3, 5, 8]);
+    /// assert_eq!(encoder.parameters().len(), 16);
+    /// ```
+    pub fn new(
+        embed_dim: usize,
+        num_heads: usize,
+        feed_forward_dim: usize,
+        dropout_rate: T,
+        epsilon: T,
+        rng: &mut Rng,
+    ) -> TransformerEncoderLayer<T> {
+        TransformerEncoderLayer {
+            self_attention: MultiHeadAttention::new(embed_dim, num_heads, true, rng),
+            feed_forward_1: Dense::new(
+                embed_dim,
+                feed_forward_dim,
+                Some(relu),
+                Initializer::GlorotUniform,
+                true,
+                rng,
+            ),
+            feed_forward_2: Dense::new(
+                feed_forward_dim,
+                embed_dim,
+                None,
+                Initializer::GlorotUniform,
+                true,
+                rng,
+            ),
+            norm_1: LayerNorm::new(vec![embed_dim], epsilon),
+            norm_2: LayerNorm::new(vec![embed_dim], epsilon),
+            dropout: Dropout::new(dropout_rate, rng),
+        }
+    }
+
+    /// Returns self-attention sub-layer.
+    pub fn self_attention(&self) -> &MultiHeadAttention<T> {
+        &self.self_attention
+    }
+
+    /// Creates a tensor that evaluates to the layer's output with masked self-attention.
+    ///
+    /// * `input` - Tensor of shape `[batch, sequence, embed_dim]`.
+    /// * `padding_mask` - Optional tensor of shape `[batch, sequence]`, zero for padding
+    ///   positions that shouldn't be attended to. It can be a placeholder.
+    /// * `causal` - If true, positions can't attend to later positions.
+    ///
+    /// **Panics** if shapes of tensors are invalid.
+    pub fn forward_with_mask(
+        &self,
+        input: &Tensor<T>,
+        padding_mask: Option<&Tensor<T>>,
+        causal: bool,
+    ) -> Tensor<T> {
+        let attention = self
+            .self_attention
+            .attend(input, input, input, padding_mask, causal);
+        let x = self
+            .norm_1
+            .forward(&(input + &self.dropout.forward(&attention)));
+        let hidden = self
+            .dropout
+            .forward(&apply_to_sequence(&self.feed_forward_1, &x));
+        let feed_forward = apply_to_sequence(&self.feed_forward_2, &hidden);
+        self.norm_2
+            .forward(&(&x + &self.dropout.forward(&feed_forward)))
+    }
+}
+
+impl<T: Numeric> Module<T> for TransformerEncoderLayer<T> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        self.forward_with_mask(input, None, false)
+    }
+
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        let sub_layers: [(&str, &dyn Module<T>); 5] = [
+            ("self_attention", &self.self_attention),
+            ("feed_forward_1", &self.feed_forward_1),
+            ("feed_forward_2", &self.feed_forward_2),
+            ("norm_1", &self.norm_1),
+            ("norm_2", &self.norm_2),
+        ];
+        sub_layers
+            .iter()
+            .flat_map(|&(prefix, layer)| {
+                layer
+                    .named_parameters()
+                    .into_iter()
+                    .map(move |(name, parameter)| (format!("{}.{}", prefix, name), parameter))
+            })
+            .collect()
+    }
+
+    fn name(&self) -> &str {
+        "TransformerEncoderLayer"
+    }
+
+    fn set_training(&self, training: bool) {
+        self.dropout.set_training(training);
+    }
+}
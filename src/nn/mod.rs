@@ -0,0 +1,9 @@
+pub mod clip;
+pub mod initializers;
+pub mod layers;
+mod module;
+pub mod regularizers;
+mod sequential;
+
+pub use module::Module;
+pub use sequential::Sequential;
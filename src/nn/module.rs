@@ -0,0 +1,58 @@
+use crate::linalg::Numeric;
+use crate::Tensor;
+
+/// Building block of neural networks.
+///
+/// A module owns its trainable variables and knows how to extend a computational
+/// graph with its computation. Optimizers update modules through tensors
+/// returned by `parameters()`.
+pub trait Module<T: Numeric> {
+    /// Creates a tensor that evaluates to module's output for a given input tensor.
+    ///
+    /// * `input` - Input tensor.
+    ///
+    /// **Panics** if shape of `input` is not valid for the module.
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>;
+
+    /// Returns trainable variables of the module together with their names.
+    ///
+    /// Names are unique within a module.
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)>;
+
+    /// Returns trainable variables of the module.
+    fn parameters(&self) -> Vec<&Tensor<T>> {
+        self.named_parameters()
+            .into_iter()
+            .map(|(_, parameter)| parameter)
+            .collect()
+    }
+
+    /// Returns non-trainable variables of the module together with their names,
+    /// e.g. running statistics of batch normalization.
+    ///
+    /// Names are unique within a module and differ from names of parameters.
+    fn named_buffers(&self) -> Vec<(String, &Tensor<T>)> {
+        Vec::new()
+    }
+
+    /// Returns all variables of the module, parameters followed by buffers, together
+    /// with their names.
+    ///
+    /// These are the variables that have to be saved to restore the module,
+    /// e.g. with `Checkpoint`.
+    fn named_variables(&self) -> Vec<(String, &Tensor<T>)> {
+        let mut variables = self.named_parameters();
+        variables.extend(self.named_buffers());
+        variables
+    }
+
+    /// Returns name of the module's type, e.g. `"Dense"`.
+    fn name(&self) -> &str;
+
+    /// Switches the module between training and inference mode.
+    ///
+    /// Modules behaving the same way in both modes (e.g. `Dense`) ignore it.
+    ///
+    /// * `training` - If true, training mode is set, otherwise inference mode is set.
+    fn set_training(&self, _training: bool) {}
+}
@@ -0,0 +1,135 @@
+use crate::linalg::Numeric;
+use crate::tensor::math::{abs, pow};
+use crate::{reduce_sum, Tensor};
+
+/// Creates a tensor that evaluates to L1 penalty of given variables.
+///
+/// The penalty is equal to `lambda * sum(|w|)`, where the sum goes over all elements
+/// of all `variables`. Resulting tensor has shape `[1]`.
+///
+/// * `variables` - Tensors to be regularized, usually trainable variables.
+/// * `lambda` - Regularization strength.
+///
+/// **Panics** if `variables` is empty.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::nn::regularizers::l1_penalty;
+///
+/// let w = Tensor::new_variable(Array::from_vec(vec![1., -2.], vec![1, 2]));
+/// let penalty = l1_penalty(&[&w], 0.5);
+///
+/// assert_eq!(penalty.eval(None), Array::new(1.5, vec![1]));
+/// assert_eq!(
+///     penalty.grad(&w, None).unwrap(),
+///     Array::from_vec(vec![0.5, -0.5], vec![1, 2])
+/// );
+/// ```
+pub fn l1_penalty<T: Numeric>(variables: &[&Tensor<T>], lambda: T) -> Tensor<T> {
+    compute_penalty(variables, lambda, |var| reduce_sum(&abs(var), None, false))
+}
+
+/// Creates a tensor that evaluates to L2 penalty (weight decay) of given variables.
+///
+/// The penalty is equal to `lambda * sum(w^2)`, where the sum goes over all elements
+/// of all `variables`. Resulting tensor has shape `[1]`.
+///
+/// * `variables` - Tensors to be regularized, usually trainable variables.
+/// * `lambda` - Regularization strength.
+///
+/// **Panics** if `variables` is empty.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::nn::regularizers::l2_penalty;
+///
+/// let w = Tensor::new_variable(Array::from_vec(vec![1., -2.], vec![1, 2]));
+/// let penalty = l2_penalty(&[&w], 0.5);
+///
+/// assert_eq!(penalty.eval(None), Array::new(2.5, vec![1]));
+/// assert_eq!(
+///     penalty.grad(&w, None).unwrap(),
+///     Array::from_vec(vec![1., -2.], vec![1, 2])
+/// );
+/// ```
+pub fn l2_penalty<T: Numeric>(variables: &[&Tensor<T>], lambda: T) -> Tensor<T> {
+    compute_penalty(variables, lambda, |var| {
+        reduce_sum(&pow(var, T::one() + T::one()), None, false)
+    })
+}
+
+/// Adds L1 penalty of given variables to a loss tensor.
+///
+/// See `l1_penalty` for details.
+///
+/// * `loss` - Loss tensor.
+/// * `variables` - Tensors to be regularized, usually trainable variables.
+/// * `lambda` - Regularization strength.
+///
+/// **Panics** if `variables` is empty or `loss` can't be broadcasted with shape `[1]`.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::nn::regularizers::add_l1_penalty;
+///
+/// let w = Tensor::new_variable(Array::from_vec(vec![1., -2.], vec![1, 2]));
+/// let loss = Tensor::new_variable(Array::new(3., vec![1]));
+/// let regularized_loss = add_l1_penalty(&loss, &[&w], 0.5);
+///
+/// assert_eq!(regularized_loss.eval(None), Array::new(4.5, vec![1]));
+/// ```
+pub fn add_l1_penalty<T: Numeric>(
+    loss: &Tensor<T>,
+    variables: &[&Tensor<T>],
+    lambda: T,
+) -> Tensor<T> {
+    loss + l1_penalty(variables, lambda)
+}
+
+/// Adds L2 penalty (weight decay) of given variables to a loss tensor.
+///
+/// See `l2_penalty` for details.
+///
+/// * `loss` - Loss tensor.
+/// * `variables` - Tensors to be regularized, usually trainable variables.
+/// * `lambda` - Regularization strength.
+///
+/// **Panics** if `variables` is empty or `loss` can't be broadcasted with shape `[1]`.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::nn::regularizers::add_l2_penalty;
+///
+/// let w = Tensor::new_variable(Array::from_vec(vec![1., -2.], vec![1, 2]));
+/// let loss = Tensor::new_variable(Array::new(3., vec![1]));
+/// let regularized_loss = add_l2_penalty(&loss, &[&w], 0.5);
+///
+/// assert_eq!(regularized_loss.eval(None), Array::new(5.5, vec![1]));
+/// ```
+pub fn add_l2_penalty<T: Numeric>(
+    loss: &Tensor<T>,
+    variables: &[&Tensor<T>],
+    lambda: T,
+) -> Tensor<T> {
+    loss + l2_penalty(variables, lambda)
+}
+
+// Sums penalties of all variables and scales the result by `lambda`.
+fn compute_penalty<T: Numeric>(
+    variables: &[&Tensor<T>],
+    lambda: T,
+    penalty_fn: fn(&Tensor<T>) -> Tensor<T>,
+) -> Tensor<T> {
+    let (first, rest) = variables
+        .split_first()
+        .expect("At least one variable is required to compute a penalty.");
+    let mut penalty = penalty_fn(first);
+    for variable in rest {
+        penalty = penalty + penalty_fn(variable);
+    }
+    penalty * lambda
+}
@@ -0,0 +1,200 @@
+use crate::linalg::Numeric;
+use crate::nn::Module;
+use crate::{Array, Tensor};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+const INPUT_PLACEHOLDER_ID: &str = "sequential_input";
+
+/// Linear stack of modules.
+///
+/// Output of each module is an input of the next one.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::Rng;
+/// use neurust::nn::initializers::Initializer;
+/// use neurust::nn::layers::Dense;
+/// use neurust::nn::{Module, Sequential};
+/// use neurust::prelude::*;
+/// use neurust::tensor::math::relu;
+///
+/// let mut rng = Rng::new(0);
+/// let mut model = Sequential::new();
+/// model.add(Dense::new(4, 8, Some(relu), Initializer::HeUniform, true, &mut rng));
+/// model.add(Dense::new(8, 1, None, Initializer::GlorotUniform, true, &mut rng));
+///
+/// let prediction = model.predict(&Array::new(1., vec![2, 4]));
+///
+/// assert_eq!(prediction.get_shape(), vec![2, 1]);
+/// assert_eq!(model.parameters().len(), 4);
+/// ```
+pub struct Sequential<T: Numeric> {
+    layers: Vec<Box<dyn Module<T>>>,
+}
+
+impl<T: Numeric> Sequential<T> {
+    /// Creates a new `Sequential` model without any layers.
+    pub fn new() -> Sequential<T> {
+        Sequential { layers: Vec::new() }
+    }
+
+    /// Appends a module at the end of the stack.
+    ///
+    /// * `layer` - Module to be added.
+    pub fn add(&mut self, layer: impl Module<T> + 'static) {
+        self.layers.push(Box::new(layer));
+    }
+
+    /// Returns modules of the model.
+    pub fn layers(&self) -> &[Box<dyn Module<T>>] {
+        &self.layers
+    }
+
+    /// Computes model's output for a given input array.
+    ///
+    /// A placeholder for the input is created internally, so there is no need
+    /// to build a feed dictionary. Modules are computed in their current mode,
+    /// so `set_training(false)` should be called before predicting with models
+    /// containing e.g. batch normalization.
+    ///
+    /// * `input` - Input array.
+    ///
+    /// **Panics** if shape of `input` is not valid for the model.
+    pub fn predict(&self, input: &Array<T>) -> Array<T> {
+        let placeholder =
+            Tensor::new_placeholder(INPUT_PLACEHOLDER_ID.to_owned(), input.get_shape());
+        let mut feed_dict = HashMap::new();
+        feed_dict.insert(INPUT_PLACEHOLDER_ID.to_owned(), input);
+        self.forward(&placeholder).eval(Some(&feed_dict))
+    }
+
+    /// Returns a table with model's layers, their output shapes and numbers of parameters.
+    ///
+    /// * `input_shape` - Shape of model's input, including batch dimension.
+    ///
+    /// **Panics** if `input_shape` is not valid for the model.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::linalg::Rng;
+    /// use neurust::nn::initializers::Initializer;
+    /// use neurust::nn::layers::Dense;
+    /// use neurust::nn::Sequential;
+    ///
+    /// let mut model = Sequential::<f32>::new();
+    /// model.add(Dense::new(4, 8, None, Initializer::Zeros, true, &mut Rng::new(0)));
+    ///
+    /// println!("{}", model.summary(vec![1, 4]));
+    /// // outputs:
+    /// // Layer (type)                  Output Shape             Param #
+    /// // ================================================================
+    /// // dense_0 (Dense)               [1, 8]                   40
+    /// // ================================================================
+    /// // Total params: 40
+    /// ```
+    pub fn summary(&self, input_shape: Vec<usize>) -> String {
+        let separator = "=".repeat(64);
+        let mut summary = String::new();
+        writeln!(
+            summary,
+            "{:<30}{:<25}Param #",
+            "Layer (type)", "Output Shape"
+        )
+        .unwrap();
+        writeln!(summary, "{}", separator).unwrap();
+
+        let mut output = Tensor::new_placeholder(INPUT_PLACEHOLDER_ID.to_owned(), input_shape);
+        let mut total_params = 0;
+        for (i, layer) in self.layers.iter().enumerate() {
+            output = layer.forward(&output);
+            let params = count_parameters(layer.as_ref());
+            total_params += params;
+            writeln!(
+                summary,
+                "{:<30}{:<25}{}",
+                format!("{} ({})", layer_prefix(layer.as_ref(), i), layer.name()),
+                format!("{:?}", output.shape()),
+                params
+            )
+            .unwrap();
+        }
+
+        writeln!(summary, "{}", separator).unwrap();
+        writeln!(summary, "Total params: {}", total_params).unwrap();
+        summary
+    }
+}
+
+impl<T: Numeric> Default for Sequential<T> {
+    fn default() -> Self {
+        Sequential::new()
+    }
+}
+
+impl<T: Numeric> Module<T> for Sequential<T> {
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T> {
+        let (first, rest) = self
+            .layers
+            .split_first()
+            .expect("Sequential model has no layers.");
+        let mut output = first.forward(input);
+        for layer in rest {
+            output = layer.forward(&output);
+        }
+        output
+    }
+
+    /// Returns parameters of all layers.
+    ///
+    /// Names are prefixed with layer's name and position, e.g. `dense_0/kernel`.
+    fn named_parameters(&self) -> Vec<(String, &Tensor<T>)> {
+        let mut parameters = Vec::new();
+        for (i, layer) in self.layers.iter().enumerate() {
+            let prefix = layer_prefix(layer.as_ref(), i);
+            for (name, parameter) in layer.named_parameters() {
+                parameters.push((format!("{}/{}", prefix, name), parameter));
+            }
+        }
+        parameters
+    }
+
+    /// Returns buffers of all layers.
+    ///
+    /// Names are prefixed in the same way as names of parameters,
+    /// e.g. `batchnorm_1/running_mean`.
+    fn named_buffers(&self) -> Vec<(String, &Tensor<T>)> {
+        let mut buffers = Vec::new();
+        for (i, layer) in self.layers.iter().enumerate() {
+            let prefix = layer_prefix(layer.as_ref(), i);
+            for (name, buffer) in layer.named_buffers() {
+                buffers.push((format!("{}/{}", prefix, name), buffer));
+            }
+        }
+        buffers
+    }
+
+    fn name(&self) -> &str {
+        "Sequential"
+    }
+
+    fn set_training(&self, training: bool) {
+        for layer in self.layers.iter() {
+            layer.set_training(training);
+        }
+    }
+}
+
+// Returns unique name of the layer in the model.
+fn layer_prefix<T: Numeric>(layer: &dyn Module<T>, position: usize) -> String {
+    format!("{}_{}", layer.name().to_lowercase(), position)
+}
+
+// Returns total number of elements of module's parameters.
+fn count_parameters<T: Numeric>(layer: &dyn Module<T>) -> usize {
+    layer
+        .parameters()
+        .iter()
+        .map(|parameter| parameter.shape().iter().product::<usize>())
+        .sum()
+}
@@ -0,0 +1,108 @@
+use crate::graph::softmax::SoftmaxOp;
+use crate::linalg::Numeric;
+use crate::tensor::shape::permute;
+use crate::Tensor;
+use std::rc::Rc;
+
+/// Creates a tensor that evaluates to softmax of `tensor` along a given axis.
+///
+/// * `tensor` - Input tensor.
+/// * `axis` - Axis along which probabilities sum to one.
+///
+/// **Panics** if `axis` is out of bounds.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::tensor::attention::softmax;
+///
+/// let a = Tensor::new_variable(Array::from_vec(vec![1., 1., 2., 2.], vec![2, 2]));
+///
+/// assert_eq!(softmax(&a, 1).eval(None), Array::new(0.5, vec![2, 2]));
+/// ```
+pub fn softmax<T: Numeric>(tensor: &Tensor<T>, axis: usize) -> Tensor<T> {
+    Tensor::new(Rc::new(SoftmaxOp::new(Rc::clone(&tensor.op), axis)))
+}
+
+/// Creates a tensor that evaluates to softmax of attention scores along the key axis
+/// with masked keys excluded.
+///
+/// Masked scores get zero probability. If all keys of a query are masked, all its
+/// probabilities are zero.
+///
+/// * `scores` - Tensor of shape `[batch, ..., query, key]`.
+/// * `padding_mask` - Optional tensor of shape `[batch, key]`, zero for padding keys
+///   that shouldn't be attended to and non-zero for valid keys. It can be a placeholder,
+///   so it can change between batches.
+/// * `causal` - If true, queries can't attend to keys at later positions, i.e. `i`-th
+///   query attends only to keys `0..=i`.
+///
+/// **Panics** if `scores` has less than 3 dimensions or `padding_mask` has invalid shape.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::tensor::attention::masked_softmax;
+///
+/// let scores = Tensor::new_variable(Array::new(0., vec![1, 2, 2]));
+///
+/// assert_eq!(
+///     masked_softmax(&scores, None, true).eval(None),
+///     Array::from_vec(vec![1., 0., 0.5, 0.5], vec![1, 2, 2])
+/// );
+/// ```
+pub fn masked_softmax<T: Numeric>(
+    scores: &Tensor<T>,
+    padding_mask: Option<&Tensor<T>>,
+    causal: bool,
+) -> Tensor<T> {
+    Tensor::new(Rc::new(SoftmaxOp::new_attention(
+        Rc::clone(&scores.op),
+        padding_mask.map(|mask| Rc::clone(&mask.op)),
+        causal,
+    )))
+}
+
+/// Creates a tensor that evaluates to scaled dot-product attention:
+/// `softmax(query x key^T / sqrt(depth)) x value`.
+///
+/// Leading dimensions (batch, heads, ...) are treated as batch dimensions of matrix
+/// products.
+///
+/// * `query` - Tensor of shape `[batch, ..., query, depth]`.
+/// * `key` - Tensor of shape `[batch, ..., key, depth]`.
+/// * `value` - Tensor of shape `[batch, ..., key, value_depth]`.
+/// * `padding_mask` - Optional tensor of shape `[batch, key]`, zero for padding keys,
+///   see `masked_softmax`.
+/// * `causal` - If true, queries can't attend to keys at later positions.
+///
+/// **Panics** if shapes of tensors are invalid.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::tensor::attention::scaled_dot_product_attention;
+///
+/// let query = Tensor::new_variable(Array::new(1., vec![1, 2, 4]));
+/// let key = Tensor::new_variable(Array::new(1., vec![1, 3, 4]));
+/// let value = Tensor::new_variable(Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![1, 3, 2]));
+/// let padding_mask = Tensor::new_variable(Array::from_vec(vec![1., 1., 0.], vec![1, 3]));
+/// let output = scaled_dot_product_attention(&query, &key, &value, Some(&padding_mask), false);
+///
+/// assert_eq!(output.eval(None), Array::from_vec(vec![2., 3., 2., 3.], vec![1, 2, 2]));
+/// ```
+pub fn scaled_dot_product_attention<T: Numeric>(
+    query: &Tensor<T>,
+    key: &Tensor<T>,
+    value: &Tensor<T>,
+    padding_mask: Option<&Tensor<T>>,
+    causal: bool,
+) -> Tensor<T> {
+    let shape = key.shape();
+    let rank = shape.len();
+    let depth = T::from(shape[rank - 1]).unwrap();
+    let mut axes: Vec<usize> = (0..rank).collect();
+    axes.swap(rank - 2, rank - 1);
+    let scores = query.matmul(&permute(key, &axes)) * (T::one() / depth.sqrt());
+    masked_softmax(&scores, padding_mask, causal).matmul(value)
+}
@@ -0,0 +1,99 @@
+use crate::graph::conv::{Conv2dOp, Conv2dTransposeOp};
+use crate::linalg::{Conv2dParams, DataFormat, Numeric};
+use crate::Tensor;
+use std::rc::Rc;
+
+/// Creates a tensor that evaluates to 2D convolution of `input` with `kernel`.
+///
+/// See `linalg::conv2d` for description of the parameters. Gradients are computed
+/// w.r.t. both `input` and `kernel`.
+///
+/// **Panics** if shapes of `input` and `kernel` are incompatible, `stride`, `dilation` or
+/// `groups` contain zero or kernel doesn't fit into the padded input.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::linalg::DataFormat;
+/// use neurust::tensor::conv::conv2d;
+///
+/// let input = Tensor::new_variable(Array::new(1., vec![1, 1, 3, 3]));
+/// let kernel = Tensor::new_variable(Array::new(1., vec![1, 1, 2, 2]));
+/// let output = conv2d(&input, &kernel, (1, 1), (0, 0), (1, 1), 1, DataFormat::Nchw);
+///
+/// assert_eq!(output.eval(None), Array::new(4., vec![1, 1, 2, 2]));
+/// assert_eq!(
+///     output.grad(&input, None).unwrap(),
+///     Array::from_vec(vec![1., 2., 1., 2., 4., 2., 1., 2., 1.], vec![1, 1, 3, 3])
+/// );
+/// ```
+pub fn conv2d<T: Numeric>(
+    input: &Tensor<T>,
+    kernel: &Tensor<T>,
+    stride: (usize, usize),
+    padding: (usize, usize),
+    dilation: (usize, usize),
+    groups: usize,
+    data_format: DataFormat,
+) -> Tensor<T> {
+    Tensor::new(Rc::new(Conv2dOp::new(
+        Rc::clone(&input.op),
+        Rc::clone(&kernel.op),
+        Conv2dParams {
+            stride,
+            padding,
+            dilation,
+            groups,
+        },
+        data_format,
+    )))
+}
+
+/// Creates a tensor that evaluates to transposed 2D convolution of `input` with `kernel`.
+///
+/// See `linalg::conv2d_transpose` for description of the parameters. Gradients are computed
+/// w.r.t. both `input` and `kernel`.
+///
+/// **Panics** if shapes of `input` and `kernel` are incompatible, `stride`, `dilation` or
+/// `groups` contain zero, `output_padding` isn't smaller than `stride` or the output
+/// would be empty.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::linalg::DataFormat;
+/// use neurust::tensor::conv::conv2d_transpose;
+///
+/// let input = Tensor::new_variable(Array::new(1., vec![1, 2, 2, 1]));
+/// let kernel = Tensor::new_variable(Array::new(1., vec![1, 3, 2, 2]));
+/// let output = conv2d_transpose(
+///     &input, &kernel, (2, 2), (0, 0), (0, 0), (1, 1), 1, DataFormat::Nhwc
+/// );
+///
+/// assert_eq!(output.shape(), vec![1, 4, 4, 3]);
+/// assert_eq!(output.eval(None), Array::new(1., vec![1, 4, 4, 3]));
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn conv2d_transpose<T: Numeric>(
+    input: &Tensor<T>,
+    kernel: &Tensor<T>,
+    stride: (usize, usize),
+    padding: (usize, usize),
+    output_padding: (usize, usize),
+    dilation: (usize, usize),
+    groups: usize,
+    data_format: DataFormat,
+) -> Tensor<T> {
+    Tensor::new(Rc::new(Conv2dTransposeOp::new(
+        Rc::clone(&input.op),
+        Rc::clone(&kernel.op),
+        Conv2dParams {
+            stride,
+            padding,
+            dilation,
+            groups,
+        },
+        output_padding,
+        data_format,
+    )))
+}
@@ -0,0 +1,60 @@
+use crate::graph::dropout::DropoutMaskOp;
+use crate::linalg::{Numeric, Rng};
+use crate::tensor::TrainingFlag;
+use crate::Tensor;
+use std::rc::Rc;
+
+/// Creates a tensor that evaluates to `tensor` with randomly dropped elements.
+///
+/// In training mode every computation of the graph zeroes elements of `tensor`
+/// with probability `rate` and scales the remaining ones by `1 / (1 - rate)`,
+/// so the expected value is unchanged. In inference mode the tensor is passed
+/// through unchanged.
+///
+/// A mask is sampled once per computation of the graph. Use `Tensor::eval_and_grads()`
+/// to get the output and gradients computed with the same mask, as separate `eval()`
+/// and `grad()` calls sample separate masks.
+///
+/// * `tensor` - Input tensor.
+/// * `rate` - Probability of dropping an element, from range `[0, 1)`.
+/// * `training` - Flag switching between training and inference mode.
+/// * `rng` - Random number generator seeding the sequence of masks. The same state
+///   results in the same sequence of masks.
+///
+/// **Panics** if `rate` is out of range.
+///
+/// # Examples
+/// ```
+/// use neurust::linalg::Rng;
+/// use neurust::prelude::*;
+/// use neurust::tensor::dropout::dropout;
+/// use neurust::tensor::TrainingFlag;
+///
+/// let input = Tensor::new_variable(Array::new(1., vec![2, 3]));
+/// let training = TrainingFlag::new(true);
+/// let output = dropout(&input, 0.5, &training, &mut Rng::new(0));
+///
+/// let (value, grads) = output.eval_and_grads(&[&input], None);
+///
+/// // every element is either dropped or scaled by 2
+/// assert_eq!(value.map(|x| x * (x - 2.)), Array::new(0., vec![2, 3]));
+/// assert_eq!(grads[0], Some(value));
+///
+/// training.set(false);
+///
+/// assert_eq!(output.eval(None), Array::new(1., vec![2, 3]));
+/// ```
+pub fn dropout<T: Numeric>(
+    tensor: &Tensor<T>,
+    rate: T,
+    training: &TrainingFlag,
+    rng: &mut Rng,
+) -> Tensor<T> {
+    let mask = Tensor::new(Rc::new(DropoutMaskOp::new(
+        tensor.shape(),
+        rate,
+        training.clone(),
+        Rng::new(rng.next_u64()),
+    )));
+    tensor * &mask
+}
@@ -0,0 +1,46 @@
+use crate::graph::embedding::EmbeddingOp;
+use crate::linalg::Numeric;
+use crate::tensor::Indices;
+use crate::Tensor;
+use std::rc::Rc;
+
+/// Creates a tensor that evaluates to rows of `table` given by `indices`.
+///
+/// The output has shape `[..., dim]`, where `...` is the shape of `indices`. Indices
+/// are read every time the tensor is computed, so they can be changed with
+/// `Indices::assign()` between computations.
+///
+/// Gradient of the lookup with respect to `table` is non-zero only in the gathered rows.
+/// Use `Tensor::sparse_grad()` to get it as `IndexedSlices` and
+/// `Tensor::assign_add_sparse()` to apply it without building a dense array.
+///
+/// * `table` - Tensor of shape `[vocab, dim]`, usually a variable.
+/// * `indices` - Row indices, each smaller than `vocab`.
+///
+/// **Panics** if `table` isn't 2-dimensional. Computation of the tensor panics
+/// if any index is out of bounds.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::tensor::embedding::embedding;
+/// use neurust::tensor::Indices;
+///
+/// let table = Tensor::new_variable(
+///     Array::from_vec(vec![0., 1., 10., 11., 20., 21.], vec![3, 2])
+/// );
+/// let indices = Indices::new(vec![2, 0], vec![2]);
+/// let output = embedding(&table, &indices);
+///
+/// assert_eq!(output.eval(None), Array::from_vec(vec![20., 21., 0., 1.], vec![2, 2]));
+///
+/// indices.assign(vec![1, 1]);
+///
+/// assert_eq!(output.eval(None), Array::from_vec(vec![10., 11., 10., 11.], vec![2, 2]));
+/// ```
+pub fn embedding<T: Numeric>(table: &Tensor<T>, indices: &Indices) -> Tensor<T> {
+    Tensor::new(Rc::new(EmbeddingOp::new(
+        Rc::clone(&table.op),
+        indices.clone(),
+    )))
+}
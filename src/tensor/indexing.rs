@@ -0,0 +1,79 @@
+use crate::graph::indexing::{GatherOp, IndexSelectOp};
+use crate::linalg::Numeric;
+use crate::tensor::Indices;
+use crate::Tensor;
+use std::rc::Rc;
+
+/// Creates a tensor that evaluates to values of `tensor` gathered along an axis.
+///
+/// See `Array::gather()` for details. Gradient with respect to `tensor` is computed
+/// with a scatter-add of the incoming gradient. Indices are read every time
+/// the tensor is computed, so they can be changed with `Indices::assign()`.
+///
+/// * `tensor` - Input tensor.
+/// * `axis` - Axis to gather values along.
+/// * `indices` - Indices with the shape of the output. The shape must have the same
+///   length as the shape of `tensor` and must not exceed it outside of `axis`.
+///
+/// **Panics** if `axis` is out of bounds, the shape of `indices` is invalid or any
+/// index is out of bounds.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::tensor::indexing::gather;
+/// use neurust::tensor::Indices;
+///
+/// // picks a score of the target class of every sample
+/// let scores = Tensor::new_variable(Array::from_vec(vec![0.1, 0.7, 0.2, 0.6, 0.3, 0.1], vec![2, 3]));
+/// let labels = Indices::new(vec![1, 0], vec![2, 1]);
+/// let picked = gather(&scores, 1, &labels);
+///
+/// assert_eq!(picked.eval(None), Array::from_vec(vec![0.7, 0.6], vec![2, 1]));
+/// assert_eq!(
+///     picked.grad(&scores, None).unwrap(),
+///     Array::from_vec(vec![0., 1., 0., 1., 0., 0.], vec![2, 3])
+/// );
+/// ```
+pub fn gather<T: Numeric>(tensor: &Tensor<T>, axis: usize, indices: &Indices) -> Tensor<T> {
+    Tensor::new(Rc::new(GatherOp::new(
+        Rc::clone(&tensor.op),
+        axis,
+        indices.clone(),
+    )))
+}
+
+/// Creates a tensor that evaluates to sub-arrays of `tensor` selected along an axis.
+///
+/// See `Array::index_select()` for details. Indices are read every time the tensor
+/// is computed, so they can be changed with `Indices::assign()`.
+///
+/// * `tensor` - Input tensor.
+/// * `axis` - Axis to select along.
+/// * `indices` - 1-dimensional indices, each smaller than the size of `axis`.
+///
+/// **Panics** if `axis` is out of bounds, `indices` aren't 1-dimensional or any index
+/// is out of bounds.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::tensor::indexing::index_select;
+/// use neurust::tensor::Indices;
+///
+/// let a = Tensor::new_variable(Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![3, 2]));
+/// let selected = index_select(&a, 0, &Indices::new(vec![2, 2], vec![2]));
+///
+/// assert_eq!(selected.eval(None), Array::from_vec(vec![5., 6., 5., 6.], vec![2, 2]));
+/// assert_eq!(
+///     selected.grad(&a, None).unwrap(),
+///     Array::from_vec(vec![0., 0., 0., 0., 2., 2.], vec![3, 2])
+/// );
+/// ```
+pub fn index_select<T: Numeric>(tensor: &Tensor<T>, axis: usize, indices: &Indices) -> Tensor<T> {
+    Tensor::new(Rc::new(IndexSelectOp::new(
+        Rc::clone(&tensor.op),
+        axis,
+        indices.clone(),
+    )))
+}
@@ -0,0 +1,68 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Shared array of integer indices used as an input of a computational graph.
+///
+/// Tensors hold only floating point values, so operators indexing other tensors
+/// (e.g. embedding lookups) read their indices from an `Indices` object instead.
+/// The shape is fixed, but the values can be replaced with `assign()` between
+/// computations without rebuilding the graph. Clones of an object share its values.
+///
+/// # Examples
+/// ```
+/// use neurust::tensor::Indices;
+///
+/// let indices = Indices::new(vec![0, 1, 2, 3], vec![2, 2]);
+/// let shared = indices.clone();
+///
+/// shared.assign(vec![3, 2, 1, 0]);
+///
+/// assert_eq!(indices.get_data(), vec![3, 2, 1, 0]);
+/// assert_eq!(indices.shape(), vec![2, 2]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Indices {
+    data: Rc<RefCell<Vec<usize>>>,
+    shape: Vec<usize>,
+}
+
+impl Indices {
+    /// Creates a new `Indices` object.
+    ///
+    /// * `data` - Indices in row-major order.
+    /// * `shape` - Non-zero shape of the indices.
+    ///
+    /// **Panics** if `shape` is empty, contains zero or doesn't match the length of `data`.
+    pub fn new(data: Vec<usize>, shape: Vec<usize>) -> Indices {
+        if shape.is_empty() || shape.contains(&0) || shape.iter().product::<usize>() != data.len() {
+            panic!("Shape {:?} is invalid for {} indices.", shape, data.len())
+        }
+        Indices {
+            data: Rc::new(RefCell::new(data)),
+            shape,
+        }
+    }
+
+    /// Replaces the indices with new values.
+    ///
+    /// * `data` - New indices in row-major order.
+    ///
+    /// **Panics** if the length of `data` differs from the current one.
+    pub fn assign(&self, data: Vec<usize>) {
+        let mut current = self.data.borrow_mut();
+        if current.len() != data.len() {
+            panic!("Expected {} indices, got: {}", current.len(), data.len())
+        }
+        *current = data;
+    }
+
+    /// Returns a copy of the indices.
+    pub fn get_data(&self) -> Vec<usize> {
+        self.data.borrow().clone()
+    }
+
+    /// Returns the shape of the indices.
+    pub fn shape(&self) -> Vec<usize> {
+        self.shape.clone()
+    }
+}
@@ -1,4 +1,4 @@
-use crate::graph::math::{CosOp, LnOp, LogOp, PowOp, ReLUOp, SigmoidOp, SinOp, TanhOp};
+use crate::graph::math::{AbsOp, CosOp, LnOp, LogOp, PowOp, ReLUOp, SigmoidOp, SinOp, TanhOp};
 use crate::linalg::Numeric;
 use crate::Tensor;
 use std::rc::Rc;
@@ -34,3 +34,7 @@ pub fn tanh<T: Numeric>(tensor: &Tensor<T>) -> Tensor<T> {
 pub fn relu<T: Numeric>(tensor: &Tensor<T>) -> Tensor<T> {
     Tensor::new(Rc::new(ReLUOp::new(Rc::clone(&tensor.op))))
 }
+
+pub fn abs<T: Numeric>(tensor: &Tensor<T>) -> Tensor<T> {
+    Tensor::new(Rc::new(AbsOp::new(Rc::clone(&tensor.op))))
+}
@@ -1,10 +1,26 @@
 mod arithmetic;
+pub mod attention;
+pub mod conv;
+pub mod dropout;
+pub mod embedding;
+pub mod indexing;
+mod indices;
 pub mod math;
+pub mod normalization;
+pub mod pad;
+pub mod pool;
 mod reduce;
+pub mod shape;
+mod sparse;
+mod training;
+pub mod upsample;
 
 use crate::graph::{GraphOp, Placeholder, Variable};
-use crate::linalg::{Array, Numeric};
+use crate::linalg::{scatter_add_rows, Array, Numeric};
+pub use indices::Indices;
 pub use reduce::{reduce_mean, reduce_sum};
+pub use sparse::IndexedSlices;
+pub use training::TrainingFlag;
 
 use crate::graph::arithmetic::MatMulOp;
 use std::cell::RefCell;
@@ -24,6 +40,10 @@ use std::rc::Rc;
 /// * `op` - Shared reference to a computational graph node.
 /// * `variable_data` - Shared reference to stored variable operator's data.
 /// This is `None` for tensors with `op` field other than `Variable`.
+///
+/// Cloning a tensor creates another handle to the same graph node (and the same
+/// variable data), not a copy of the node.
+#[derive(Clone)]
 pub struct Tensor<T: Numeric> {
     op: Rc<(dyn GraphOp<T>)>,
     variable_data: Option<Rc<RefCell<Array<T>>>>,
@@ -167,6 +187,103 @@ impl<T: Numeric> Tensor<T> {
         self.op.grad(y.op.as_ref(), feed_dict)
     }
 
+    /// Evaluates a tensor and computes its gradients with respect to `ys` tensors
+    /// in a single computation of the graph.
+    ///
+    /// Unlike separate calls to `eval()` and `grad()`, every node is computed once,
+    /// so stochastic operators (e.g. dropout masks) contribute the same values to
+    /// the result and all the gradients. It is also the only method which updates state
+    /// of operators, e.g. running statistics of batch normalization in training mode,
+    /// so it should be called once per training step. Gradients are returned in the order
+    /// of `ys`, `None` for tensors not connected to the current tensor.
+    ///
+    /// * `ys` - Tensors to differentiate with respect to.
+    /// * `feed_dict` - Dictionary with values for *placeholder* tensors current tensor
+    ///   is dependant of.
+    ///
+    /// **Panics** if `feed_dict` does not contain required data or if shapes
+    /// of tensors in a graph are invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::prelude::*;
+    ///
+    /// let a = Tensor::new_variable(Array::from_vec(vec![1., 2.], vec![2, 1]));
+    /// let b = Tensor::new_variable(Array::from_vec(vec![3., 4.], vec![2, 1]));
+    /// let mul = &a * &b;
+    ///
+    /// let (value, grads) = mul.eval_and_grads(&[&a, &b], None);
+    ///
+    /// assert_eq!(value, Array::from_vec(vec![3., 8.], vec![2, 1]));
+    /// assert_eq!(grads[0], Some(Array::from_vec(vec![3., 4.], vec![2, 1])));
+    /// assert_eq!(grads[1], Some(Array::from_vec(vec![1., 2.], vec![2, 1])));
+    /// ```
+    pub fn eval_and_grads(
+        &self,
+        ys: &[&Tensor<T>],
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+    ) -> (Array<T>, Vec<Option<Array<T>>>) {
+        let mut compute_cache = HashMap::<usize, Array<T>>::new();
+        let value = self.op.value(feed_dict, &mut compute_cache);
+        let grads = ys
+            .iter()
+            .map(|y| {
+                self.op
+                    .grad_with_cache(y.op.as_ref(), feed_dict, &mut compute_cache)
+            })
+            .collect();
+        self.op.update_graph_state();
+        (value, grads)
+    }
+
+    /// Computes gradient of a tensor with respect to the table of an embedding lookup
+    /// as `IndexedSlices`.
+    ///
+    /// Only rows gathered by `lookup` are included, so the gradient is never
+    /// materialized as a dense array. Gradients flowing to the table through other
+    /// tensors are not included. If `lookup` is not connected to a tensor in any way,
+    /// then `None` is returned.
+    ///
+    /// * `lookup` - Tensor created by `embedding()`.
+    /// * `feed_dict` - Dictionary with values for *placeholder* tensors current tensor
+    ///   is dependant of.
+    ///
+    /// **Panics** if `lookup` is not an embedding lookup, `feed_dict` does not contain
+    /// required data or if shapes of tensors in a graph are invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::prelude::*;
+    /// use neurust::tensor::embedding::embedding;
+    /// use neurust::tensor::Indices;
+    ///
+    /// let table = Tensor::new_variable(Array::new(1., vec![1000, 2]));
+    /// let lookup = embedding(&table, &Indices::new(vec![7, 3, 7], vec![3]));
+    /// let loss = &lookup * 2.;
+    ///
+    /// let grad = loss.sparse_grad(&lookup, None).unwrap();
+    ///
+    /// assert_eq!(grad.indices(), &[7, 3, 7]);
+    /// assert_eq!(grad.values(), &Array::new(2., vec![3, 2]));
+    /// assert_eq!(grad.dense_shape(), vec![1000, 2]);
+    /// ```
+    pub fn sparse_grad(
+        &self,
+        lookup: &Tensor<T>,
+        feed_dict: Option<&HashMap<String, &Array<T>>>,
+    ) -> Option<IndexedSlices<T>> {
+        let rows = lookup
+            .op
+            .sparse_rows()
+            .expect("Sparse gradients can be computed only w.r.t. embedding lookups.");
+        let table_shape = lookup.op.get_inputs().unwrap_or_default()[0].shape();
+        self.grad(lookup, feed_dict).map(|grad| {
+            let mut values_shape = vec![rows.len()];
+            values_shape.extend_from_slice(&table_shape[1..]);
+            IndexedSlices::new(rows, grad.reshape(values_shape), table_shape)
+        })
+    }
+
     /// Creates a tensor that evaluates to matrix product of two tensors.
     ///
     /// Tensors can be multiplied only if:
@@ -256,4 +373,44 @@ impl<T: Numeric> Tensor<T> {
             .expect("New data cannot be added to non-variable tensors.")
             .borrow_mut() += value;
     }
+
+    /// Updates stored variable's data by adding sparse rows to it.
+    ///
+    /// Only rows given by `slices` are updated, which makes applying gradients of
+    /// embedding lookups cheap for large tables.
+    ///
+    /// * `slices` - Rows to be added. Repeated rows are added multiple times.
+    ///
+    /// **Panics** if stored graph operator is not of `Variable` type or shape
+    /// of the variable differs from the dense shape of `slices`.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::prelude::*;
+    /// use neurust::tensor::IndexedSlices;
+    ///
+    /// let arr = Tensor::new_variable(Array::new(0., vec![3, 2]));
+    ///
+    /// arr.assign_add_sparse(&IndexedSlices::new(vec![1], Array::new(1., vec![1, 2]), vec![3, 2]));
+    ///
+    /// assert_eq!(
+    ///     arr.eval(None),
+    ///     Array::from_vec(vec![0., 0., 1., 1., 0., 0.], vec![3, 2])
+    /// )
+    /// ```
+    pub fn assign_add_sparse(&self, slices: &IndexedSlices<T>) {
+        let mut data = self
+            .variable_data
+            .as_ref()
+            .expect("New data cannot be added to non-variable tensors.")
+            .borrow_mut();
+        if data.get_shape() != slices.dense_shape() {
+            panic!(
+                "Variable of shape {:?} can't be updated with slices of dense shape {:?}.",
+                data.get_shape(),
+                slices.dense_shape()
+            )
+        }
+        scatter_add_rows(&mut data, slices.indices(), slices.values());
+    }
 }
@@ -0,0 +1,184 @@
+use crate::graph::normalization::{BatchNormOp, NormalizationOp};
+use crate::linalg::Numeric;
+use crate::tensor::TrainingFlag;
+use crate::Tensor;
+use std::rc::Rc;
+
+/// Creates a tensor that evaluates to batch normalization of `input`.
+///
+/// Every channel (slice along `axis`) is normalized to zero mean and unit variance,
+/// then scaled by `gamma` and shifted by `beta`.
+///
+/// In training mode statistics of the current batch are used and every call to
+/// `Tensor::eval_and_grads()` updates running statistics once:
+/// `running = momentum * running + (1 - momentum) * batch_statistic`.
+/// `eval()` and `grad()` leave them unchanged. In inference mode the running statistics
+/// are used instead.
+///
+/// * `input` - Tensor to be normalized.
+/// * `gamma` - Scale of shape `[channels]`.
+/// * `beta` - Shift of shape `[channels]`.
+/// * `running_mean` - Variable of shape `[channels]` with running mean.
+/// * `running_variance` - Variable of shape `[channels]` with running (biased) variance.
+/// * `training` - Flag switching between training and inference mode.
+/// * `axis` - Channel axis, e.g. `1` for `NCHW` images and `[batch, features]` inputs.
+/// * `momentum` - Momentum of running statistics, from range `[0, 1]`.
+/// * `epsilon` - Small value added to variance for numerical stability.
+///
+/// **Panics** if `axis` is out of bounds, shapes of `gamma`, `beta` or running
+/// statistics are not `[channels]`, running statistics are not variables or `momentum`
+/// is out of range.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::tensor::normalization::batch_norm;
+/// use neurust::tensor::TrainingFlag;
+///
+/// let input = Tensor::new_variable(Array::from_vec(vec![1., 2., 3., 4.], vec![2, 2]));
+/// let gamma = Tensor::new_variable(Array::new(1., vec![2]));
+/// let beta = Tensor::new_variable(Array::new(0., vec![2]));
+/// let running_mean = Tensor::new_variable(Array::new(0., vec![2]));
+/// let running_variance = Tensor::new_variable(Array::new(1., vec![2]));
+/// let training = TrainingFlag::new(true);
+/// let output = batch_norm(
+///     &input, &gamma, &beta, &running_mean, &running_variance, &training, 1, 0.5, 0.
+/// );
+///
+/// let (value, _) = output.eval_and_grads(&[], None);
+///
+/// assert_eq!(value, Array::from_vec(vec![-1., -1., 1., 1.], vec![2, 2]));
+/// assert_eq!(running_mean.eval(None), Array::from_vec(vec![1., 1.5], vec![2]));
+///
+/// training.set(false);
+///
+/// assert_eq!(output.eval(None), Array::from_vec(vec![0., 0.5, 2., 2.5], vec![2, 2]));
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn batch_norm<T: Numeric>(
+    input: &Tensor<T>,
+    gamma: &Tensor<T>,
+    beta: &Tensor<T>,
+    running_mean: &Tensor<T>,
+    running_variance: &Tensor<T>,
+    training: &TrainingFlag,
+    axis: usize,
+    momentum: T,
+    epsilon: T,
+) -> Tensor<T> {
+    let running_data = |tensor: &Tensor<T>| {
+        Rc::clone(
+            tensor
+                .variable_data
+                .as_ref()
+                .expect("Running statistics must be variable tensors."),
+        )
+    };
+    Tensor::new(Rc::new(BatchNormOp::new(
+        Rc::clone(&input.op),
+        Rc::clone(&gamma.op),
+        Rc::clone(&beta.op),
+        running_data(running_mean),
+        running_data(running_variance),
+        training.clone(),
+        axis,
+        momentum,
+        epsilon,
+    )))
+}
+
+/// Creates a tensor that evaluates to layer normalization of `input`.
+///
+/// Input is normalized to zero mean and unit variance over `axes` (separately for
+/// every index of the remaining axes), then scaled by `gamma` and shifted by `beta`.
+/// Unlike batch normalization it behaves identically during training and inference.
+///
+/// * `input` - Tensor to be normalized.
+/// * `gamma` - Scale with shape of the normalized axes, e.g. `[features]` when
+///   normalizing over the last axis of `[batch, features]`.
+/// * `beta` - Shift with the same shape as `gamma`.
+/// * `axes` - Axes to normalize over.
+/// * `epsilon` - Small value added to variance for numerical stability.
+///
+/// **Panics** if `axes` are empty, repeated or out of bounds, or shapes of `gamma`
+/// or `beta` don't match the normalized axes.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::tensor::normalization::layer_norm;
+///
+/// let input = Tensor::new_variable(Array::from_vec(vec![1., 3., 2., 6.], vec![2, 2]));
+/// let gamma = Tensor::new_variable(Array::from_vec(vec![1., 2.], vec![2]));
+/// let beta = Tensor::new_variable(Array::new(1., vec![2]));
+/// let output = layer_norm(&input, &gamma, &beta, &[1], 0.);
+///
+/// assert_eq!(output.eval(None), Array::from_vec(vec![0., 3., 0., 3.], vec![2, 2]));
+/// ```
+pub fn layer_norm<T: Numeric>(
+    input: &Tensor<T>,
+    gamma: &Tensor<T>,
+    beta: &Tensor<T>,
+    axes: &[usize],
+    epsilon: T,
+) -> Tensor<T> {
+    Tensor::new(Rc::new(NormalizationOp::new_layer_norm(
+        Rc::clone(&input.op),
+        Rc::clone(&gamma.op),
+        Rc::clone(&beta.op),
+        axes,
+        epsilon,
+    )))
+}
+
+/// Creates a tensor that evaluates to group normalization of `input`.
+///
+/// Channels are split into `num_groups` groups of consecutive channels. Every group of
+/// every sample (index along the first axis) is normalized to zero mean and unit
+/// variance over its channels and all remaining axes, then scaled by `gamma` and
+/// shifted by `beta` per channel.
+///
+/// * `input` - Tensor to be normalized, with the batch dimension first.
+/// * `gamma` - Scale of shape `[channels]`.
+/// * `beta` - Shift of shape `[channels]`.
+/// * `num_groups` - Number of groups, must divide number of channels.
+/// * `axis` - Channel axis, e.g. `1` for `NCHW` and `3` for `NHWC` images.
+/// * `epsilon` - Small value added to variance for numerical stability.
+///
+/// **Panics** if `axis` is `0` or out of bounds, number of channels isn't divisible
+/// by `num_groups`, or shapes of `gamma` or `beta` are not `[channels]`.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::tensor::normalization::group_norm;
+///
+/// let input = Tensor::new_variable(
+///     Array::from_vec(vec![1., 3., 2., 6., 0., 2., 4., 8.], vec![2, 2, 2])
+/// );
+/// let gamma = Tensor::new_variable(Array::new(1., vec![2]));
+/// let beta = Tensor::new_variable(Array::new(0., vec![2]));
+/// let output = group_norm(&input, &gamma, &beta, 2, 1, 0.);
+///
+/// assert_eq!(
+///     output.eval(None),
+///     Array::from_vec(vec![-1., 1., -1., 1., -1., 1., -1., 1.], vec![2, 2, 2])
+/// );
+/// ```
+pub fn group_norm<T: Numeric>(
+    input: &Tensor<T>,
+    gamma: &Tensor<T>,
+    beta: &Tensor<T>,
+    num_groups: usize,
+    axis: usize,
+    epsilon: T,
+) -> Tensor<T> {
+    Tensor::new(Rc::new(NormalizationOp::new_group_norm(
+        Rc::clone(&input.op),
+        Rc::clone(&gamma.op),
+        Rc::clone(&beta.op),
+        num_groups,
+        axis,
+        epsilon,
+    )))
+}
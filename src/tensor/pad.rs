@@ -0,0 +1,36 @@
+use crate::graph::pad::PadOp;
+use crate::linalg::{Numeric, PadMode};
+use crate::Tensor;
+use std::rc::Rc;
+
+/// Creates a tensor that evaluates to padded `tensor`.
+///
+/// See `linalg::pad` for description of the parameters. Gradient of the padded region
+/// is cropped back, and for non-constant modes gradients of padded values are
+/// added to the elements they were copied from.
+///
+/// **Panics** if `pad_widths` has wrong length or pad widths are too big for given mode.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::linalg::PadMode;
+/// use neurust::tensor::pad::pad;
+///
+/// let a = Tensor::new_variable(Array::from_vec(vec![1., 2., 3.], vec![1, 3]));
+/// let padded = pad(&a, &[(0, 0), (1, 1)], PadMode::Constant(0.));
+///
+/// assert_eq!(padded.eval(None), Array::from_vec(vec![0., 1., 2., 3., 0.], vec![1, 5]));
+/// assert_eq!(padded.grad(&a, None).unwrap(), Array::new(1., vec![1, 3]));
+/// ```
+pub fn pad<T: Numeric>(
+    tensor: &Tensor<T>,
+    pad_widths: &[(usize, usize)],
+    mode: PadMode<T>,
+) -> Tensor<T> {
+    Tensor::new(Rc::new(PadOp::new(
+        Rc::clone(&tensor.op),
+        pad_widths.to_vec(),
+        mode,
+    )))
+}
@@ -0,0 +1,129 @@
+use crate::graph::pool::{AvgPool2dOp, MaxPool2dOp};
+use crate::linalg::{DataFormat, Numeric, Pool2dParams};
+use crate::Tensor;
+use std::rc::Rc;
+
+/// Creates a tensor that evaluates to 2D max pooling of `input`.
+///
+/// See `linalg::max_pool2d` for description of the parameters. Gradient is routed
+/// to positions of maximal elements of every window.
+///
+/// **Panics** if `input` isn't 4-dimensional, `kernel_size` or `stride` contain zero,
+/// `padding` isn't smaller than `kernel_size` or the window doesn't fit into the padded input.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::linalg::DataFormat;
+/// use neurust::tensor::pool::max_pool2d;
+///
+/// let input = Tensor::new_variable(Array::from_vec(vec![1., 5., 2., 0.], vec![1, 1, 1, 4]));
+/// let output = max_pool2d(&input, (1, 2), (1, 2), (0, 0), DataFormat::Nchw);
+///
+/// assert_eq!(output.eval(None), Array::from_vec(vec![5., 2.], vec![1, 1, 1, 2]));
+/// assert_eq!(
+///     output.grad(&input, None).unwrap(),
+///     Array::from_vec(vec![0., 1., 1., 0.], vec![1, 1, 1, 4])
+/// );
+/// ```
+pub fn max_pool2d<T: Numeric>(
+    input: &Tensor<T>,
+    kernel_size: (usize, usize),
+    stride: (usize, usize),
+    padding: (usize, usize),
+    data_format: DataFormat,
+) -> Tensor<T> {
+    Tensor::new(Rc::new(MaxPool2dOp::new(
+        Rc::clone(&input.op),
+        Pool2dParams {
+            kernel_size,
+            stride,
+            padding,
+        },
+        data_format,
+    )))
+}
+
+/// Creates a tensor that evaluates to 2D average pooling of `input`.
+///
+/// See `linalg::avg_pool2d` for description of the parameters. Gradient is spread
+/// evenly over elements of every window.
+///
+/// **Panics** if `input` isn't 4-dimensional, `kernel_size` or `stride` contain zero,
+/// `padding` isn't smaller than `kernel_size` or the window doesn't fit into the padded input.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::linalg::DataFormat;
+/// use neurust::tensor::pool::avg_pool2d;
+///
+/// let input = Tensor::new_variable(Array::from_vec(vec![1., 5., 2., 0.], vec![1, 1, 1, 4]));
+/// let output = avg_pool2d(&input, (1, 2), (1, 2), (0, 0), DataFormat::Nchw);
+///
+/// assert_eq!(output.eval(None), Array::from_vec(vec![3., 1.], vec![1, 1, 1, 2]));
+/// assert_eq!(output.grad(&input, None).unwrap(), Array::new(0.5, vec![1, 1, 1, 4]));
+/// ```
+pub fn avg_pool2d<T: Numeric>(
+    input: &Tensor<T>,
+    kernel_size: (usize, usize),
+    stride: (usize, usize),
+    padding: (usize, usize),
+    data_format: DataFormat,
+) -> Tensor<T> {
+    Tensor::new(Rc::new(AvgPool2dOp::new(
+        Rc::clone(&input.op),
+        Pool2dParams {
+            kernel_size,
+            stride,
+            padding,
+        },
+        data_format,
+    )))
+}
+
+/// Creates a tensor that evaluates to maximum over spatial dimensions of `input`.
+///
+/// See `linalg::global_max_pool2d` for description of the parameters.
+///
+/// **Panics** if `input` isn't 4-dimensional.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::linalg::DataFormat;
+/// use neurust::tensor::pool::global_max_pool2d;
+///
+/// let input = Tensor::<f64>::new_placeholder("x".to_string(), vec![8, 7, 7, 16]);
+///
+/// assert_eq!(global_max_pool2d(&input, DataFormat::Nhwc).shape(), vec![8, 1, 1, 16]);
+/// ```
+pub fn global_max_pool2d<T: Numeric>(input: &Tensor<T>, data_format: DataFormat) -> Tensor<T> {
+    Tensor::new(Rc::new(MaxPool2dOp::new_global(
+        Rc::clone(&input.op),
+        data_format,
+    )))
+}
+
+/// Creates a tensor that evaluates to mean over spatial dimensions of `input`.
+///
+/// See `linalg::global_avg_pool2d` for description of the parameters.
+///
+/// **Panics** if `input` isn't 4-dimensional.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::linalg::DataFormat;
+/// use neurust::tensor::pool::global_avg_pool2d;
+///
+/// let input = Tensor::<f64>::new_placeholder("x".to_string(), vec![8, 16, 7, 7]);
+///
+/// assert_eq!(global_avg_pool2d(&input, DataFormat::Nchw).shape(), vec![8, 16, 1, 1]);
+/// ```
+pub fn global_avg_pool2d<T: Numeric>(input: &Tensor<T>, data_format: DataFormat) -> Tensor<T> {
+    Tensor::new(Rc::new(AvgPool2dOp::new_global(
+        Rc::clone(&input.op),
+        data_format,
+    )))
+}
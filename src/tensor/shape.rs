@@ -0,0 +1,103 @@
+use crate::graph::shape::{PermuteOp, ReshapeOp, SelectOp, StackOp};
+use crate::graph::GraphOp;
+use crate::linalg::Numeric;
+use crate::Tensor;
+use std::rc::Rc;
+
+/// Creates a tensor that evaluates to `tensor` with a new shape.
+///
+/// * `tensor` - Input tensor.
+/// * `shape` - New shape with the same number of elements.
+///
+/// **Panics** if `shape` contains zero or has a different number of elements.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::tensor::shape::reshape;
+///
+/// let a = Tensor::new_variable(Array::from_vec(vec![1., 2., 3., 4.], vec![2, 2]));
+///
+/// assert_eq!(reshape(&a, vec![4]).eval(None), Array::from_vec(vec![1., 2., 3., 4.], vec![4]));
+/// ```
+pub fn reshape<T: Numeric>(tensor: &Tensor<T>, shape: Vec<usize>) -> Tensor<T> {
+    Tensor::new(Rc::new(ReshapeOp::new(Rc::clone(&tensor.op), shape)))
+}
+
+/// Creates a tensor that evaluates to tensors stacked along a new axis.
+///
+/// See `neurust::linalg::stack()` for details.
+///
+/// * `tensors` - Non-empty slice of tensors with equal shapes.
+/// * `axis` - Position of the new axis in the output shape.
+///
+/// **Panics** if `tensors` is empty, their shapes differ or `axis` is out of bounds.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::tensor::shape::stack;
+///
+/// let a = Tensor::new_variable(Array::from_vec(vec![1., 2.], vec![2]));
+/// let b = Tensor::new_variable(Array::from_vec(vec![3., 4.], vec![2]));
+///
+/// assert_eq!(
+///     stack(&[&a, &b], 1).eval(None),
+///     Array::from_vec(vec![1., 3., 2., 4.], vec![2, 2])
+/// );
+/// ```
+pub fn stack<T: Numeric>(tensors: &[&Tensor<T>], axis: usize) -> Tensor<T> {
+    let inputs: Vec<Rc<dyn GraphOp<T>>> = tensors.iter().map(|t| Rc::clone(&t.op)).collect();
+    Tensor::new(Rc::new(StackOp::new(inputs, axis)))
+}
+
+/// Creates a tensor that evaluates to the sub-array of `tensor` at `index` along `axis`,
+/// with the axis removed.
+///
+/// See `Array::select()` for details.
+///
+/// * `tensor` - Input tensor.
+/// * `axis` - Axis to select along.
+/// * `index` - Index smaller than the size of `axis`.
+///
+/// **Panics** if `axis` or `index` is out of bounds.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::tensor::shape::select;
+///
+/// // second time step of a `[batch, time, features]` sequence
+/// let a = Tensor::new_variable(Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![1, 3, 2]));
+///
+/// assert_eq!(select(&a, 1, 1).eval(None), Array::from_vec(vec![3., 4.], vec![1, 2]));
+/// ```
+pub fn select<T: Numeric>(tensor: &Tensor<T>, axis: usize, index: usize) -> Tensor<T> {
+    Tensor::new(Rc::new(SelectOp::new(Rc::clone(&tensor.op), axis, index)))
+}
+
+/// Creates a tensor that evaluates to `tensor` with permuted dimensions.
+///
+/// `i`-th dimension of the resulting tensor corresponds to `axes[i]`-th dimension
+/// of `tensor`, see `Array::permute`.
+///
+/// * `tensor` - Input tensor.
+/// * `axes` - Permutation of dimensions.
+///
+/// **Panics** if `axes` is not a permutation of `tensor`'s dimensions.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::tensor::shape::permute;
+///
+/// let a = Tensor::new_variable(Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![1, 2, 3]));
+///
+/// assert_eq!(
+///     permute(&a, &[2, 0, 1]).eval(None),
+///     Array::from_vec(vec![1., 4., 2., 5., 3., 6.], vec![3, 1, 2])
+/// );
+/// ```
+pub fn permute<T: Numeric>(tensor: &Tensor<T>, axes: &[usize]) -> Tensor<T> {
+    Tensor::new(Rc::new(PermuteOp::new(Rc::clone(&tensor.op), axes)))
+}
@@ -0,0 +1,125 @@
+use crate::linalg::{scatter_add_rows, Array, Numeric};
+use std::collections::BTreeMap;
+
+/// Sparse representation of an array in which only some rows are non-zero.
+///
+/// Gradients of embedding lookups with respect to their tables touch only the
+/// gathered rows, so they can be stored and applied as `IndexedSlices` instead of
+/// full dense arrays. Indices may repeat, in which case the rows are summed.
+///
+/// * `indices` - Row indices of the dense array.
+/// * `values` - Array of shape `[indices.len(), ...]` with the rows.
+/// * `dense_shape` - Shape of the represented dense array.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexedSlices<T: Numeric> {
+    indices: Vec<usize>,
+    values: Array<T>,
+    dense_shape: Vec<usize>,
+}
+
+impl<T: Numeric> IndexedSlices<T> {
+    /// Creates a new `IndexedSlices` object.
+    ///
+    /// * `indices` - Row indices of the dense array.
+    /// * `values` - Array of shape `[indices.len(), ...]`, where `...` are the trailing
+    ///   dimensions of `dense_shape`.
+    /// * `dense_shape` - Shape of the represented dense array.
+    ///
+    /// **Panics** if shapes don't match or any index is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// use neurust::prelude::*;
+    /// use neurust::tensor::IndexedSlices;
+    ///
+    /// let slices = IndexedSlices::new(
+    ///     vec![2, 0, 2],
+    ///     Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![3, 2]),
+    ///     vec![3, 2],
+    /// );
+    ///
+    /// assert_eq!(
+    ///     slices.to_dense(),
+    ///     Array::from_vec(vec![3., 4., 0., 0., 6., 8.], vec![3, 2])
+    /// );
+    /// assert_eq!(slices.coalesce().indices(), &[0, 2]);
+    /// ```
+    pub fn new(indices: Vec<usize>, values: Array<T>, dense_shape: Vec<usize>) -> IndexedSlices<T> {
+        let mut expected_shape = vec![indices.len()];
+        expected_shape.extend_from_slice(&dense_shape[1..]);
+        if dense_shape.is_empty() || values.shape != expected_shape {
+            panic!(
+                "Values must have shape {:?}. Got: {:?}",
+                expected_shape, values.shape
+            )
+        }
+        if let Some(index) = indices.iter().find(|&&index| index >= dense_shape[0]) {
+            panic!(
+                "Index {} is out of bounds for dense shape {:?}.",
+                index, dense_shape
+            )
+        }
+        IndexedSlices {
+            indices,
+            values,
+            dense_shape,
+        }
+    }
+
+    /// Returns row indices.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Returns rows as an array of shape `[indices.len(), ...]`.
+    pub fn values(&self) -> &Array<T> {
+        &self.values
+    }
+
+    /// Returns the shape of the represented dense array.
+    pub fn dense_shape(&self) -> Vec<usize> {
+        self.dense_shape.clone()
+    }
+
+    /// Returns new slices with `f` applied to every value, e.g. scaled by a learning rate.
+    ///
+    /// * `f` - Function applied to values.
+    pub fn map(&self, f: impl Fn(T) -> T) -> IndexedSlices<T> {
+        IndexedSlices {
+            indices: self.indices.clone(),
+            values: self.values.map(f),
+            dense_shape: self.dense_shape.clone(),
+        }
+    }
+
+    /// Returns equivalent slices with sorted, unique indices.
+    pub fn coalesce(&self) -> IndexedSlices<T> {
+        let row_size: usize = self.dense_shape[1..].iter().product();
+        let mut rows = BTreeMap::<usize, Vec<T>>::new();
+        for (&index, row) in self.indices.iter().zip(self.values.data.chunks(row_size)) {
+            let sum = rows
+                .entry(index)
+                .or_insert_with(|| vec![T::zero(); row_size]);
+            for (x, &y) in sum.iter_mut().zip(row.iter()) {
+                *x = *x + y;
+            }
+        }
+        let mut shape = vec![rows.len()];
+        shape.extend_from_slice(&self.dense_shape[1..]);
+        IndexedSlices {
+            indices: rows.keys().copied().collect(),
+            values: Array {
+                data: rows.into_values().flatten().collect(),
+                shape,
+            },
+            dense_shape: self.dense_shape.clone(),
+        }
+    }
+
+    /// Returns the represented dense array.
+    pub fn to_dense(&self) -> Array<T> {
+        let mut dense = Array::new(T::zero(), self.dense_shape.clone());
+        scatter_add_rows(&mut dense, &self.indices, &self.values);
+        dense
+    }
+}
@@ -0,0 +1,54 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Shared switch between training and inference mode.
+///
+/// Some operators (e.g. batch normalization) behave differently during training
+/// and inference. Such operators hold a `TrainingFlag` which is read every time
+/// they are computed, so the mode can be changed without rebuilding the graph.
+/// Clones of a flag share its state.
+///
+/// # Examples
+/// ```
+/// use neurust::tensor::TrainingFlag;
+///
+/// let flag = TrainingFlag::new(true);
+/// let shared = flag.clone();
+///
+/// shared.set(false);
+///
+/// assert!(!flag.is_training());
+/// ```
+#[derive(Clone, Debug)]
+pub struct TrainingFlag {
+    training: Rc<Cell<bool>>,
+}
+
+impl TrainingFlag {
+    /// Creates a new flag.
+    ///
+    /// * `training` - If true, the flag starts in training mode.
+    pub fn new(training: bool) -> TrainingFlag {
+        TrainingFlag {
+            training: Rc::new(Cell::new(training)),
+        }
+    }
+
+    /// Sets the mode.
+    ///
+    /// * `training` - If true, training mode is set, otherwise inference mode is set.
+    pub fn set(&self, training: bool) {
+        self.training.set(training);
+    }
+
+    /// Returns true if the flag is in training mode.
+    pub fn is_training(&self) -> bool {
+        self.training.get()
+    }
+}
+
+impl Default for TrainingFlag {
+    fn default() -> Self {
+        TrainingFlag::new(true)
+    }
+}
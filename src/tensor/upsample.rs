@@ -0,0 +1,42 @@
+use crate::graph::upsample::Upsample2dOp;
+use crate::linalg::{DataFormat, Interpolation, Numeric};
+use crate::Tensor;
+use std::rc::Rc;
+
+/// Creates a tensor that evaluates to an image batch upsampled by integer factors.
+///
+/// See `linalg::upsample2d` for description of the parameters.
+///
+/// **Panics** if `input` isn't 4-dimensional or `scale_factor` contains zero.
+///
+/// # Examples
+/// ```
+/// use neurust::prelude::*;
+/// use neurust::linalg::{DataFormat, Interpolation};
+/// use neurust::tensor::upsample::upsample2d;
+///
+/// let input = Tensor::new_variable(Array::from_vec(vec![1., 2.], vec![1, 1, 1, 2]));
+/// let output = upsample2d(&input, (2, 2), Interpolation::Nearest, DataFormat::Nchw);
+///
+/// assert_eq!(
+///     output.eval(None),
+///     Array::from_vec(vec![1., 1., 2., 2., 1., 1., 2., 2.], vec![1, 1, 2, 4])
+/// );
+/// assert_eq!(
+///     output.grad(&input, None).unwrap(),
+///     Array::new(4., vec![1, 1, 1, 2])
+/// );
+/// ```
+pub fn upsample2d<T: Numeric>(
+    input: &Tensor<T>,
+    scale_factor: (usize, usize),
+    mode: Interpolation,
+    data_format: DataFormat,
+) -> Tensor<T> {
+    Tensor::new(Rc::new(Upsample2dOp::new(
+        Rc::clone(&input.op),
+        scale_factor,
+        mode,
+        data_format,
+    )))
+}
@@ -285,4 +285,31 @@ mod tests {
 
         a.matmul(&b);
     }
+
+    #[test]
+    fn test_permute() {
+        let a = Array::from_vec(
+            vec![1., 2., 3., 4., 5., 6., 7., 8., 9., 10., 11., 12.],
+            vec![2, 3, 2],
+        );
+
+        let result = a.permute(&[1, 2, 0]);
+
+        assert_eq!(
+            result,
+            Array::from_vec(
+                vec![1., 7., 2., 8., 3., 9., 4., 10., 5., 11., 6., 12.],
+                vec![3, 2, 2]
+            )
+        );
+        assert_eq!(result.permute(&[2, 0, 1]), a);
+    }
+
+    #[should_panic]
+    #[test]
+    fn test_permute_invalid_axes() {
+        let a = Array::new(1., vec![2, 3, 2]);
+
+        a.permute(&[0, 1, 1]);
+    }
 }
@@ -0,0 +1,263 @@
+mod common;
+
+use common::{numerical_grad, weighted_sum};
+use neurust::linalg::utils::are_arrays_near_equal;
+use neurust::linalg::{softmax as softmax_array, Rng};
+use neurust::nn::layers::{MultiHeadAttention, TransformerEncoderLayer};
+use neurust::nn::Module;
+use neurust::tensor::attention::{masked_softmax, scaled_dot_product_attention, softmax};
+use neurust::tensor::shape::permute;
+use neurust::{assert_arrays_rel_eq, Array, Tensor};
+use std::collections::HashMap;
+
+#[test]
+fn test_softmax_values() {
+    let a = Array::from_vec(vec![0., 1., 2., 1000., 1000., 1000.], vec![2, 3]);
+    let sum = 1. + 1f64.exp() + 2f64.exp();
+
+    assert_arrays_rel_eq!(
+        softmax_array(&a, 1),
+        Array::from_vec(
+            vec![
+                1. / sum,
+                1f64.exp() / sum,
+                2f64.exp() / sum,
+                1. / 3.,
+                1. / 3.,
+                1. / 3.
+            ],
+            vec![2, 3]
+        ),
+        1e-12
+    );
+}
+
+#[test]
+fn test_softmax_gradient() {
+    let mut rng = Rng::new(5);
+    let input_array = Array::random_uniform(vec![3, 4, 2], -2., 2., &mut rng);
+    let weights = Array::random_uniform(vec![3, 4, 2], -1., 1., &mut rng);
+    let input = Tensor::new_variable(input_array.clone());
+    let loss = &softmax(&input, 1) * &Tensor::new_variable(weights.clone());
+    let eval = |x: &Array<f64>| weighted_sum(&softmax_array(x, 1), &weights);
+
+    assert_arrays_rel_eq!(
+        loss.grad(&input, None).unwrap(),
+        numerical_grad(eval, &input_array),
+        1e-5
+    );
+}
+
+#[test]
+fn test_permute_gradient() {
+    let a = Tensor::new_variable(Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![1, 2, 3]));
+    let weights = Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![3, 1, 2]);
+    let output = &permute(&a, &[2, 0, 1]) * &Tensor::new_variable(weights.clone());
+
+    assert_eq!(output.grad(&a, None).unwrap(), weights.permute(&[1, 2, 0]));
+}
+
+#[test]
+fn test_causal_attention_attends_to_previous_positions() {
+    let mut rng = Rng::new(0);
+    let query = Tensor::new_variable(Array::random_uniform(vec![2, 3, 4], -1., 1., &mut rng));
+    let key = Tensor::new_variable(Array::random_uniform(vec![2, 3, 4], -1., 1., &mut rng));
+    let value_array: Array<f64> = Array::random_uniform(vec![2, 3, 2], -1., 1., &mut rng);
+    let value = Tensor::new_variable(value_array.clone());
+    let output = scaled_dot_product_attention(&query, &key, &value, None, true).eval(None);
+
+    // the first position can attend only to itself
+    for batch in 0..2 {
+        for i in 0..2 {
+            assert!((output[vec![batch, 0, i]] - value_array[vec![batch, 0, i]]).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn test_attention_ignores_padded_keys() {
+    let mut rng = Rng::new(1);
+    let query = Tensor::new_variable(Array::random_uniform(vec![2, 2, 4], -1., 1., &mut rng));
+    let key_array = Array::random_uniform(vec![2, 3, 4], -1., 1., &mut rng);
+    let key = Tensor::new_variable(key_array.clone());
+    let value = Tensor::new_variable(Array::random_uniform(vec![2, 3, 2], -1., 1., &mut rng));
+    let padding_mask = Tensor::new_placeholder("mask".to_owned(), vec![2, 3]);
+    let output = scaled_dot_product_attention(&query, &key, &value, Some(&padding_mask), false);
+
+    let mask = Array::from_vec(vec![1., 1., 0., 1., 0., 0.], vec![2, 3]);
+    let mut feed_dict = HashMap::new();
+    feed_dict.insert("mask".to_owned(), &mask);
+    let masked_output = output.eval(Some(&feed_dict));
+    let mut changed_key = key_array.clone();
+    changed_key[vec![0, 2, 0]] = 100.;
+    changed_key[vec![1, 1, 3]] = -100.;
+    key.assign(&changed_key);
+
+    assert_eq!(output.eval(Some(&feed_dict)), masked_output);
+
+    let no_padding = Array::new(1., vec![2, 3]);
+    let mut feed_dict = HashMap::new();
+    feed_dict.insert("mask".to_owned(), &no_padding);
+
+    assert_ne!(output.eval(Some(&feed_dict)), masked_output);
+}
+
+#[test]
+fn test_masked_softmax_fully_masked_query() {
+    let scores = Tensor::new_variable(Array::new(1., vec![1, 2, 2]));
+    let padding_mask = Tensor::new_variable(Array::new(0., vec![1, 2]));
+
+    assert_eq!(
+        masked_softmax(&scores, Some(&padding_mask), false).eval(None),
+        Array::new(0., vec![1, 2, 2])
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_masked_softmax_invalid_mask_shape() {
+    let scores = Tensor::new_variable(Array::new(1., vec![1, 2, 3]));
+    let padding_mask = Tensor::new_variable(Array::new(1., vec![1, 2]));
+
+    masked_softmax(&scores, Some(&padding_mask), false);
+}
+
+#[test]
+fn test_attention_gradients() {
+    let mut rng = Rng::new(2);
+    let arrays: Vec<Array<f64>> = [vec![2, 2, 3, 4], vec![2, 2, 5, 4], vec![2, 2, 5, 3]]
+        .iter()
+        .map(|shape| Array::random_uniform(shape.clone(), -1., 1., &mut rng))
+        .collect();
+    let weights = Array::random_uniform(vec![2, 2, 3, 3], -1., 1., &mut rng);
+    let padding_mask = Tensor::new_variable(Array::from_vec(
+        vec![1., 1., 1., 0., 1., 1., 1., 1., 0., 0.],
+        vec![2, 5],
+    ));
+    for causal in [false, true] {
+        let tensors: Vec<Tensor<f64>> = arrays
+            .iter()
+            .map(|array| Tensor::new_variable(array.clone()))
+            .collect();
+        let build = |tensors: &[Tensor<f64>]| {
+            scaled_dot_product_attention(
+                &tensors[0],
+                &tensors[1],
+                &tensors[2],
+                Some(&padding_mask),
+                causal,
+            )
+        };
+        let loss = &build(&tensors) * &Tensor::new_variable(weights.clone());
+        for (i, tensor) in tensors.iter().enumerate() {
+            let eval = |x: &Array<f64>| {
+                let mut inputs: Vec<Tensor<f64>> = arrays
+                    .iter()
+                    .map(|array| Tensor::new_variable(array.clone()))
+                    .collect();
+                inputs[i] = Tensor::new_variable(x.clone());
+                weighted_sum(&build(&inputs).eval(None), &weights)
+            };
+
+            assert_arrays_rel_eq!(
+                loss.grad(tensor, None).unwrap(),
+                numerical_grad(eval, &arrays[i]),
+                1e-5
+            );
+        }
+    }
+}
+
+#[test]
+fn test_multi_head_attention_gradients() {
+    let mut rng = Rng::new(3);
+    let attention = MultiHeadAttention::new(4, 2, true, &mut rng);
+    let input_array = Array::random_uniform(vec![2, 3, 4], -1., 1., &mut rng);
+    let weights = Array::random_uniform(vec![2, 3, 4], -1., 1., &mut rng);
+    let input = Tensor::new_variable(input_array.clone());
+    let output = attention.attend(&input, &input, &input, None, true);
+    let loss = &output * &Tensor::new_variable(weights.clone());
+    let eval = |x: &Array<f64>| {
+        let x = Tensor::new_variable(x.clone());
+        weighted_sum(
+            &attention.attend(&x, &x, &x, None, true).eval(None),
+            &weights,
+        )
+    };
+
+    assert_arrays_rel_eq!(
+        loss.grad(&input, None).unwrap(),
+        numerical_grad(eval, &input_array),
+        1e-5
+    );
+    for (name, parameter) in attention.named_parameters() {
+        // attention weights don't depend on key bias, so its gradient is zero
+        if name == "key_bias" {
+            assert_eq!(
+                loss.grad(parameter, None)
+                    .unwrap()
+                    .map(|x| if x.abs() < 1e-12 { 0. } else { x }),
+                Array::new(0., vec![4])
+            );
+            continue;
+        }
+        let parameter_array = parameter.eval(None);
+        let eval = |value: &Array<f64>| {
+            parameter.assign(value);
+            weighted_sum(&output.eval(None), &weights)
+        };
+        let expected = numerical_grad(eval, &parameter_array);
+        parameter.assign(&parameter_array);
+
+        assert_arrays_rel_eq!(loss.grad(parameter, None).unwrap(), expected, 1e-5);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_multi_head_attention_indivisible_heads() {
+    MultiHeadAttention::<f64>::new(6, 4, true, &mut Rng::new(0));
+}
+
+#[test]
+fn test_transformer_encoder_layer() {
+    let mut rng = Rng::new(4);
+    let encoder = TransformerEncoderLayer::new(4, 2, 8, 0.5, 1e-5, &mut rng);
+    let input_array = Array::random_uniform(vec![2, 3, 4], -1., 1., &mut rng);
+    let input = Tensor::new_variable(input_array.clone());
+    let padding_mask =
+        Tensor::new_variable(Array::from_vec(vec![1., 1., 0., 1., 1., 1.], vec![2, 3]));
+    let output = encoder.forward_with_mask(&input, Some(&padding_mask), false);
+
+    assert_eq!(output.shape(), vec![2, 3, 4]);
+    assert_ne!(output.eval(None), output.eval(None));
+    assert_eq!(
+        &encoder
+            .named_parameters()
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<&str>>()[6..10],
+        [
+            "self_attention.output_kernel",
+            "self_attention.output_bias",
+            "feed_forward_1.kernel",
+            "feed_forward_1.bias"
+        ]
+    );
+
+    encoder.set_training(false);
+    let weights = Array::random_uniform(vec![2, 3, 4], -1., 1., &mut rng);
+    let loss = &output * &Tensor::new_variable(weights.clone());
+    let eval = |x: &Array<f64>| {
+        let output =
+            encoder.forward_with_mask(&Tensor::new_variable(x.clone()), Some(&padding_mask), false);
+        weighted_sum(&output.eval(None), &weights)
+    };
+
+    assert_eq!(output.eval(None), output.eval(None));
+    assert_arrays_rel_eq!(
+        loss.grad(&input, None).unwrap(),
+        numerical_grad(eval, &input_array),
+        1e-5
+    );
+}
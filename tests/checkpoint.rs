@@ -0,0 +1,170 @@
+use neurust::io::{Checkpoint, Dtype, Error};
+use neurust::linalg::Rng;
+use neurust::nn::initializers::Initializer;
+use neurust::nn::layers::{BatchNorm, Dense};
+use neurust::nn::{Module, Sequential};
+use neurust::{Array, Tensor};
+
+fn build_model(rng: &mut Rng) -> Sequential<f64> {
+    let mut model = Sequential::new();
+    model.add(Dense::new(
+        3,
+        4,
+        None,
+        Initializer::GlorotUniform,
+        true,
+        rng,
+    ));
+    model.add(BatchNorm::new(4, 1, 0.5, 1e-5));
+    model.add(Dense::new(
+        4,
+        2,
+        None,
+        Initializer::GlorotUniform,
+        true,
+        rng,
+    ));
+    model
+}
+
+#[test]
+fn test_checkpoint_file_roundtrip() {
+    let path = std::env::temp_dir().join("neurust_test_checkpoint_file_roundtrip.ckpt");
+    let input = Array::random_uniform(vec![5, 3], -1., 1., &mut Rng::new(2));
+    let model = build_model(&mut Rng::new(0));
+    model
+        .forward(&Tensor::new_variable(input.clone()))
+        .eval_and_grads(&[], None);
+    model.set_training(false);
+    let mut checkpoint = Checkpoint::from_variables(&model.named_variables());
+    checkpoint.add_state("step", Array::new(7., vec![1]));
+    checkpoint.add_state("momentum", Array::new(0.5, vec![3, 4]));
+    checkpoint.save(&path).unwrap();
+
+    let restored_model = build_model(&mut Rng::new(1));
+    restored_model.set_training(false);
+    assert_ne!(restored_model.predict(&input), model.predict(&input));
+
+    let loaded = Checkpoint::load(&path).unwrap();
+    loaded.restore(&restored_model.named_variables()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(restored_model.predict(&input), model.predict(&input));
+    assert_eq!(loaded, checkpoint);
+    assert_eq!(loaded.state_names(), vec!["step", "momentum"]);
+    assert_eq!(loaded.state("step"), Some(&Array::new(7., vec![1])));
+}
+
+#[test]
+fn test_checkpoint_f32_roundtrip() {
+    let variable = Tensor::new_variable(Array::from_vec(vec![0.1f32, -2.5, 3.25], vec![3, 1]));
+    let checkpoint = Checkpoint::from_variables(&[("v".to_owned(), &variable)]);
+    let mut buffer = Vec::new();
+    checkpoint.write(&mut buffer).unwrap();
+
+    assert_eq!(
+        Checkpoint::<f32>::read(&mut buffer.as_slice()).unwrap(),
+        checkpoint
+    );
+    assert!(matches!(
+        Checkpoint::<f64>::read(&mut buffer.as_slice()),
+        Err(Error::DtypeMismatch {
+            expected: Dtype::F64,
+            found: Dtype::F32
+        })
+    ));
+}
+
+#[test]
+fn test_checkpoint_add_variable_replaces_value() {
+    let variable = Tensor::new_variable(Array::new(1., vec![2]));
+    let mut checkpoint = Checkpoint::new();
+    checkpoint.add_variable("v", &variable);
+    variable.assign(&Array::new(2., vec![2]));
+    checkpoint.add_variable("v", &variable);
+
+    assert_eq!(checkpoint.variable_names(), vec!["v"]);
+    assert_eq!(checkpoint.variable("v"), Some(&Array::new(2., vec![2])));
+}
+
+#[test]
+fn test_checkpoint_restore_errors_leave_variables_unchanged() {
+    let a = Tensor::new_variable(Array::new(1., vec![2]));
+    let b = Tensor::new_variable(Array::new(2., vec![2, 2]));
+    let checkpoint = Checkpoint::from_variables(&[
+        (
+            "a".to_owned(),
+            &Tensor::new_variable(Array::new(3., vec![2])),
+        ),
+        (
+            "b".to_owned(),
+            &Tensor::new_variable(Array::new(4., vec![2])),
+        ),
+    ]);
+
+    let missing = checkpoint.restore(&[("a".to_owned(), &a), ("c".to_owned(), &b)]);
+    let mismatch = checkpoint.restore(&[("a".to_owned(), &a), ("b".to_owned(), &b)]);
+
+    assert!(matches!(missing, Err(Error::Missing(name)) if name == "c"));
+    match mismatch {
+        Err(Error::ShapeMismatch {
+            name,
+            expected,
+            found,
+        }) => {
+            assert_eq!(name, "b");
+            assert_eq!(expected, vec![2, 2]);
+            assert_eq!(found, vec![2]);
+        }
+        _ => panic!("Expected shape mismatch."),
+    }
+    assert_eq!(a.eval(None), Array::new(1., vec![2]));
+    assert_eq!(b.eval(None), Array::new(2., vec![2, 2]));
+}
+
+#[test]
+fn test_checkpoint_malformed_data() {
+    let checkpoint = Checkpoint::from_variables(&[(
+        "v".to_owned(),
+        &Tensor::new_variable(Array::new(1., vec![4])),
+    )]);
+    let mut buffer = Vec::new();
+    checkpoint.write(&mut buffer).unwrap();
+
+    let truncated = Checkpoint::<f64>::read(&mut &buffer[..buffer.len() - 1]);
+    let invalid_magic = Checkpoint::<f64>::read(&mut &b"NOTACHECKPOINT"[..]);
+    let missing_file = Checkpoint::<f64>::load(std::env::temp_dir().join("neurust_missing.ckpt"));
+
+    let mut zero_dim = buffer.clone();
+    // the first dimension follows magic, header, count, name and rank
+    let dim_offset = 6 + 2 + 4 + 4 + 1 + 4;
+    zero_dim[dim_offset..dim_offset + 8].copy_from_slice(&0u64.to_le_bytes());
+
+    assert!(matches!(truncated, Err(Error::Format(_))));
+    assert!(matches!(invalid_magic, Err(Error::Format(_))));
+    assert!(matches!(
+        Checkpoint::<f64>::read(&mut zero_dim.as_slice()),
+        Err(Error::Format(_))
+    ));
+    assert!(matches!(missing_file, Err(Error::Io(_))));
+}
+
+#[test]
+fn test_checkpoint_includes_buffers() {
+    let model = build_model(&mut Rng::new(0));
+    let checkpoint = Checkpoint::from_variables(&model.named_variables());
+
+    assert_eq!(
+        checkpoint.variable_names(),
+        vec![
+            "dense_0/kernel",
+            "dense_0/bias",
+            "batchnorm_1/gamma",
+            "batchnorm_1/beta",
+            "dense_2/kernel",
+            "dense_2/bias",
+            "batchnorm_1/running_mean",
+            "batchnorm_1/running_variance",
+        ]
+    );
+}
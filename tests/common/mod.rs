@@ -0,0 +1,41 @@
+use neurust::Array;
+
+// Iterates over all multi-dimensional indices of a given shape in row-major order.
+pub fn indices(shape: &[usize]) -> Vec<Vec<usize>> {
+    let size: usize = shape.iter().product();
+    let mut result = Vec::with_capacity(size);
+    let mut index = vec![0; shape.len()];
+    for _ in 0..size {
+        result.push(index.clone());
+        for i in (0..shape.len()).rev() {
+            index[i] += 1;
+            if index[i] < shape[i] {
+                break;
+            }
+            index[i] = 0;
+        }
+    }
+    result
+}
+
+// Approximates gradient of a scalar function `f` at `x` with central differences.
+pub fn numerical_grad<F: Fn(&Array<f64>) -> f64>(f: F, x: &Array<f64>) -> Array<f64> {
+    let eps = 1e-6;
+    let mut grad = Array::new(0., x.get_shape());
+    for index in indices(&x.get_shape()) {
+        let mut x_plus = x.clone();
+        x_plus[index.clone()] = x_plus[index.clone()] + eps;
+        let mut x_minus = x.clone();
+        x_minus[index.clone()] = x_minus[index.clone()] - eps;
+        grad[index] = (f(&x_plus) - f(&x_minus)) / (2. * eps);
+    }
+    grad
+}
+
+// Computes sum of element-wise product of two arrays.
+pub fn weighted_sum(a: &Array<f64>, weights: &Array<f64>) -> f64 {
+    indices(&a.get_shape())
+        .into_iter()
+        .map(|index| a[index.clone()] * weights[index])
+        .sum()
+}
@@ -0,0 +1,423 @@
+mod common;
+
+use common::{indices, numerical_grad, weighted_sum};
+use neurust::linalg::utils::are_arrays_near_equal;
+use neurust::linalg::{
+    conv2d as conv2d_array, conv2d_transpose as conv2d_transpose_array, DataFormat, Rng,
+};
+use neurust::tensor::conv::{conv2d, conv2d_transpose};
+use neurust::{assert_arrays_rel_eq, Array, Tensor};
+
+// Direct (naive) NCHW convolution used as a reference implementation.
+fn naive_conv2d(
+    input: &Array<f64>,
+    kernel: &Array<f64>,
+    stride: (usize, usize),
+    padding: (usize, usize),
+    dilation: (usize, usize),
+    groups: usize,
+) -> Array<f64> {
+    let (n, h, w) = {
+        let s = input.get_shape();
+        (s[0], s[2], s[3])
+    };
+    let (c_out, c_g, kh, kw) = {
+        let s = kernel.get_shape();
+        (s[0], s[1], s[2], s[3])
+    };
+    let out_h = (h + 2 * padding.0 - dilation.0 * (kh - 1) - 1) / stride.0 + 1;
+    let out_w = (w + 2 * padding.1 - dilation.1 * (kw - 1) - 1) / stride.1 + 1;
+    let c_out_g = c_out / groups;
+    let mut output = Array::new(0., vec![n, c_out, out_h, out_w]);
+    for index in indices(&[n, c_out, out_h, out_w]) {
+        let (b, o, oi, oj) = (index[0], index[1], index[2], index[3]);
+        let g = o / c_out_g;
+        let mut sum = 0.;
+        for ci in 0..c_g {
+            for ki in 0..kh {
+                for kj in 0..kw {
+                    let i = (oi * stride.0 + ki * dilation.0) as isize - padding.0 as isize;
+                    let j = (oj * stride.1 + kj * dilation.1) as isize - padding.1 as isize;
+                    if i >= 0 && j >= 0 && (i as usize) < h && (j as usize) < w {
+                        sum += input[vec![b, g * c_g + ci, i as usize, j as usize]]
+                            * kernel[vec![o, ci, ki, kj]];
+                    }
+                }
+            }
+        }
+        output[index] = sum;
+    }
+    output
+}
+
+#[test]
+fn test_conv2d_matches_naive() {
+    let mut rng = Rng::new(0);
+    let configs = vec![
+        ((1, 1), (0, 0), (1, 1), 1),
+        ((2, 1), (1, 2), (1, 1), 1),
+        ((1, 2), (1, 1), (2, 1), 1),
+        ((2, 2), (2, 0), (1, 2), 2),
+    ];
+    for (stride, padding, dilation, groups) in configs {
+        let input = Array::random_uniform(vec![2, 4, 7, 6], -1., 1., &mut rng);
+        let kernel = Array::random_uniform(vec![6, 4 / groups, 3, 2], -1., 1., &mut rng);
+
+        assert_arrays_rel_eq!(
+            conv2d_array(
+                &input,
+                &kernel,
+                stride,
+                padding,
+                dilation,
+                groups,
+                DataFormat::Nchw
+            ),
+            naive_conv2d(&input, &kernel, stride, padding, dilation, groups),
+            1e-10
+        );
+    }
+}
+
+#[test]
+fn test_conv2d_nhwc() {
+    let mut rng = Rng::new(1);
+    let input = Array::random_uniform(vec![2, 3, 5, 5], -1., 1., &mut rng);
+    let kernel = Array::random_uniform(vec![4, 3, 3, 3], -1., 1., &mut rng);
+
+    let output = conv2d_array(
+        &input.permute(&[0, 2, 3, 1]),
+        &kernel,
+        (2, 2),
+        (1, 1),
+        (1, 1),
+        1,
+        DataFormat::Nhwc,
+    );
+
+    assert_arrays_rel_eq!(
+        output,
+        naive_conv2d(&input, &kernel, (2, 2), (1, 1), (1, 1), 1).permute(&[0, 2, 3, 1]),
+        1e-10
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_conv2d_wrong_channels() {
+    let input = Array::new(1., vec![1, 3, 5, 5]);
+    let kernel = Array::new(1., vec![2, 2, 3, 3]);
+
+    conv2d_array(&input, &kernel, (1, 1), (0, 0), (1, 1), 1, DataFormat::Nchw);
+}
+
+#[test]
+#[should_panic]
+fn test_conv2d_transpose_wrong_channels() {
+    let input = Array::new(1., vec![1, 2, 2, 2]);
+    let kernel = Array::new(1., vec![1, 1, 2, 2]);
+
+    conv2d_transpose_array(
+        &input,
+        &kernel,
+        (1, 1),
+        (0, 0),
+        (0, 0),
+        (1, 1),
+        1,
+        DataFormat::Nchw,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_conv2d_transpose_op_wrong_channels() {
+    let input = Tensor::new_variable(Array::new(1., vec![1, 2, 2, 2]));
+    let kernel = Tensor::new_variable(Array::new(1., vec![1, 1, 2, 2]));
+
+    conv2d_transpose(
+        &input,
+        &kernel,
+        (1, 1),
+        (0, 0),
+        (0, 0),
+        (1, 1),
+        1,
+        DataFormat::Nchw,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_conv2d_kernel_too_big() {
+    let input = Array::new(1., vec![1, 1, 2, 2]);
+    let kernel = Array::new(1., vec![1, 1, 3, 3]);
+
+    conv2d_array(&input, &kernel, (1, 1), (0, 0), (1, 1), 1, DataFormat::Nchw);
+}
+
+#[test]
+fn test_conv2d_tensor_shape() {
+    let input = Tensor::<f64>::new_placeholder("x".to_string(), vec![2, 9, 9, 3]);
+    let kernel = Tensor::new_variable(Array::new(1., vec![8, 3, 3, 3]));
+
+    let output = conv2d(&input, &kernel, (2, 2), (1, 1), (1, 1), 1, DataFormat::Nhwc);
+
+    assert_eq!(output.shape(), vec![2, 5, 5, 8]);
+}
+
+#[test]
+fn test_conv2d_gradients() {
+    let mut rng = Rng::new(2);
+    let configs = vec![
+        ((1, 1), (0, 0), (1, 1), 1, DataFormat::Nchw),
+        ((2, 1), (1, 1), (1, 2), 2, DataFormat::Nchw),
+        ((2, 2), (1, 0), (1, 1), 1, DataFormat::Nhwc),
+    ];
+    for (stride, padding, dilation, groups, data_format) in configs {
+        let input_shape = match data_format {
+            DataFormat::Nchw => vec![2, 2, 5, 6],
+            DataFormat::Nhwc => vec![2, 5, 6, 2],
+        };
+        let input_array = Array::random_uniform(input_shape, -1., 1., &mut rng);
+        let kernel_array = Array::random_uniform(vec![4, 2 / groups, 3, 2], -1., 1., &mut rng);
+        let input = Tensor::new_variable(input_array.clone());
+        let kernel = Tensor::new_variable(kernel_array.clone());
+        let output = conv2d(
+            &input,
+            &kernel,
+            stride,
+            padding,
+            dilation,
+            groups,
+            data_format,
+        );
+        let weights = Array::random_uniform(output.shape(), -1., 1., &mut rng);
+        let loss = &output * &Tensor::new_variable(weights.clone());
+
+        let expected_input_grad = numerical_grad(
+            |x| {
+                weighted_sum(
+                    &conv2d_array(
+                        x,
+                        &kernel_array,
+                        stride,
+                        padding,
+                        dilation,
+                        groups,
+                        data_format,
+                    ),
+                    &weights,
+                )
+            },
+            &input_array,
+        );
+        let expected_kernel_grad = numerical_grad(
+            |k| {
+                weighted_sum(
+                    &conv2d_array(
+                        &input_array,
+                        k,
+                        stride,
+                        padding,
+                        dilation,
+                        groups,
+                        data_format,
+                    ),
+                    &weights,
+                )
+            },
+            &kernel_array,
+        );
+
+        assert_arrays_rel_eq!(loss.grad(&input, None).unwrap(), expected_input_grad, 1e-6);
+        assert_arrays_rel_eq!(
+            loss.grad(&kernel, None).unwrap(),
+            expected_kernel_grad,
+            1e-6
+        );
+    }
+}
+
+// Direct (naive) NCHW transposed convolution used as a reference implementation.
+fn naive_conv2d_transpose(
+    input: &Array<f64>,
+    kernel: &Array<f64>,
+    stride: (usize, usize),
+    padding: (usize, usize),
+    output_padding: (usize, usize),
+    groups: usize,
+) -> Array<f64> {
+    let (n, c_in, h, w) = {
+        let s = input.get_shape();
+        (s[0], s[1], s[2], s[3])
+    };
+    let (c_out_g, kh, kw) = {
+        let s = kernel.get_shape();
+        (s[1], s[2], s[3])
+    };
+    let full_h = (h - 1) * stride.0 + kh + output_padding.0;
+    let full_w = (w - 1) * stride.1 + kw + output_padding.1;
+    let c_in_g = c_in / groups;
+    let mut full = Array::new(0., vec![n, c_out_g * groups, full_h, full_w]);
+    for index in indices(&[n, c_in, h, w]) {
+        let (b, ci, i, j) = (index[0], index[1], index[2], index[3]);
+        let g = ci / c_in_g;
+        for co in 0..c_out_g {
+            for ki in 0..kh {
+                for kj in 0..kw {
+                    let out_index = vec![b, g * c_out_g + co, i * stride.0 + ki, j * stride.1 + kj];
+                    let value = full[out_index.clone()]
+                        + input[index.clone()] * kernel[vec![ci, co, ki, kj]];
+                    full[out_index] = value;
+                }
+            }
+        }
+    }
+    let out_h = full_h - 2 * padding.0;
+    let out_w = full_w - 2 * padding.1;
+    let mut output = Array::new(0., vec![n, c_out_g * groups, out_h, out_w]);
+    for index in indices(&output.get_shape()) {
+        output[index.clone()] = full[vec![
+            index[0],
+            index[1],
+            index[2] + padding.0,
+            index[3] + padding.1,
+        ]];
+    }
+    output
+}
+
+#[test]
+fn test_conv2d_transpose_matches_naive() {
+    let mut rng = Rng::new(3);
+    let configs = vec![
+        ((1, 1), (0, 0), (0, 0), 1),
+        ((2, 2), (0, 0), (0, 0), 1),
+        ((2, 3), (1, 1), (1, 2), 1),
+        ((3, 2), (1, 0), (0, 1), 2),
+    ];
+    for (stride, padding, output_padding, groups) in configs {
+        let input = Array::random_uniform(vec![2, 4, 3, 4], -1., 1., &mut rng);
+        let kernel = Array::random_uniform(vec![4, 6 / groups, 3, 2], -1., 1., &mut rng);
+
+        assert_arrays_rel_eq!(
+            conv2d_transpose_array(
+                &input,
+                &kernel,
+                stride,
+                padding,
+                output_padding,
+                (1, 1),
+                groups,
+                DataFormat::Nchw
+            ),
+            naive_conv2d_transpose(&input, &kernel, stride, padding, output_padding, groups),
+            1e-10
+        );
+    }
+}
+
+#[test]
+fn test_conv2d_transpose_is_adjoint_of_conv2d() {
+    let mut rng = Rng::new(4);
+    let x = Array::random_uniform(vec![1, 2, 4, 3], -1., 1., &mut rng);
+    let y = Array::random_uniform(vec![1, 3, 10, 7], -1., 1., &mut rng);
+    let kernel = Array::random_uniform(vec![2, 3, 3, 2], -1., 1., &mut rng);
+
+    let lhs = weighted_sum(
+        &conv2d_transpose_array(
+            &x,
+            &kernel,
+            (2, 2),
+            (1, 0),
+            (1, 1),
+            (2, 1),
+            1,
+            DataFormat::Nchw,
+        ),
+        &y,
+    );
+    let rhs = weighted_sum(
+        &conv2d_array(&y, &kernel, (2, 2), (1, 0), (2, 1), 1, DataFormat::Nchw),
+        &x,
+    );
+
+    assert!((lhs - rhs).abs() < 1e-10);
+}
+
+#[test]
+#[should_panic]
+fn test_conv2d_transpose_output_padding_too_big() {
+    let input = Array::new(1., vec![1, 1, 2, 2]);
+    let kernel = Array::new(1., vec![1, 1, 2, 2]);
+
+    conv2d_transpose_array(
+        &input,
+        &kernel,
+        (2, 2),
+        (0, 0),
+        (2, 0),
+        (1, 1),
+        1,
+        DataFormat::Nchw,
+    );
+}
+
+#[test]
+fn test_conv2d_transpose_gradients() {
+    let mut rng = Rng::new(5);
+    let configs = vec![
+        ((2, 2), (0, 0), (1, 0), 1, DataFormat::Nchw),
+        ((1, 2), (1, 1), (0, 1), 2, DataFormat::Nhwc),
+    ];
+    for (stride, padding, output_padding, groups, data_format) in configs {
+        let input_shape = match data_format {
+            DataFormat::Nchw => vec![2, 2, 3, 4],
+            DataFormat::Nhwc => vec![2, 3, 4, 2],
+        };
+        let input_array = Array::random_uniform(input_shape, -1., 1., &mut rng);
+        let kernel_array = Array::random_uniform(vec![2, 4 / groups, 3, 3], -1., 1., &mut rng);
+        let input = Tensor::new_variable(input_array.clone());
+        let kernel = Tensor::new_variable(kernel_array.clone());
+        let output = conv2d_transpose(
+            &input,
+            &kernel,
+            stride,
+            padding,
+            output_padding,
+            (1, 1),
+            groups,
+            data_format,
+        );
+        let weights = Array::random_uniform(output.shape(), -1., 1., &mut rng);
+        let loss = &output * &Tensor::new_variable(weights.clone());
+        let compute = |x: &Array<f64>, k: &Array<f64>| {
+            weighted_sum(
+                &conv2d_transpose_array(
+                    x,
+                    k,
+                    stride,
+                    padding,
+                    output_padding,
+                    (1, 1),
+                    groups,
+                    data_format,
+                ),
+                &weights,
+            )
+        };
+
+        assert_eq!(output.eval(None).get_shape(), output.shape());
+        assert_arrays_rel_eq!(
+            loss.grad(&input, None).unwrap(),
+            numerical_grad(|x| compute(x, &kernel_array), &input_array),
+            1e-6
+        );
+        assert_arrays_rel_eq!(
+            loss.grad(&kernel, None).unwrap(),
+            numerical_grad(|k| compute(&input_array, k), &kernel_array),
+            1e-6
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use neurust::io::{CsvColumns, CsvOptions, Error, MissingValues};
+use neurust::linalg::Rng;
+use neurust::Array;
+
+#[test]
+fn test_csv_read() {
+    let csv = "# measurements\na;b;c\n1;2.5;-3\n\n4;5e-1;6\n";
+    let options = CsvOptions {
+        delimiter: ';',
+        skip_rows: 1,
+        ..CsvOptions::default()
+    };
+
+    assert_eq!(
+        Array::<f64>::from_csv(csv.as_bytes(), &options).unwrap(),
+        Array::from_vec(vec![1., 2.5, -3., 4., 0.5, 6.], vec![2, 3])
+    );
+}
+
+#[test]
+fn test_csv_read_without_header() {
+    let csv = "1\t2\n3\t4\n";
+    let options = CsvOptions {
+        delimiter: '\t',
+        has_header: false,
+        ..CsvOptions::default()
+    };
+
+    assert_eq!(
+        Array::<f32>::from_csv(csv.as_bytes(), &options).unwrap(),
+        Array::from_vec(vec![1., 2., 3., 4.], vec![2, 2])
+    );
+}
+
+#[test]
+fn test_csv_column_selection() {
+    let csv = "\"id\",\"x, cm\",y\n1,\"2\",3\n4,5,6\n";
+    let by_indices = CsvOptions {
+        columns: CsvColumns::Indices(vec![2, 0]),
+        ..CsvOptions::default()
+    };
+    let by_names = CsvOptions {
+        columns: CsvColumns::Names(vec!["x, cm".to_owned()]),
+        ..CsvOptions::default()
+    };
+
+    assert_eq!(
+        Array::<f64>::from_csv(csv.as_bytes(), &by_indices).unwrap(),
+        Array::from_vec(vec![3., 1., 6., 4.], vec![2, 2])
+    );
+    assert_eq!(
+        Array::<f64>::from_csv(csv.as_bytes(), &by_names).unwrap(),
+        Array::from_vec(vec![2., 5.], vec![2, 1])
+    );
+}
+
+#[test]
+fn test_csv_missing_values() {
+    let csv = "a,b\n1,NA\n2,3\n,4\n";
+    let read = |missing: MissingValues<f64>| {
+        Array::<f64>::from_csv(
+            csv.as_bytes(),
+            &CsvOptions {
+                missing,
+                ..CsvOptions::default()
+            },
+        )
+    };
+
+    assert_eq!(
+        read(MissingValues::Fill(0.)).unwrap(),
+        Array::from_vec(vec![1., 0., 2., 3., 0., 4.], vec![3, 2])
+    );
+    assert_eq!(
+        read(MissingValues::SkipRow).unwrap(),
+        Array::from_vec(vec![2., 3.], vec![1, 2])
+    );
+    assert!(read(MissingValues::Fill(f64::NAN)).unwrap()[vec![0, 1]].is_nan());
+    assert!(matches!(read(MissingValues::Error), Err(Error::Format(_))));
+}
+
+#[test]
+fn test_csv_missing_values_only_in_selected_columns() {
+    let csv = "a,b\n1,\n2,3\n";
+    let options = CsvOptions {
+        columns: CsvColumns::Indices(vec![0]),
+        ..CsvOptions::default()
+    };
+
+    assert_eq!(
+        Array::<f64>::from_csv(csv.as_bytes(), &options).unwrap(),
+        Array::from_vec(vec![1., 2.], vec![2, 1])
+    );
+}
+
+#[test]
+fn test_csv_invalid_data() {
+    let read = |csv: &str, columns: CsvColumns| {
+        Array::<f64>::from_csv(
+            csv.as_bytes(),
+            &CsvOptions {
+                columns,
+                ..CsvOptions::default()
+            },
+        )
+    };
+
+    assert!(matches!(
+        read("a,b\n1,x\n", CsvColumns::All),
+        Err(Error::Format(_))
+    ));
+    assert!(matches!(
+        read("a,b\n1,2\n3\n", CsvColumns::All),
+        Err(Error::Format(_))
+    ));
+    assert!(matches!(
+        read("a,b\n", CsvColumns::All),
+        Err(Error::Format(_))
+    ));
+    assert!(matches!(
+        read("a,b\n1,2\n", CsvColumns::Indices(vec![2])),
+        Err(Error::Format(_))
+    ));
+    assert!(matches!(
+        read("a,b\n1,\"2\n3\n", CsvColumns::All),
+        Err(Error::Format(message)) if message.contains("line 2")
+    ));
+    assert!(matches!(
+        read("a,b\n1,2\n", CsvColumns::Names(vec!["c".to_owned()])),
+        Err(Error::Missing(name)) if name == "c"
+    ));
+}
+
+#[test]
+fn test_csv_write_with_header() {
+    let a = Array::from_vec(vec![1.5f32, -2., 0.1, 3.], vec![2, 2]);
+    let mut buffer = Vec::new();
+    a.write_csv(&mut buffer, Some(&["x", "y;z"]), ';').unwrap();
+
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "x;\"y;z\"\n1.5;-2\n0.1;3\n"
+    );
+}
+
+#[test]
+fn test_csv_header_with_line_breaks_roundtrip() {
+    let a = Array::from_vec(vec![1., 2., 3., 4.], vec![2, 2]);
+    let mut buffer = Vec::new();
+    a.write_csv(
+        &mut buffer,
+        Some(&["first\nname", "say \"hi\"\n\nthere"]),
+        ',',
+    )
+    .unwrap();
+    let options = CsvOptions {
+        columns: CsvColumns::Names(vec![
+            "say \"hi\"\n\nthere".to_owned(),
+            "first\nname".to_owned(),
+        ]),
+        ..CsvOptions::default()
+    };
+
+    assert_eq!(
+        Array::<f64>::from_csv(buffer.as_slice(), &options).unwrap(),
+        Array::from_vec(vec![2., 1., 4., 3.], vec![2, 2])
+    );
+}
+
+#[test]
+fn test_csv_file_roundtrip() {
+    let path = std::env::temp_dir().join("neurust_test_csv_file_roundtrip.csv");
+    let a = Array::random_uniform(vec![5, 3], -1e3, 1e3, &mut Rng::new(0));
+    a.save_csv(&path, Some(&["a", "b", "c"])).unwrap();
+    let loaded = Array::<f64>::load_csv(&path, &CsvOptions::default()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, a);
+}
+
+#[test]
+#[should_panic]
+fn test_csv_write_invalid_shape() {
+    let a = Array::new(1., vec![2, 2, 2]);
+
+    a.to_csv(&mut Vec::new()).unwrap();
+}
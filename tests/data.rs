@@ -0,0 +1,212 @@
+use neurust::data::{ArrayDataset, DataLoader, Dataset};
+use neurust::linalg::Rng;
+use neurust::{Array, Tensor};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+// Dataset preparing examples slowly, with time depending on the index, and counting
+// prepared examples. Examples from `invalid_index` on can't be prepared.
+struct SlowDataset {
+    len: usize,
+    invalid_index: usize,
+    prepared: Arc<AtomicUsize>,
+}
+
+impl Dataset<f64> for SlowDataset {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get(&self, index: usize) -> Vec<Array<f64>> {
+        thread::sleep(Duration::from_millis((index % 3) as u64 * 2));
+        self.prepared.fetch_add(1, Ordering::SeqCst);
+        if index >= self.invalid_index {
+            panic!("Invalid example.")
+        }
+        vec![Array::new(index as f64, vec![1])]
+    }
+}
+
+fn dataset(len: usize) -> ArrayDataset<f64> {
+    let features = Array::from_vec((0..len * 2).map(|x| x as f64).collect(), vec![len, 2]);
+    let labels = Array::from_vec((0..len).map(|x| x as f64).collect(), vec![len]);
+    ArrayDataset::new(vec![features, labels])
+}
+
+// Returns labels, equal to indices of examples, of all batches of an epoch.
+fn epoch_labels<D: Dataset<f64>>(loader: &mut DataLoader<f64, D>) -> Vec<Vec<f64>> {
+    loader
+        .epoch()
+        .map(|batch| {
+            let labels = batch.get("y").unwrap();
+            (0..batch.len()).map(|i| labels[vec![i, 0]]).collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_batches_in_order() {
+    let mut loader = DataLoader::new(dataset(5), &["x", "y"], 2, None, false);
+    let batches: Vec<_> = loader.epoch().collect();
+
+    assert_eq!(loader.len(), 3);
+    assert_eq!(batches.len(), 3);
+    assert_eq!(
+        batches[1].get("x").unwrap(),
+        &Array::from_vec(vec![4., 5., 6., 7.], vec![2, 2])
+    );
+    assert_eq!(
+        batches[1].arrays()[1],
+        Array::from_vec(vec![2., 3.], vec![2, 1])
+    );
+    assert_eq!(
+        batches[2].get("x").unwrap(),
+        &Array::from_vec(vec![8., 9.], vec![1, 2])
+    );
+    assert!(batches[0].get("z").is_none());
+}
+
+#[test]
+fn test_drop_last() {
+    let mut loader = DataLoader::new(dataset(5), &["x", "y"], 2, Some(0), true);
+    let small_loader = DataLoader::new(dataset(3), &["x", "y"], 4, None, true);
+
+    assert_eq!(loader.len(), 2);
+    assert!(epoch_labels(&mut loader)
+        .iter()
+        .all(|batch| batch.len() == 2));
+    assert!(small_loader.is_empty());
+}
+
+#[test]
+fn test_shuffling() {
+    let mut loader = DataLoader::new(dataset(20), &["x", "y"], 6, Some(7), false);
+    let mut other_loader = DataLoader::new(dataset(20), &["x", "y"], 6, Some(7), false);
+    let first_epoch = epoch_labels(&mut loader);
+    let second_epoch = epoch_labels(&mut loader);
+
+    let mut labels: Vec<f64> = first_epoch.concat();
+    labels.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    // every epoch contains all examples in a different order
+    assert_eq!(labels, (0..20).map(|x| x as f64).collect::<Vec<f64>>());
+    assert_ne!(first_epoch, second_epoch);
+    assert_ne!(first_epoch.concat(), labels);
+    // orders are reproducible
+    assert_eq!(epoch_labels(&mut other_loader), first_epoch);
+    other_loader.set_epoch(1);
+    assert_eq!(epoch_labels(&mut other_loader), second_epoch);
+    assert_eq!(other_loader.current_epoch(), 2);
+}
+
+#[test]
+fn test_shuffling_with_neighbouring_seeds() {
+    let mut loader = DataLoader::new(dataset(20), &["x", "y"], 6, Some(7), false);
+    let mut other_loader = DataLoader::new(dataset(20), &["x", "y"], 6, Some(8), false);
+    loader.set_epoch(1);
+
+    assert_ne!(epoch_labels(&mut loader), epoch_labels(&mut other_loader));
+}
+
+#[test]
+fn test_examples_stay_aligned() {
+    let mut loader = DataLoader::new(dataset(9), &["x", "y"], 4, Some(1), false);
+    for batch in loader.epoch() {
+        let features = batch.get("x").unwrap();
+        let labels = batch.get("y").unwrap();
+        for i in 0..batch.len() {
+            assert_eq!(features[vec![i, 0]], 2. * labels[vec![i, 0]]);
+        }
+    }
+}
+
+#[test]
+fn test_feed_dict() {
+    let mut loader = DataLoader::new(dataset(4), &["x", "y"], 4, None, false);
+    let x = Tensor::new_placeholder("x".to_owned(), vec![4, 2]);
+    let y = Tensor::new_placeholder("y".to_owned(), vec![4, 1]);
+    let weights = Tensor::new_variable(Array::from_vec(vec![1., -1.], vec![2, 1]));
+    let output = &x.matmul(&weights) + &y;
+    let batch = loader.epoch().next().unwrap();
+
+    assert_eq!(
+        output.eval(Some(&batch.feed_dict())),
+        Array::from_vec(vec![-1., 0., 1., 2.], vec![4, 1])
+    );
+}
+
+#[test]
+fn test_list_of_examples_dataset() {
+    let mut rng = Rng::new(0);
+    let examples: Vec<Vec<Array<f64>>> = (0..3)
+        .map(|_| vec![Array::random_uniform(vec![2, 2], 0., 1., &mut rng)])
+        .collect();
+    let mut loader = DataLoader::new(&examples, &["image"], 3, None, false);
+    let batch = loader.epoch().next().unwrap();
+
+    assert_eq!(batch.arrays()[0].get_shape(), vec![3, 2, 2]);
+    assert_eq!(batch.arrays()[0].select(0, 2), examples[2][0]);
+}
+
+#[test]
+fn test_prefetched_batches_match_epoch() {
+    let mut loader = DataLoader::new(dataset(23), &["x", "y"], 4, Some(3), false);
+    let mut other_loader = DataLoader::new(dataset(23), &["x", "y"], 4, Some(3), false);
+    for workers in 1..5 {
+        let batches: Vec<_> = loader.epoch().collect();
+        let prefetched = other_loader.prefetch_epoch(workers, 2);
+
+        assert_eq!(prefetched.len(), 6);
+        assert_eq!(prefetched.collect::<Vec<_>>(), batches);
+    }
+}
+
+#[test]
+fn test_prefetching_is_bounded_and_stops_early() {
+    let prepared = Arc::new(AtomicUsize::new(0));
+    let dataset = SlowDataset {
+        len: 100,
+        invalid_index: 100,
+        prepared: Arc::clone(&prepared),
+    };
+    let mut loader = DataLoader::new(dataset, &["x"], 2, Some(0), false);
+    let mut batches = loader.prefetch_epoch(3, 2);
+    let first = batches.next().unwrap();
+    thread::sleep(Duration::from_millis(50));
+
+    // besides the returned batch, each of 3 workers has at most 2 queued batches, 1 batch
+    // waiting to be queued and 1 batch being prepared
+    assert_eq!(first.len(), 2);
+    assert!(prepared.load(Ordering::SeqCst) <= 2 * (1 + 3 * 4));
+    drop(batches);
+    let prepared_count = prepared.load(Ordering::SeqCst);
+    thread::sleep(Duration::from_millis(20));
+    assert_eq!(prepared.load(Ordering::SeqCst), prepared_count);
+}
+
+#[test]
+#[should_panic(expected = "Invalid example.")]
+fn test_prefetching_propagates_worker_panics() {
+    let dataset = SlowDataset {
+        len: 12,
+        invalid_index: 10,
+        prepared: Arc::new(AtomicUsize::new(0)),
+    };
+    let mut loader = DataLoader::new(dataset, &["x"], 2, None, false);
+
+    for _ in loader.prefetch_epoch(2, 1) {}
+}
+
+#[test]
+#[should_panic]
+fn test_array_dataset_invalid_shapes() {
+    ArrayDataset::new(vec![Array::new(0., vec![3, 2]), Array::new(0., vec![2])]);
+}
+
+#[test]
+#[should_panic]
+fn test_data_loader_invalid_names() {
+    DataLoader::new(dataset(3), &["x"], 2, None, false);
+}
@@ -0,0 +1,94 @@
+use neurust::linalg::{reduce_sum, Rng};
+use neurust::nn::initializers::Initializer;
+use neurust::nn::layers::{Dense, Dropout};
+use neurust::nn::{Module, Sequential};
+use neurust::tensor::dropout::dropout;
+use neurust::tensor::TrainingFlag;
+use neurust::{Array, Tensor};
+
+#[test]
+fn test_dropout_rate_and_scaling() {
+    let input = Tensor::new_variable(Array::new(1., vec![100, 100]));
+    let output = dropout(&input, 0.3, &TrainingFlag::new(true), &mut Rng::new(0));
+
+    let value = output.eval(None);
+    let kept = value.map(|x| if x > 0. { 1. } else { 0. });
+    let kept_fraction: f64 = reduce_sum(&kept, None, false)[vec![0]] / 1e4;
+
+    assert_eq!(
+        value.map(|x| if x == 0. || x == 1. / 0.7 { 0. } else { 1. }),
+        Array::new(0., vec![100, 100])
+    );
+    assert!((kept_fraction - 0.7).abs() < 0.02);
+}
+
+#[test]
+fn test_dropout_inference_is_identity() {
+    let input_array = Array::from_vec(vec![1., -2., 3., 4.], vec![2, 2]);
+    let input = Tensor::new_variable(input_array.clone());
+    let output = dropout(&input, 0.9, &TrainingFlag::new(false), &mut Rng::new(0));
+
+    assert_eq!(output.eval(None), input_array);
+    assert_eq!(
+        output.grad(&input, None).unwrap(),
+        Array::new(1., vec![2, 2])
+    );
+}
+
+#[test]
+fn test_dropout_samples_mask_per_evaluation() {
+    let input = Tensor::new_variable(Array::new(1., vec![10, 10]));
+    let output = dropout(&input, 0.5, &TrainingFlag::new(true), &mut Rng::new(0));
+
+    assert_ne!(output.eval(None), output.eval(None));
+}
+
+#[test]
+fn test_dropout_is_deterministic() {
+    let input = Tensor::new_variable(Array::new(1., vec![10, 10]));
+    let training = TrainingFlag::new(true);
+    let output1 = dropout(&input, 0.5, &training, &mut Rng::new(7));
+    let output2 = dropout(&input, 0.5, &training, &mut Rng::new(7));
+
+    for _ in 0..3 {
+        assert_eq!(output1.eval(None), output2.eval(None));
+    }
+}
+
+#[test]
+fn test_dropout_mask_shared_by_value_and_gradient() {
+    let input = Tensor::new_variable(Array::new(1., vec![5, 6]));
+    let weights = Tensor::new_variable(Array::random_uniform(vec![5, 6], 1., 2., &mut Rng::new(1)));
+    let dropped = dropout(&input, 0.5, &TrainingFlag::new(true), &mut Rng::new(3));
+    let output = &dropped * &weights;
+
+    let (value, grads) = output.eval_and_grads(&[&input, &weights], None);
+
+    // for input of ones value and gradient w.r.t. input are equal to mask * weights
+    // and gradient w.r.t. weights is equal to mask
+    assert_eq!(grads[0], Some(value.clone()));
+    assert_eq!(grads[1].as_ref().unwrap().mul(&weights.eval(None)), value);
+}
+
+#[test]
+#[should_panic]
+fn test_dropout_invalid_rate() {
+    let input = Tensor::new_variable(Array::new(1., vec![2, 2]));
+
+    dropout(&input, 1., &TrainingFlag::new(true), &mut Rng::new(0));
+}
+
+#[test]
+fn test_dropout_layer_in_sequential() {
+    let mut rng = Rng::new(0);
+    let mut model = Sequential::new();
+    model.add(Dense::new(4, 3, None, Initializer::Ones, false, &mut rng));
+    model.add(Dropout::new(0.5, &mut rng));
+    let input = Tensor::new_variable(Array::new(1., vec![2, 4]));
+    let output = model.forward(&input);
+
+    model.set_training(false);
+
+    assert_eq!(output.eval(None), Array::new(4., vec![2, 3]));
+    assert_eq!(model.parameters().len(), 1);
+}
@@ -0,0 +1,109 @@
+use neurust::linalg::Rng;
+use neurust::tensor::embedding::embedding;
+use neurust::tensor::{IndexedSlices, Indices};
+use neurust::{Array, Tensor};
+
+#[test]
+fn test_embedding_shape_and_values() {
+    let table_array = Array::random_uniform(vec![5, 3], -1., 1., &mut Rng::new(0));
+    let table = Tensor::new_variable(table_array.clone());
+    let indices = Indices::new(vec![4, 0, 4, 2], vec![2, 2]);
+
+    let output = embedding(&table, &indices);
+    let value = output.eval(None);
+
+    assert_eq!(output.shape(), vec![2, 2, 3]);
+    for (position, &row) in [4, 0, 4, 2].iter().enumerate() {
+        for j in 0..3 {
+            assert_eq!(
+                value[vec![position / 2, position % 2, j]],
+                table_array[vec![row, j]]
+            );
+        }
+    }
+}
+
+#[test]
+fn test_embedding_dense_gradient() {
+    let table = Tensor::new_variable(Array::new(0., vec![4, 2]));
+    let indices = Indices::new(vec![1, 3, 1], vec![3]);
+    let weights = Tensor::new_variable(Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![3, 2]));
+    let loss = &embedding(&table, &indices) * &weights;
+
+    assert_eq!(
+        loss.grad(&table, None).unwrap(),
+        Array::from_vec(vec![0., 0., 6., 8., 0., 0., 3., 4.], vec![4, 2])
+    );
+}
+
+#[test]
+fn test_embedding_sparse_gradient_matches_dense() {
+    let table = Tensor::new_variable(Array::new(0.5, vec![6, 3]));
+    let indices = Indices::new(vec![5, 1, 1, 0], vec![2, 2]);
+    let lookup = embedding(&table, &indices);
+    let weights = Array::random_uniform(vec![2, 2, 3], -1., 1., &mut Rng::new(1));
+    let loss = &lookup * &Tensor::new_variable(weights);
+
+    let sparse = loss.sparse_grad(&lookup, None).unwrap();
+
+    assert_eq!(sparse.indices(), &[5, 1, 1, 0]);
+    assert_eq!(sparse.values().get_shape(), vec![4, 3]);
+    assert_eq!(sparse.to_dense(), loss.grad(&table, None).unwrap());
+    assert_eq!(sparse.coalesce().to_dense(), sparse.to_dense());
+    assert_eq!(sparse.coalesce().indices(), &[0, 1, 5]);
+}
+
+#[test]
+fn test_embedding_sparse_update() {
+    let table = Tensor::new_variable(Array::new(1., vec![4, 2]));
+    let indices = Indices::new(vec![2, 2], vec![2]);
+    let lookup = embedding(&table, &indices);
+
+    let grad = lookup.sparse_grad(&lookup, None).unwrap();
+    table.assign_add_sparse(&grad.map(|x| -0.25 * x));
+
+    assert_eq!(
+        table.eval(None),
+        Array::from_vec(vec![1., 1., 1., 1., 0.5, 0.5, 1., 1.], vec![4, 2])
+    );
+}
+
+#[test]
+fn test_embedding_reads_assigned_indices() {
+    let table = Tensor::new_variable(Array::from_vec(vec![0., 1., 2.], vec![3, 1]));
+    let indices = Indices::new(vec![0, 1], vec![2]);
+    let output = embedding(&table, &indices);
+
+    indices.assign(vec![2, 2]);
+
+    assert_eq!(output.eval(None), Array::new(2., vec![2, 1]));
+}
+
+#[test]
+#[should_panic]
+fn test_embedding_index_out_of_bounds() {
+    let table = Tensor::new_variable(Array::new(1., vec![3, 2]));
+
+    embedding(&table, &Indices::new(vec![3], vec![1])).eval(None);
+}
+
+#[test]
+#[should_panic]
+fn test_indices_assign_wrong_length() {
+    Indices::new(vec![0, 1], vec![2]).assign(vec![0]);
+}
+
+#[test]
+#[should_panic]
+fn test_sparse_grad_requires_lookup() {
+    let table = Tensor::new_variable(Array::new(1., vec![3, 2]));
+    let loss = &table * 2.;
+
+    loss.sparse_grad(&table, None);
+}
+
+#[test]
+#[should_panic]
+fn test_indexed_slices_wrong_values_shape() {
+    IndexedSlices::new(vec![0, 1], Array::new(1., vec![2, 3]), vec![4, 2]);
+}
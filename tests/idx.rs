@@ -0,0 +1,142 @@
+use neurust::io::{
+    load_idx_images, load_idx_labels, read_idx_images, read_idx_labels, Error, LabelEncoding,
+};
+use neurust::Array;
+
+// Creates IDX data with a given data type code, shape and raw big-endian data.
+fn idx_bytes(type_code: u8, shape: &[u32], data: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![0, 0, type_code, shape.len() as u8];
+    for dim in shape {
+        bytes.extend_from_slice(&dim.to_be_bytes());
+    }
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+#[test]
+fn test_idx_read_data_types() {
+    let unsigned = idx_bytes(0x08, &[2, 2], &[0, 1, 128, 255]);
+    let signed = idx_bytes(0x09, &[3], &[0, 1, 255]);
+    let mut shorts = Vec::new();
+    for x in [-2i16, 300].iter() {
+        shorts.extend_from_slice(&x.to_be_bytes());
+    }
+    let mut ints = Vec::new();
+    for x in [-70000i32, 5].iter() {
+        ints.extend_from_slice(&x.to_be_bytes());
+    }
+    let mut floats = Vec::new();
+    for x in [0.5f32, -1.25].iter() {
+        floats.extend_from_slice(&x.to_be_bytes());
+    }
+    let doubles = 0.1f64.to_be_bytes();
+
+    assert_eq!(
+        Array::<f64>::read_idx(unsigned.as_slice()).unwrap(),
+        Array::from_vec(vec![0., 1., 128., 255.], vec![2, 2])
+    );
+    assert_eq!(
+        Array::<f64>::read_idx(signed.as_slice()).unwrap(),
+        Array::from_vec(vec![0., 1., -1.], vec![3])
+    );
+    assert_eq!(
+        Array::<f64>::read_idx(idx_bytes(0x0b, &[2], &shorts).as_slice()).unwrap(),
+        Array::from_vec(vec![-2., 300.], vec![2])
+    );
+    assert_eq!(
+        Array::<f64>::read_idx(idx_bytes(0x0c, &[1, 2], &ints).as_slice()).unwrap(),
+        Array::from_vec(vec![-70000., 5.], vec![1, 2])
+    );
+    assert_eq!(
+        Array::<f32>::read_idx(idx_bytes(0x0d, &[2], &floats).as_slice()).unwrap(),
+        Array::from_vec(vec![0.5, -1.25], vec![2])
+    );
+    assert_eq!(
+        Array::<f64>::read_idx(idx_bytes(0x0e, &[], &doubles).as_slice()).unwrap(),
+        Array::from_vec(vec![0.1], vec![1])
+    );
+}
+
+#[test]
+fn test_idx_images_and_labels() {
+    let images = idx_bytes(0x08, &[2, 1, 3], &[0, 255, 51, 102, 153, 204]);
+    let labels = idx_bytes(0x08, &[2], &[1, 0]);
+
+    assert_eq!(
+        read_idx_images::<f32>(images.as_slice()).unwrap(),
+        Array::from_vec(vec![0., 1., 0.2, 0.4, 0.6, 0.8], vec![2, 1, 1, 3])
+    );
+    assert_eq!(
+        read_idx_labels::<f64>(labels.as_slice(), LabelEncoding::Integer).unwrap(),
+        Array::from_vec(vec![1., 0.], vec![2])
+    );
+    assert_eq!(
+        read_idx_labels::<f64>(labels.as_slice(), LabelEncoding::OneHot(2)).unwrap(),
+        Array::from_vec(vec![0., 1., 1., 0.], vec![2, 2])
+    );
+}
+
+#[test]
+fn test_idx_load_gzipped_files() {
+    // created with Python's gzip module, images of shape [3, 4, 5] are equal to
+    // `(i * 37) % 256` and labels are 7, 0, 9
+    let images = load_idx_images::<f64>("tests/data/images-idx3-ubyte.gz").unwrap();
+    let labels =
+        load_idx_labels::<f64>("tests/data/labels-idx1-ubyte.gz", LabelEncoding::OneHot(10))
+            .unwrap();
+    let raw_labels = Array::<f64>::load_idx("tests/data/labels-idx1-ubyte.gz").unwrap();
+
+    assert_eq!(
+        images,
+        Array::from_vec(
+            (0..60).map(|i| ((i * 37) % 256) as f64 / 255.).collect(),
+            vec![3, 1, 4, 5]
+        )
+    );
+    assert_eq!(labels.get_shape(), vec![3, 10]);
+    assert_eq!(labels[vec![0, 7]], 1.);
+    assert_eq!(labels[vec![1, 0]], 1.);
+    assert_eq!(labels[vec![2, 9]], 1.);
+    assert_eq!(raw_labels, Array::from_vec(vec![7., 0., 9.], vec![3]));
+}
+
+#[test]
+fn test_idx_invalid_data() {
+    let is_format_error =
+        |result: Result<Array<f64>, Error>| matches!(result, Err(Error::Format(_)));
+
+    // invalid magic number
+    assert!(is_format_error(Array::read_idx(
+        &[1, 0, 8, 1, 0, 0, 0, 1, 0][..]
+    )));
+    // unknown data type
+    assert!(is_format_error(Array::read_idx(
+        idx_bytes(0x0a, &[1], &[0]).as_slice()
+    )));
+    // empty dimension
+    assert!(is_format_error(Array::read_idx(
+        idx_bytes(0x08, &[0], &[]).as_slice()
+    )));
+    // size overflowing usize
+    assert!(is_format_error(Array::read_idx(
+        idx_bytes(0x08, &[u32::MAX, u32::MAX, u32::MAX], &[]).as_slice()
+    )));
+    // images of invalid rank
+    assert!(is_format_error(read_idx_images(
+        idx_bytes(0x08, &[2, 2], &[0; 4]).as_slice()
+    )));
+    // labels of invalid data type
+    assert!(is_format_error(read_idx_labels(
+        idx_bytes(0x0d, &[1], &[0; 4]).as_slice(),
+        LabelEncoding::Integer
+    )));
+    // label out of range of one-hot classes
+    assert!(is_format_error(read_idx_labels(
+        idx_bytes(0x08, &[2], &[1, 3]).as_slice(),
+        LabelEncoding::OneHot(3)
+    )));
+    // truncated data
+    assert!(is_format_error(Array::read_idx(
+        idx_bytes(0x08, &[4], &[1, 2]).as_slice()
+    )));
+}
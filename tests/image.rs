@@ -0,0 +1,193 @@
+use neurust::io::{Error, ImageFormat};
+use neurust::linalg::{DataFormat, Rng};
+use neurust::Array;
+
+// Creates a 24-bit BMP of size 2x2 with rows stored bottom-up for positive `height`.
+fn bmp_bytes(height: i32) -> Vec<u8> {
+    let mut bytes = b"BM".to_vec();
+    bytes.extend_from_slice(&70u32.to_le_bytes());
+    bytes.extend_from_slice(&0u32.to_le_bytes());
+    bytes.extend_from_slice(&54u32.to_le_bytes());
+    bytes.extend_from_slice(&40u32.to_le_bytes());
+    bytes.extend_from_slice(&2i32.to_le_bytes());
+    bytes.extend_from_slice(&height.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&24u16.to_le_bytes());
+    bytes.extend_from_slice(&[0; 24]);
+    // blue, green, red pixels with rows padded to 4 bytes
+    bytes.extend_from_slice(&[0, 0, 255, 0, 255, 0, 0, 0]);
+    bytes.extend_from_slice(&[255, 0, 0, 255, 255, 255, 0, 0]);
+    bytes
+}
+
+#[test]
+fn test_read_bmp() {
+    // the first stored row is the bottom one
+    let bottom_up = Array::<f64>::read_image(bmp_bytes(2).as_slice(), DataFormat::Nhwc).unwrap();
+    let top_down = Array::<f64>::read_image(bmp_bytes(-2).as_slice(), DataFormat::Nchw).unwrap();
+
+    assert_eq!(
+        bottom_up,
+        Array::from_vec(
+            vec![0., 0., 1., 1., 1., 1., 1., 0., 0., 0., 1., 0.],
+            vec![2, 2, 3]
+        )
+    );
+    assert_eq!(
+        top_down,
+        Array::from_vec(
+            vec![1., 0., 0., 1., 0., 1., 0., 1., 0., 0., 1., 1.],
+            vec![3, 2, 2]
+        )
+    );
+}
+
+#[test]
+fn test_read_pnm() {
+    let ascii_ppm = "P3 # comment\n2 1 255\n255 0 0\n0 0 51\n";
+    let mut binary_pgm = b"P5\n2 1\n65535\n".to_vec();
+    binary_pgm.extend_from_slice(&[0xff, 0xff, 0x00, 0x00]);
+
+    assert_eq!(
+        Array::<f64>::read_image(ascii_ppm.as_bytes(), DataFormat::Nchw).unwrap(),
+        Array::from_vec(vec![1., 0., 0., 0., 0., 0.2], vec![3, 1, 2])
+    );
+    assert_eq!(
+        Array::<f32>::read_image(binary_pgm.as_slice(), DataFormat::Nhwc).unwrap(),
+        Array::from_vec(vec![1., 0.], vec![1, 2, 1])
+    );
+}
+
+#[test]
+fn test_image_roundtrip() {
+    let mut rng = Rng::new(0);
+    let quantize = |array: &Array<f64>| array.map(|x| (x * 255.).round() / 255.);
+    for &image_format in [
+        ImageFormat::PnmAscii,
+        ImageFormat::PnmBinary,
+        ImageFormat::Bmp,
+    ]
+    .iter()
+    {
+        for (data_format, shape) in [
+            (DataFormat::Nchw, vec![3, 5, 3]),
+            (DataFormat::Nhwc, vec![2, 7, 3]),
+        ] {
+            let image = quantize(&Array::random_uniform(shape, 0., 1., &mut rng));
+            let mut buffer = Vec::new();
+            image
+                .write_image(&mut buffer, data_format, image_format)
+                .unwrap();
+
+            assert_eq!(
+                Array::read_image(buffer.as_slice(), data_format).unwrap(),
+                image
+            );
+        }
+    }
+}
+
+#[test]
+fn test_grayscale_image_formats() {
+    let image = Array::from_vec(vec![0., 0.2, 1., 0.6], vec![1, 2, 2]);
+    let mut pgm = Vec::new();
+    image
+        .write_image(&mut pgm, DataFormat::Nchw, ImageFormat::PnmAscii)
+        .unwrap();
+    let mut bmp = Vec::new();
+    image
+        .write_image(&mut bmp, DataFormat::Nchw, ImageFormat::Bmp)
+        .unwrap();
+
+    assert_eq!(
+        String::from_utf8(pgm).unwrap(),
+        "P2\n2 2\n255\n0 51\n255 153\n"
+    );
+    // BMP stores grayscale images as RGB
+    assert_eq!(
+        Array::<f64>::read_image(bmp.as_slice(), DataFormat::Nchw).unwrap(),
+        Array::from_vec(
+            vec![0., 0.2, 1., 0.6, 0., 0.2, 1., 0.6, 0., 0.2, 1., 0.6],
+            vec![3, 2, 2]
+        )
+    );
+}
+
+#[test]
+fn test_image_file_roundtrip() {
+    let image = Array::from_vec(vec![0., 0.2, 1., 0.6, 0.4, 0.8], vec![2, 1, 3]);
+    for extension in ["bmp", "PPM"].iter() {
+        let path = std::env::temp_dir().join(format!("neurust_test_image.{}", extension));
+        image.save_image(&path, DataFormat::Nhwc).unwrap();
+        let loaded = Array::load_image(&path, DataFormat::Nhwc).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, image);
+    }
+    assert!(matches!(
+        image.save_image("image.png", DataFormat::Nhwc),
+        Err(Error::Format(_))
+    ));
+}
+
+#[test]
+fn test_save_image_channels_must_match_extension() {
+    let gray = Array::new(0.2, vec![1, 2, 2]);
+    let color = Array::new(0.2, vec![3, 2, 2]);
+    let path = |extension: &str| {
+        std::env::temp_dir().join(format!("neurust_test_image_channels.{}", extension))
+    };
+
+    assert!(matches!(
+        color.save_image(path("pgm"), DataFormat::Nchw),
+        Err(Error::Format(_))
+    ));
+    assert!(matches!(
+        gray.save_image(path("PPM"), DataFormat::Nchw),
+        Err(Error::Format(_))
+    ));
+    assert!(!path("pgm").exists() && !path("PPM").exists());
+    for (image, extension) in [(&gray, "pgm"), (&gray, "pnm"), (&color, "pnm")].iter() {
+        image.save_image(path(extension), DataFormat::Nchw).unwrap();
+        let loaded = Array::load_image(path(extension), DataFormat::Nchw).unwrap();
+        std::fs::remove_file(path(extension)).unwrap();
+
+        assert_eq!(&loaded, *image);
+    }
+}
+
+#[test]
+fn test_read_invalid_images() {
+    let is_format_error = |data: &[u8]| {
+        matches!(
+            Array::<f64>::read_image(data, DataFormat::Nchw),
+            Err(Error::Format(_))
+        )
+    };
+    let mut compressed_bmp = bmp_bytes(2);
+    compressed_bmp[30] = 1;
+    let mut huge_bmp = bmp_bytes(i32::MIN);
+    huge_bmp[10..14].copy_from_slice(&u32::MAX.to_le_bytes());
+    huge_bmp[18..22].copy_from_slice(&i32::MAX.to_le_bytes());
+
+    assert!(is_format_error(b"\x89PNG"));
+    assert!(is_format_error(b"P1\n1 1\n1\n"));
+    assert!(is_format_error(b"P2\n2 1\n255\n1\n"));
+    assert!(is_format_error(b"P5\n2 1\n255\n\x01"));
+    assert!(is_format_error(b"P2\n0 1\n255\n"));
+    assert!(is_format_error(b"P5 99999999999 99999999999 255\n"));
+    assert!(is_format_error(b"P6 4294967296 1431655765 65535\n"));
+    assert!(is_format_error(&bmp_bytes(2)[..60]));
+    assert!(is_format_error(&compressed_bmp));
+    assert!(is_format_error(&huge_bmp));
+}
+
+#[test]
+#[should_panic]
+fn test_write_image_invalid_channels() {
+    let image = Array::new(0.5, vec![2, 2, 2]);
+
+    image
+        .write_image(&mut Vec::new(), DataFormat::Nhwc, ImageFormat::Bmp)
+        .unwrap();
+}
@@ -0,0 +1,127 @@
+mod common;
+
+use common::{indices, numerical_grad, weighted_sum};
+use neurust::linalg::utils::are_arrays_near_equal;
+use neurust::linalg::Rng;
+use neurust::tensor::indexing::{gather, index_select};
+use neurust::tensor::Indices;
+use neurust::{assert_arrays_rel_eq, Array, Tensor};
+
+#[test]
+fn test_gather_matches_definition() {
+    let mut rng = Rng::new(0);
+    let array = Array::random_uniform(vec![3, 4, 2], -1., 1., &mut rng);
+    let indices_shape = [2, 3, 2];
+    let index_data: Vec<usize> = (0..12).map(|_| rng.below(4)).collect();
+
+    let gathered = array.gather(1, &index_data, &indices_shape);
+
+    for (position, index) in indices(&indices_shape).into_iter().zip(index_data.iter()) {
+        assert_eq!(
+            gathered[position.clone()],
+            array[vec![position[0], *index, position[2]]]
+        );
+    }
+}
+
+#[test]
+fn test_scatter_add_is_adjoint_of_gather() {
+    let mut rng = Rng::new(1);
+    let array = Array::random_uniform(vec![4, 3], -1., 1., &mut rng);
+    let updates = Array::random_uniform(vec![5, 3], -1., 1., &mut rng);
+    let index_data: Vec<usize> = (0..15).map(|_| rng.below(4)).collect();
+
+    let gathered = array.gather(0, &index_data, &[5, 3]);
+    let scattered = Array::new(0., vec![4, 3]).scatter_add(0, &index_data, &updates);
+
+    assert!((weighted_sum(&gathered, &updates) - weighted_sum(&array, &scattered)).abs() < 1e-12);
+}
+
+#[test]
+fn test_index_select() {
+    let array = Array::from_vec((0..24).map(|x| x as f64).collect(), vec![2, 3, 4]);
+
+    let selected = array.index_select(2, &[3, 0]);
+
+    assert_eq!(selected.get_shape(), vec![2, 3, 2]);
+    for position in indices(&[2, 3, 2]) {
+        let source = [3, 0][position[2]];
+        assert_eq!(
+            selected[position.clone()],
+            array[vec![position[0], position[1], source]]
+        );
+    }
+}
+
+#[test]
+fn test_masked_select_and_fill() {
+    let array = Array::from_vec(vec![1., -2., 3., -4., 5., -6.], vec![3, 2]);
+    let mask = array.mask(|x| x < 0.);
+
+    assert_eq!(
+        array.masked_select(&mask),
+        Some(Array::from_vec(vec![-2., -4., -6.], vec![3]))
+    );
+    assert_eq!(
+        array.masked_fill(&mask, 0.),
+        Array::from_vec(vec![1., 0., 3., 0., 5., 0.], vec![3, 2])
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_gather_index_out_of_bounds() {
+    Array::new(1., vec![2, 3]).gather(1, &[3], &[1, 1]);
+}
+
+#[test]
+#[should_panic]
+fn test_gather_invalid_indices_shape() {
+    Array::new(1., vec![2, 3]).gather(1, &[0, 0, 0], &[3, 1]);
+}
+
+#[test]
+#[should_panic]
+fn test_masked_select_invalid_mask() {
+    Array::new(1., vec![2, 3]).masked_select(&[true; 5]);
+}
+
+#[test]
+fn test_gather_and_index_select_gradients() {
+    let mut rng = Rng::new(2);
+    let input_array = Array::random_uniform(vec![3, 4], -1., 1., &mut rng);
+    let input = Tensor::new_variable(input_array.clone());
+    let gather_indices = Indices::new(vec![1, 1, 3, 0, 2, 2], vec![3, 2]);
+    let select_indices = Indices::new(vec![2, 0, 2], vec![3]);
+    let gathered = gather(&input, 1, &gather_indices);
+    let selected = index_select(&input, 0, &select_indices);
+    let gather_weights = Array::random_uniform(vec![3, 2], -1., 1., &mut rng);
+    let select_weights = Array::random_uniform(vec![3, 4], -1., 1., &mut rng);
+
+    assert_arrays_rel_eq!(
+        (&gathered * &Tensor::new_variable(gather_weights.clone()))
+            .grad(&input, None)
+            .unwrap(),
+        numerical_grad(
+            |x| weighted_sum(
+                &x.gather(1, &gather_indices.get_data(), &[3, 2]),
+                &gather_weights
+            ),
+            &input_array
+        ),
+        1e-6
+    );
+    assert_arrays_rel_eq!(
+        (&selected * &Tensor::new_variable(select_weights.clone()))
+            .grad(&input, None)
+            .unwrap(),
+        numerical_grad(
+            |x| weighted_sum(
+                &x.index_select(0, &select_indices.get_data()),
+                &select_weights
+            ),
+            &input_array
+        ),
+        1e-6
+    );
+}
@@ -0,0 +1,455 @@
+use neurust::linalg::utils::are_arrays_near_equal;
+use neurust::linalg::{reduce_max, reduce_mean, reduce_sum as reduce_sum_array, Rng};
+use neurust::nn::clip::{clip_by_global_norm, clip_by_norm, clip_by_value};
+use neurust::nn::initializers::{
+    glorot_normal, glorot_uniform, he_uniform, normal, orthogonal, truncated_normal, uniform,
+    Initializer,
+};
+use neurust::nn::regularizers::{add_l1_penalty, add_l2_penalty, l2_penalty};
+use neurust::{assert_arrays_rel_eq, reduce_sum, Array, Tensor};
+use std::collections::HashMap;
+
+#[test]
+fn test_clip_by_value() {
+    let grad = Array::from_vec(vec![-5., -1., 0., 2., 7., 1.5], vec![2, 3]);
+
+    assert_eq!(
+        clip_by_value(&grad, -2., 1.5),
+        Array::from_vec(vec![-2., -1., 0., 1.5, 1.5, 1.5], vec![2, 3])
+    );
+}
+
+#[test]
+fn test_clip_by_norm() {
+    let grad = Array::from_vec(vec![0., 3., 0., 4.], vec![2, 2]);
+
+    assert_arrays_rel_eq!(
+        clip_by_norm(&grad, 2.5),
+        Array::from_vec(vec![0., 1.5, 0., 2.], vec![2, 2]),
+        1e-7
+    );
+}
+
+#[test]
+fn test_clip_by_global_norm() {
+    let grads = vec![
+        Array::from_vec(vec![1., 1., 1., 1.], vec![2, 2]),
+        Array::from_vec(vec![2., 2., 2., 2., 2., 2., 2., 2.], vec![2, 2, 2]),
+    ];
+
+    let (clipped, global_norm) = clip_by_global_norm(&grads, 3.);
+
+    assert_eq!(global_norm, 6.);
+    assert_arrays_rel_eq!(clipped[0], Array::new(0.5, vec![2, 2]), 1e-7);
+    assert_arrays_rel_eq!(clipped[1], Array::new(1., vec![2, 2, 2]), 1e-7);
+}
+
+#[test]
+fn test_clip_gradients_of_tensor() {
+    let w = Tensor::new_variable(Array::new(2., vec![2, 2]));
+    let loss = reduce_sum(&(&w * 10.), None, false);
+
+    let clipped = clip_by_value(&loss.grad(&w, None).unwrap(), -1., 1.);
+
+    assert_eq!(clipped, Array::new(1., vec![2, 2]));
+}
+
+#[test]
+fn test_l1_penalty_multiple_variables() {
+    let w1 = Tensor::new_variable(Array::from_vec(vec![1., -2., 0.], vec![1, 3]));
+    let w2 = Tensor::new_variable(Array::from_vec(vec![-4., 4.], vec![2, 1]));
+    let x = Tensor::new_placeholder("x".to_owned(), vec![1, 3]);
+    let x_value = Array::new(1., vec![1, 3]);
+    let mut feed_dict = HashMap::new();
+    feed_dict.insert("x".to_owned(), &x_value);
+    let loss = reduce_sum(&(&x * &w1), None, false);
+
+    let regularized = add_l1_penalty(&loss, &[&w1, &w2], 0.25);
+
+    assert_eq!(
+        regularized.eval(Some(&feed_dict)),
+        Array::new(-1. + 0.25 * 11., vec![1])
+    );
+    assert_eq!(
+        regularized.grad(&w1, Some(&feed_dict)).unwrap(),
+        Array::from_vec(vec![1.25, 0.75, 1.], vec![1, 3])
+    );
+    assert_eq!(
+        regularized.grad(&w2, Some(&feed_dict)).unwrap(),
+        Array::from_vec(vec![-0.25, 0.25], vec![2, 1])
+    );
+}
+
+#[test]
+fn test_l2_penalty_multiple_variables() {
+    let w1 = Tensor::new_variable(Array::from_vec(vec![1., -2.], vec![2]));
+    let w2 = Tensor::new_variable(Array::from_vec(vec![3.], vec![1]));
+
+    let penalty = l2_penalty(&[&w1, &w2], 0.5);
+
+    assert_eq!(penalty.eval(None), Array::new(7., vec![1]));
+    assert_eq!(
+        penalty.grad(&w2, None).unwrap(),
+        Array::from_vec(vec![3.], vec![1])
+    );
+}
+
+#[test]
+fn test_add_l2_penalty_gradient() {
+    let w = Tensor::new_variable(Array::from_vec(vec![1., 2.], vec![1, 2]));
+    let loss = reduce_sum(&(&w * 3.), None, false);
+
+    let regularized = add_l2_penalty(&loss, &[&w], 0.5);
+
+    assert_eq!(
+        regularized.grad(&w, None).unwrap(),
+        Array::from_vec(vec![4., 5.], vec![1, 2])
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_l2_penalty_no_variables() {
+    l2_penalty::<f32>(&[], 0.5);
+}
+
+#[test]
+fn test_initializers_deterministic() {
+    let a = glorot_uniform::<f32>(vec![5, 4], 5, 4, &mut Rng::new(13));
+    let b = glorot_uniform::<f32>(vec![5, 4], 5, 4, &mut Rng::new(13));
+    let c = glorot_uniform::<f32>(vec![5, 4], 5, 4, &mut Rng::new(14));
+
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+}
+
+#[test]
+fn test_uniform_bounds() {
+    let arr = uniform::<f64>(vec![50, 50], 2., 3., &mut Rng::new(0));
+
+    let in_bounds = arr.map(|x| if (2. ..3.).contains(&x) { 1. } else { 0. });
+
+    assert_eq!(reduce_sum_array(&in_bounds, None, false).i(vec![0]), 2500.);
+    assert!((reduce_mean(&arr, None, false).i(vec![0]) - 2.5).abs() < 0.05);
+}
+
+#[test]
+fn test_normal_mean() {
+    let arr = normal::<f64>(vec![100, 100], 3., 1., &mut Rng::new(0));
+
+    assert!((reduce_mean(&arr, None, false).i(vec![0]) - 3.).abs() < 0.05);
+}
+
+#[test]
+fn test_truncated_normal_bounds() {
+    let arr = truncated_normal::<f64>(vec![100, 100], 1., 0.5, &mut Rng::new(0));
+
+    let in_bounds = arr.map(|x| if (0. ..=2.).contains(&x) { 1. } else { 0. });
+
+    assert_eq!(reduce_sum_array(&in_bounds, None, false).i(vec![0]), 10000.);
+}
+
+#[test]
+fn test_he_uniform_limit() {
+    let arr = he_uniform(vec![6, 100], 6, &mut Rng::new(0));
+
+    assert!(reduce_max(&arr.map(|x: f64| x.abs()), None, false).i(vec![0]) <= 1.);
+}
+
+#[test]
+fn test_glorot_normal_std() {
+    let arr = glorot_normal(vec![200, 200], 100, 300, &mut Rng::new(0));
+    let variance = reduce_mean(&arr.map(|x: f64| x * x), None, false).i(vec![0]);
+
+    assert!((variance - 2. / 400.).abs() < 2e-4);
+}
+
+#[test]
+fn test_orthogonal_non_square() {
+    let wide = orthogonal::<f64>(vec![2, 5], 2., &mut Rng::new(0));
+    let tall = orthogonal::<f64>(vec![6, 2], 1., &mut Rng::new(0));
+
+    let wide_error = wide
+        .matmul(&wide.transpose())
+        .sub(&Array::from_vec(vec![4., 0., 0., 4.], vec![2, 2]))
+        .map(|x| x.abs());
+    let tall_error = tall
+        .transpose()
+        .matmul(&tall)
+        .sub(&Array::from_vec(vec![1., 0., 0., 1.], vec![2, 2]))
+        .map(|x| x.abs());
+
+    assert!(reduce_max(&wide_error, None, false).i(vec![0]) < 1e-10);
+    assert!(reduce_max(&tall_error, None, false).i(vec![0]) < 1e-10);
+}
+
+#[test]
+#[should_panic(expected = "Orthogonal initialization requires at least 2 dimensions")]
+fn test_orthogonal_initializer_one_dimension() {
+    Initializer::<f64>::Orthogonal(1.).initialize(vec![4], &mut Rng::new(0));
+}
+
+#[test]
+fn test_initializer_enum() {
+    let mut rng = Rng::new(0);
+
+    assert_eq!(
+        Initializer::Constant(0.5).initialize(vec![2, 2], &mut rng),
+        Array::new(0.5, vec![2, 2])
+    );
+    assert_eq!(
+        Initializer::<f32>::Zeros.initialize(vec![3], &mut rng),
+        Array::new(0., vec![3])
+    );
+    assert_eq!(
+        Initializer::HeNormal.initialize(vec![3, 4, 2, 2], &mut Rng::new(1)),
+        neurust::nn::initializers::he_normal::<f64>(vec![3, 4, 2, 2], 16, &mut Rng::new(1))
+    );
+}
+
+mod test_dense {
+    use super::*;
+    use neurust::nn::layers::Dense;
+    use neurust::nn::Module;
+    use neurust::tensor::math::{pow, sigmoid};
+
+    #[test]
+    fn test_dense_forward() {
+        let dense = Dense::new(
+            2,
+            3,
+            None,
+            Initializer::Constant(0.5),
+            true,
+            &mut Rng::new(0),
+        );
+        dense
+            .bias()
+            .unwrap()
+            .assign(&Array::from_vec(vec![1., 2., 3.], vec![3]));
+        let input = Tensor::new_variable(Array::from_vec(vec![1., 1., 2., 4.], vec![2, 2]));
+
+        let output = dense.forward(&input);
+
+        assert_eq!(output.shape(), vec![2, 3]);
+        assert_eq!(
+            output.eval(None),
+            Array::from_vec(vec![2., 3., 4., 4., 5., 6.], vec![2, 3])
+        );
+    }
+
+    #[test]
+    fn test_dense_gradients() {
+        let dense = Dense::new(2, 1, None, Initializer::Ones, true, &mut Rng::new(0));
+        let input = Tensor::new_placeholder("x".to_owned(), vec![3, 2]);
+        let input_value = Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![3, 2]);
+        let mut feed_dict = HashMap::new();
+        feed_dict.insert("x".to_owned(), &input_value);
+        let loss = reduce_sum(&dense.forward(&input), None, false);
+
+        assert_eq!(
+            loss.grad(dense.kernel(), Some(&feed_dict)).unwrap(),
+            Array::from_vec(vec![9., 12.], vec![2, 1])
+        );
+        assert_eq!(
+            loss.grad(dense.bias().unwrap(), Some(&feed_dict)).unwrap(),
+            Array::from_vec(vec![3.], vec![1])
+        );
+    }
+
+    #[test]
+    fn test_dense_without_bias_and_with_activation() {
+        let dense = Dense::new(
+            2,
+            2,
+            Some(sigmoid),
+            Initializer::Zeros,
+            false,
+            &mut Rng::new(0),
+        );
+        let input = Tensor::new_variable(Array::new(1., vec![4, 2]));
+
+        assert!(dense.bias().is_none());
+        assert_eq!(dense.named_parameters().len(), 1);
+        assert_eq!(dense.named_parameters()[0].0, "kernel");
+        assert_eq!(
+            dense.forward(&input).eval(None),
+            Array::new(0.5, vec![4, 2])
+        );
+    }
+
+    #[test]
+    fn test_dense_batched_input() {
+        let dense = Dense::new(3, 2, None, Initializer::Ones, true, &mut Rng::new(0));
+        let input = Tensor::new_variable(Array::new(1., vec![4, 5, 3]));
+        let loss = reduce_sum(&dense.forward(&input), None, false);
+
+        assert_eq!(dense.forward(&input).shape(), vec![4, 5, 2]);
+        assert_eq!(
+            loss.grad(dense.kernel(), None).unwrap(),
+            Array::new(20., vec![3, 2])
+        );
+        assert_eq!(
+            loss.grad(dense.bias().unwrap(), None).unwrap(),
+            Array::new(20., vec![2])
+        );
+    }
+
+    #[test]
+    fn test_dense_training() {
+        let dense = Dense::new(
+            2,
+            1,
+            None,
+            Initializer::GlorotUniform,
+            true,
+            &mut Rng::new(0),
+        );
+        let x = Tensor::new_placeholder("x".to_owned(), vec![4, 2]);
+        let y = Tensor::new_placeholder("y".to_owned(), vec![4, 1]);
+        let x_value = Array::from_vec(vec![0., 0., 0., 1., 1., 0., 1., 1.], vec![4, 2]);
+        let y_value = Array::from_vec(vec![1., 3., -1., 1.], vec![4, 1]);
+        let mut feed_dict = HashMap::new();
+        feed_dict.insert("x".to_owned(), &x_value);
+        feed_dict.insert("y".to_owned(), &y_value);
+        let loss = reduce_sum(&pow(&(dense.forward(&x) + -&y), 2.), None, false);
+
+        for _ in 0..200 {
+            let grads: Vec<Array<f64>> = dense
+                .parameters()
+                .iter()
+                .map(|parameter| loss.grad(parameter, Some(&feed_dict)).unwrap())
+                .collect();
+            for (parameter, grad) in dense.parameters().iter().zip(grads.iter()) {
+                parameter.assign_add(&(grad * -0.1));
+            }
+        }
+
+        assert!(loss.eval(Some(&feed_dict)).i(vec![0]) < 1e-6);
+        assert_arrays_rel_eq!(
+            dense.kernel().eval(None).map(|x| (x * 1e3).round() / 1e3),
+            Array::from_vec(vec![-2., 2.], vec![2, 1]),
+            1e-7
+        );
+    }
+}
+
+mod test_sequential {
+    use super::*;
+    use neurust::nn::layers::{BatchNorm, Dense};
+    use neurust::nn::{Module, Sequential};
+    use neurust::tensor::math::relu;
+
+    fn build_model() -> Sequential<f64> {
+        let mut rng = Rng::new(0);
+        let mut model = Sequential::new();
+        model.add(Dense::new(
+            3,
+            4,
+            Some(relu),
+            Initializer::Constant(1.),
+            true,
+            &mut rng,
+        ));
+        model.add(Dense::new(
+            4,
+            2,
+            None,
+            Initializer::Constant(0.5),
+            false,
+            &mut rng,
+        ));
+        model
+    }
+
+    #[test]
+    fn test_sequential_forward() {
+        let model = build_model();
+        let input =
+            Tensor::new_variable(Array::from_vec(vec![1., 2., 3., -1., -2., -3.], vec![2, 3]));
+
+        assert_eq!(
+            model.forward(&input).eval(None),
+            Array::from_vec(vec![12., 12., 0., 0.], vec![2, 2])
+        );
+    }
+
+    #[test]
+    fn test_sequential_predict() {
+        let model = build_model();
+
+        assert_eq!(
+            model.predict(&Array::from_vec(vec![1., 2., 3.], vec![1, 3])),
+            Array::from_vec(vec![12., 12.], vec![1, 2])
+        );
+        assert_eq!(
+            model.predict(&Array::new(1., vec![5, 3])).get_shape(),
+            vec![5, 2]
+        );
+    }
+
+    #[test]
+    fn test_sequential_parameters() {
+        let model = build_model();
+        let names: Vec<String> = model
+            .named_parameters()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+
+        assert_eq!(model.parameters().len(), 3);
+        assert_eq!(model.layers().len(), 2);
+        assert_eq!(
+            names,
+            vec!["dense_0/kernel", "dense_0/bias", "dense_1/kernel"]
+        );
+    }
+
+    #[test]
+    fn test_sequential_gradients() {
+        let model = build_model();
+        let input = Tensor::new_variable(Array::from_vec(vec![1., 2., 3.], vec![1, 3]));
+        let loss = reduce_sum(&model.forward(&input), None, false);
+
+        assert_eq!(
+            loss.grad(model.parameters()[1], None).unwrap(),
+            Array::new(1., vec![4])
+        );
+    }
+
+    #[test]
+    fn test_sequential_summary() {
+        let summary = build_model().summary(vec![8, 3]);
+        let lines: Vec<&str> = summary.lines().collect();
+
+        assert_eq!(lines.len(), 6);
+        assert!(lines[2].starts_with("dense_0 (Dense)"));
+        assert!(lines[2].contains("[8, 4]"));
+        assert!(lines[2].ends_with("16"));
+        assert!(lines[3].starts_with("dense_1 (Dense)"));
+        assert!(lines[3].contains("[8, 2]"));
+        assert!(lines[3].ends_with("8"));
+        assert_eq!(lines[5], "Total params: 24");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sequential_empty_forward() {
+        Sequential::<f32>::new().predict(&Array::new(1., vec![1, 3]));
+    }
+
+    #[test]
+    fn test_sequential_set_training() {
+        let mut model = Sequential::new();
+        model.add(BatchNorm::new(3, 1, 0.5, 0.));
+        let input = Array::from_vec(vec![1., 2., 3., 3., 4., 5.], vec![2, 3]);
+
+        model.set_training(false);
+
+        assert_eq!(model.predict(&input), input);
+        assert_eq!(
+            model.named_parameters()[0].0,
+            "batchnorm_0/gamma".to_owned()
+        );
+    }
+}
@@ -0,0 +1,408 @@
+mod common;
+
+use common::{indices, numerical_grad, weighted_sum};
+use neurust::linalg::utils::are_arrays_near_equal;
+use neurust::linalg::Rng;
+use neurust::nn::layers::{BatchNorm, GroupNorm, LayerNorm};
+use neurust::nn::Module;
+use neurust::tensor::normalization::{batch_norm, group_norm, layer_norm};
+use neurust::tensor::TrainingFlag;
+use neurust::{assert_arrays_rel_eq, Array, Tensor};
+
+// Evaluates batch normalization of given arrays with fresh running statistics.
+fn eval_batch_norm(
+    input: &Array<f64>,
+    gamma: &Array<f64>,
+    beta: &Array<f64>,
+    training: bool,
+    axis: usize,
+) -> Array<f64> {
+    let channels = input.get_shape()[axis];
+    let running_mean = Tensor::new_variable(Array::new(0.5, vec![channels]));
+    let running_variance = Tensor::new_variable(Array::new(2., vec![channels]));
+    batch_norm(
+        &Tensor::new_variable(input.clone()),
+        &Tensor::new_variable(gamma.clone()),
+        &Tensor::new_variable(beta.clone()),
+        &running_mean,
+        &running_variance,
+        &TrainingFlag::new(training),
+        axis,
+        0.9,
+        1e-5,
+    )
+    .eval(None)
+}
+
+#[test]
+fn test_batch_norm_training_normalizes_channels() {
+    let mut rng = Rng::new(0);
+    let input = Array::random_normal(vec![4, 3, 2, 5], 3., 2., &mut rng);
+    let gamma = Array::new(1., vec![3]);
+    let beta = Array::new(0., vec![3]);
+
+    let output = eval_batch_norm(&input, &gamma, &beta, true, 1);
+
+    for c in 0..3 {
+        let values: Vec<f64> = indices(&[4, 2, 5])
+            .into_iter()
+            .map(|index| output[vec![index[0], c, index[1], index[2]]])
+            .collect();
+        let mean = values.iter().sum::<f64>() / 40.;
+        let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 40.;
+        assert!(mean.abs() < 1e-10);
+        assert!((variance - 1.).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn test_batch_norm_running_statistics() {
+    let input = Tensor::new_variable(Array::from_vec(vec![1., 10., 3., 30.], vec![2, 2]));
+    let batch_norm = BatchNorm::new(2, 1, 0.75, 0.);
+    let output = batch_norm.forward(&input);
+
+    output.eval(None);
+    output.grad(&input, None);
+
+    assert_eq!(
+        batch_norm.running_mean().eval(None),
+        Array::from_vec(vec![0., 0.], vec![2])
+    );
+
+    output.eval_and_grads(&[&input, batch_norm.gamma()], None);
+
+    assert_eq!(
+        batch_norm.running_mean().eval(None),
+        Array::from_vec(vec![0.5, 5.], vec![2])
+    );
+    assert_eq!(
+        batch_norm.running_variance().eval(None),
+        Array::from_vec(vec![1., 25.75], vec![2])
+    );
+
+    batch_norm.set_training(false);
+    let mean_before = batch_norm.running_mean().eval(None);
+
+    assert_arrays_rel_eq!(
+        output.eval(None),
+        Array::from_vec(
+            vec![0.5, 5. / 25.75f64.sqrt(), 2.5, 25. / 25.75f64.sqrt()],
+            vec![2, 2]
+        ),
+        1e-12
+    );
+    assert_eq!(batch_norm.running_mean().eval(None), mean_before);
+}
+
+#[test]
+fn test_batch_norm_inference_ignores_earlier_training_statistics() {
+    let input = Tensor::new_variable(Array::from_vec(vec![1., 10., 3., 30.], vec![2, 2]));
+    let batch_norm = BatchNorm::new(2, 1, 0.75, 0.);
+    let output = batch_norm.forward(&input);
+
+    output.eval(None);
+    batch_norm.set_training(false);
+    output.eval_and_grads(&[&input], None);
+
+    assert_eq!(
+        batch_norm.running_mean().eval(None),
+        Array::from_vec(vec![0., 0.], vec![2])
+    );
+    assert_eq!(
+        batch_norm.running_variance().eval(None),
+        Array::from_vec(vec![1., 1.], vec![2])
+    );
+}
+
+#[test]
+#[should_panic(expected = "BatchNorm momentum must be in range [0, 1]")]
+fn test_batch_norm_invalid_momentum() {
+    BatchNorm::new(2, 1, 1.5, 1e-5);
+}
+
+#[test]
+fn test_batch_norm_parameters() {
+    let batch_norm = BatchNorm::<f64>::new(3, 1, 0.9, 1e-5);
+
+    let names: Vec<String> = batch_norm
+        .named_parameters()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+
+    assert_eq!(names, vec!["gamma".to_owned(), "beta".to_owned()]);
+    assert_eq!(
+        batch_norm
+            .named_buffers()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<String>>(),
+        vec!["running_mean".to_owned(), "running_variance".to_owned()]
+    );
+    assert!(batch_norm.is_training());
+}
+
+#[test]
+#[should_panic]
+fn test_batch_norm_wrong_gamma_shape() {
+    let input = Tensor::new_variable(Array::new(1., vec![2, 3]));
+    let running = Tensor::new_variable(Array::new(1., vec![3]));
+
+    batch_norm(
+        &input,
+        &Tensor::new_variable(Array::new(1., vec![2])),
+        &Tensor::new_variable(Array::new(0., vec![3])),
+        &running,
+        &running,
+        &TrainingFlag::new(true),
+        1,
+        0.9,
+        1e-5,
+    );
+}
+
+#[test]
+fn test_batch_norm_gradients() {
+    let mut rng = Rng::new(1);
+    for (training, axis) in [(true, 1), (true, 2), (false, 1)] {
+        let input_array = Array::random_uniform(vec![3, 2, 4], -1., 1., &mut rng);
+        let channels = input_array.get_shape()[axis];
+        let gamma_array = Array::random_uniform(vec![channels], 0.5, 1.5, &mut rng);
+        let beta_array = Array::random_uniform(vec![channels], -1., 1., &mut rng);
+        let input = Tensor::new_variable(input_array.clone());
+        let gamma = Tensor::new_variable(gamma_array.clone());
+        let beta = Tensor::new_variable(beta_array.clone());
+        let output = batch_norm(
+            &input,
+            &gamma,
+            &beta,
+            &Tensor::new_variable(Array::new(0.5, vec![channels])),
+            &Tensor::new_variable(Array::new(2., vec![channels])),
+            &TrainingFlag::new(training),
+            axis,
+            0.9,
+            1e-5,
+        );
+        let weights = Array::random_uniform(output.shape(), -1., 1., &mut rng);
+        let loss = &output * &Tensor::new_variable(weights.clone());
+
+        assert_arrays_rel_eq!(
+            loss.grad(&input, None).unwrap(),
+            numerical_grad(
+                |x| weighted_sum(
+                    &eval_batch_norm(x, &gamma_array, &beta_array, training, axis),
+                    &weights
+                ),
+                &input_array
+            ),
+            1e-5
+        );
+        assert_arrays_rel_eq!(
+            loss.grad(&gamma, None).unwrap(),
+            numerical_grad(
+                |g| weighted_sum(
+                    &eval_batch_norm(&input_array, g, &beta_array, training, axis),
+                    &weights
+                ),
+                &gamma_array
+            ),
+            1e-5
+        );
+        assert_arrays_rel_eq!(
+            loss.grad(&beta, None).unwrap(),
+            numerical_grad(
+                |b| weighted_sum(
+                    &eval_batch_norm(&input_array, &gamma_array, b, training, axis),
+                    &weights
+                ),
+                &beta_array
+            ),
+            1e-5
+        );
+    }
+}
+
+// Evaluates layer normalization of given arrays.
+fn eval_layer_norm(
+    input: &Array<f64>,
+    gamma: &Array<f64>,
+    beta: &Array<f64>,
+    axes: &[usize],
+) -> Array<f64> {
+    layer_norm(
+        &Tensor::new_variable(input.clone()),
+        &Tensor::new_variable(gamma.clone()),
+        &Tensor::new_variable(beta.clone()),
+        axes,
+        1e-5,
+    )
+    .eval(None)
+}
+
+// Evaluates group normalization of given arrays.
+fn eval_group_norm(
+    input: &Array<f64>,
+    gamma: &Array<f64>,
+    beta: &Array<f64>,
+    num_groups: usize,
+    axis: usize,
+) -> Array<f64> {
+    group_norm(
+        &Tensor::new_variable(input.clone()),
+        &Tensor::new_variable(gamma.clone()),
+        &Tensor::new_variable(beta.clone()),
+        num_groups,
+        axis,
+        1e-5,
+    )
+    .eval(None)
+}
+
+#[test]
+fn test_layer_norm_normalizes_axes() {
+    let mut rng = Rng::new(2);
+    let input = Array::random_normal(vec![3, 4, 5], 3., 2., &mut rng);
+
+    let output = eval_layer_norm(
+        &input,
+        &Array::new(1., vec![3, 5]),
+        &Array::new(0., vec![3, 5]),
+        &[0, 2],
+    );
+
+    for j in 0..4 {
+        let values: Vec<f64> = indices(&[3, 5])
+            .into_iter()
+            .map(|index| output[vec![index[0], j, index[1]]])
+            .collect();
+        let mean = values.iter().sum::<f64>() / 15.;
+        let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 15.;
+        assert!(mean.abs() < 1e-10);
+        assert!((variance - 1.).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn test_group_norm_matches_layer_norm_with_single_group() {
+    let mut rng = Rng::new(3);
+    let input = Array::random_normal(vec![2, 4, 3], 0., 1., &mut rng);
+
+    assert_arrays_rel_eq!(
+        eval_group_norm(
+            &input,
+            &Array::new(1., vec![4]),
+            &Array::new(0., vec![4]),
+            1,
+            1
+        ),
+        eval_layer_norm(
+            &input,
+            &Array::new(1., vec![4, 3]),
+            &Array::new(0., vec![4, 3]),
+            &[1, 2]
+        ),
+        1e-12
+    );
+}
+
+#[test]
+fn test_group_norm_nhwc_matches_nchw() {
+    let mut rng = Rng::new(4);
+    let input = Array::random_normal(vec![2, 6, 3, 2], 1., 2., &mut rng);
+    let gamma = Array::random_uniform(vec![6], 0.5, 1.5, &mut rng);
+    let beta = Array::random_uniform(vec![6], -1., 1., &mut rng);
+
+    assert_arrays_rel_eq!(
+        eval_group_norm(&input, &gamma, &beta, 3, 1).permute(&[0, 2, 3, 1]),
+        eval_group_norm(&input.permute(&[0, 2, 3, 1]), &gamma, &beta, 3, 3),
+        1e-12
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_group_norm_indivisible_channels() {
+    eval_group_norm(
+        &Array::new(1., vec![2, 3, 4]),
+        &Array::new(1., vec![3]),
+        &Array::new(0., vec![3]),
+        2,
+        1,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_layer_norm_wrong_gamma_shape() {
+    eval_layer_norm(
+        &Array::new(1., vec![2, 3]),
+        &Array::new(1., vec![2]),
+        &Array::new(0., vec![3]),
+        &[1],
+    );
+}
+
+type NormBuilder = Box<dyn Fn(&Tensor<f64>, &Tensor<f64>, &Tensor<f64>) -> Tensor<f64>>;
+
+#[test]
+fn test_layer_norm_and_group_norm_gradients() {
+    let mut rng = Rng::new(5);
+    let cases: Vec<(Vec<usize>, NormBuilder)> = vec![
+        (vec![3], Box::new(|x, g, b| layer_norm(x, g, b, &[2], 1e-5))),
+        (
+            vec![2, 3],
+            Box::new(|x, g, b| layer_norm(x, g, b, &[0, 2], 1e-5)),
+        ),
+        (vec![4], Box::new(|x, g, b| group_norm(x, g, b, 2, 1, 1e-5))),
+        (vec![3], Box::new(|x, g, b| group_norm(x, g, b, 3, 2, 1e-5))),
+    ];
+    for (param_shape, build) in cases {
+        let input_array = Array::random_uniform(vec![2, 4, 3], -1., 1., &mut rng);
+        let gamma_array = Array::random_uniform(param_shape.clone(), 0.5, 1.5, &mut rng);
+        let beta_array = Array::random_uniform(param_shape, -1., 1., &mut rng);
+        let weights = Array::random_uniform(vec![2, 4, 3], -1., 1., &mut rng);
+        let input = Tensor::new_variable(input_array.clone());
+        let gamma = Tensor::new_variable(gamma_array.clone());
+        let beta = Tensor::new_variable(beta_array.clone());
+        let loss = &build(&input, &gamma, &beta) * &Tensor::new_variable(weights.clone());
+        let eval = |x: &Array<f64>, g: &Array<f64>, b: &Array<f64>| {
+            let output = build(
+                &Tensor::new_variable(x.clone()),
+                &Tensor::new_variable(g.clone()),
+                &Tensor::new_variable(b.clone()),
+            );
+            weighted_sum(&output.eval(None), &weights)
+        };
+
+        assert_arrays_rel_eq!(
+            loss.grad(&input, None).unwrap(),
+            numerical_grad(|x| eval(x, &gamma_array, &beta_array), &input_array),
+            1e-5
+        );
+        assert_arrays_rel_eq!(
+            loss.grad(&gamma, None).unwrap(),
+            numerical_grad(|g| eval(&input_array, g, &beta_array), &gamma_array),
+            1e-5
+        );
+        assert_arrays_rel_eq!(
+            loss.grad(&beta, None).unwrap(),
+            numerical_grad(|b| eval(&input_array, &gamma_array, b), &beta_array),
+            1e-5
+        );
+    }
+}
+
+#[test]
+fn test_layer_norm_and_group_norm_layers() {
+    let input = Tensor::new_variable(Array::from_vec(vec![1., 3., 2., 6.], vec![1, 2, 2]));
+    let layer_norm = LayerNorm::new(vec![2], 0.);
+    let group_norm = GroupNorm::new(1, 2, 1, 0.);
+
+    assert_eq!(
+        layer_norm.forward(&input).eval(None),
+        Array::from_vec(vec![-1., 1., -1., 1.], vec![1, 2, 2])
+    );
+    assert_eq!(group_norm.forward(&input).shape(), vec![1, 2, 2]);
+    assert_eq!(layer_norm.parameters().len(), 2);
+    assert_eq!(group_norm.name(), "GroupNorm");
+}
@@ -0,0 +1,167 @@
+use neurust::io::{load_npz, read_npz, save_npz, write_npz, Dtype, Error};
+use neurust::linalg::Rng;
+use neurust::Array;
+
+// Creates `.npy` data with a given header dictionary and raw data.
+fn npy_bytes(header: &str, data: &[u8]) -> Vec<u8> {
+    let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+#[test]
+fn test_npy_file_roundtrip() {
+    let path = std::env::temp_dir().join("neurust_test_npy_file_roundtrip.npy");
+    let a = Array::random_uniform(vec![2, 3, 4], -1., 1., &mut Rng::new(0));
+    a.save_npy(&path).unwrap();
+    let loaded = Array::<f64>::load_npy(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded, a);
+}
+
+#[test]
+fn test_npy_format() {
+    let a = Array::from_vec(vec![1f32, 2., 3.], vec![3]);
+    let mut buffer = Vec::new();
+    a.write_npy(&mut buffer).unwrap();
+    let header_len = u16::from_le_bytes([buffer[8], buffer[9]]) as usize;
+    let header = String::from_utf8(buffer[10..10 + header_len].to_vec()).unwrap();
+
+    assert_eq!(&buffer[..8], b"\x93NUMPY\x01\x00");
+    assert_eq!((10 + header_len) % 64, 0);
+    assert!(header.starts_with("{'descr': '<f4', 'fortran_order': False, 'shape': (3,), }"));
+    assert!(header.ends_with('\n'));
+    assert_eq!(buffer.len(), 10 + header_len + 12);
+    assert_eq!(
+        &buffer[10 + header_len..10 + header_len + 4],
+        &1f32.to_le_bytes()
+    );
+    assert_eq!(Array::<f32>::read_npy(&mut buffer.as_slice()).unwrap(), a);
+}
+
+#[test]
+fn test_read_npy_scalar_and_version_2() {
+    let header = "{'descr': '<f8', 'fortran_order': False, 'shape': (), }\n";
+    let scalar = npy_bytes(header, &2.5f64.to_le_bytes());
+    let mut version_2 = b"\x93NUMPY\x02\x00".to_vec();
+    let header = "{'descr': '<f8', 'fortran_order': False, 'shape': (1, 2), }\n";
+    version_2.extend_from_slice(&(header.len() as u32).to_le_bytes());
+    version_2.extend_from_slice(header.as_bytes());
+    version_2.extend_from_slice(&1f64.to_le_bytes());
+    version_2.extend_from_slice(&(-1f64).to_le_bytes());
+
+    assert_eq!(
+        Array::<f64>::read_npy(&mut scalar.as_slice()).unwrap(),
+        Array::new(2.5, vec![1])
+    );
+    assert_eq!(
+        Array::<f64>::read_npy(&mut version_2.as_slice()).unwrap(),
+        Array::from_vec(vec![1., -1.], vec![1, 2])
+    );
+}
+
+#[test]
+fn test_read_npy_errors() {
+    let a = Array::new(1f32, vec![2]);
+    let mut buffer = Vec::new();
+    a.write_npy(&mut buffer).unwrap();
+    let fortran = npy_bytes(
+        "{'descr': '<f8', 'fortran_order': True, 'shape': (1,), }\n",
+        &[0; 8],
+    );
+    let big_endian = npy_bytes(
+        "{'descr': '>f8', 'fortran_order': False, 'shape': (1,), }\n",
+        &[0; 8],
+    );
+    let integers = npy_bytes(
+        "{'descr': '<i8', 'fortran_order': False, 'shape': (1,), }\n",
+        &[0; 8],
+    );
+    let empty = npy_bytes(
+        "{'descr': '<f8', 'fortran_order': False, 'shape': (0,), }\n",
+        &[],
+    );
+    let empty_axis = npy_bytes(
+        "{'descr': '<f8', 'fortran_order': False, 'shape': (2, 0), }\n",
+        &[],
+    );
+
+    assert!(matches!(
+        Array::<f64>::read_npy(&mut buffer.as_slice()),
+        Err(Error::DtypeMismatch {
+            expected: Dtype::F64,
+            found: Dtype::F32
+        })
+    ));
+    assert!(matches!(
+        Array::<f32>::read_npy(&mut &buffer[..buffer.len() - 1]),
+        Err(Error::Format(_))
+    ));
+    for bytes in [fortran, big_endian, integers, empty, empty_axis] {
+        assert!(matches!(
+            Array::<f64>::read_npy(&mut bytes.as_slice()),
+            Err(Error::Format(_))
+        ));
+    }
+}
+
+#[test]
+fn test_npz_file_roundtrip() {
+    let path = std::env::temp_dir().join("neurust_test_npz_file_roundtrip.npz");
+    let mut rng = Rng::new(1);
+    let x = Array::random_uniform(vec![4, 3], -1., 1., &mut rng);
+    let y = Array::random_uniform(vec![4], 0., 1., &mut rng);
+    save_npz(&path, &[("x", &x), ("y", &y)]).unwrap();
+    let arrays = load_npz::<f64>(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(arrays, vec![("x".to_owned(), x), ("y".to_owned(), y)]);
+}
+
+#[test]
+fn test_read_compressed_npz() {
+    // created with Python's zipfile using deflate compression, as `numpy.savez_compressed`
+    let data = include_bytes!("data/compressed.npz");
+    let arrays = read_npz::<f32>(&mut &data[..]).unwrap();
+    let x: Vec<f32> = (0..300)
+        .map(|i: u32| ((i * i * 31 + 7) % 256) as f32 / 256.)
+        .collect();
+    let y: Vec<f32> = (0..100).map(|i| (i % 3) as f32).collect();
+
+    assert_eq!(
+        arrays,
+        vec![
+            ("x".to_owned(), Array::from_vec(x, vec![100, 3])),
+            ("y".to_owned(), Array::from_vec(y, vec![100]))
+        ]
+    );
+}
+
+#[test]
+fn test_npz_errors() {
+    let a = Array::new(1., vec![2]);
+    let mut buffer = Vec::new();
+
+    assert!(matches!(
+        write_npz(&mut buffer, &[("a", &a), ("a", &a)]),
+        Err(Error::Format(_))
+    ));
+    assert!(matches!(
+        read_npz::<f64>(&mut &b"not a zip archive"[..]),
+        Err(Error::Format(_))
+    ));
+
+    let mut buffer = Vec::new();
+    write_npz(&mut buffer, &[("a", &a)]).unwrap();
+    // corrupt stored data
+    let position = buffer.len() / 2;
+    buffer[position] ^= 0xff;
+
+    assert!(matches!(
+        read_npz::<f64>(&mut buffer.as_slice()),
+        Err(Error::Format(_))
+    ));
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use common::{numerical_grad, weighted_sum};
+use neurust::linalg::utils::are_arrays_near_equal;
+use neurust::linalg::{pad as pad_array, PadMode, Rng};
+use neurust::tensor::pad::pad;
+use neurust::{assert_arrays_rel_eq, Array, Tensor};
+
+#[test]
+fn test_pad_modes_2d() {
+    let a = Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![2, 3]);
+
+    assert_eq!(
+        pad_array(&a, &[(1, 1), (1, 1)], PadMode::Replicate),
+        Array::from_vec(
+            vec![1., 1., 2., 3., 3., 1., 1., 2., 3., 3., 4., 4., 5., 6., 6., 4., 4., 5., 6., 6.],
+            vec![4, 5]
+        )
+    );
+    assert_eq!(
+        pad_array(&a, &[(2, 1), (0, 0)], PadMode::Circular),
+        Array::from_vec(
+            vec![1., 2., 3., 4., 5., 6., 1., 2., 3., 4., 5., 6., 1., 2., 3.],
+            vec![5, 3]
+        )
+    );
+    assert_eq!(
+        pad_array(&a, &[(1, 0), (1, 2)], PadMode::Reflect),
+        Array::from_vec(
+            vec![5., 4., 5., 6., 5., 4., 2., 1., 2., 3., 2., 1., 5., 4., 5., 6., 5., 4.],
+            vec![3, 6]
+        )
+    );
+}
+
+#[test]
+fn test_pad_constant_value() {
+    let a = Array::from_vec(vec![1., 2.], vec![1, 2, 1]);
+
+    assert_eq!(
+        pad_array(&a, &[(0, 0), (0, 1), (1, 0)], PadMode::Constant(-1.)),
+        Array::from_vec(vec![-1., 1., -1., 2., -1., -1.], vec![1, 3, 2])
+    );
+}
+
+#[test]
+fn test_pad_zero_widths() {
+    let a = Array::from_vec(vec![1., 2., 3., 4.], vec![2, 2]);
+
+    assert_eq!(pad_array(&a, &[(0, 0), (0, 0)], PadMode::Reflect), a);
+}
+
+#[test]
+#[should_panic]
+fn test_pad_wrong_widths_length() {
+    let a = Array::new(1., vec![2, 2]);
+
+    pad_array(&a, &[(1, 1)], PadMode::Constant(0.));
+}
+
+#[test]
+#[should_panic]
+fn test_pad_reflect_too_wide() {
+    let a = Array::new(1., vec![2, 3]);
+
+    pad_array(&a, &[(0, 0), (3, 0)], PadMode::Reflect);
+}
+
+#[test]
+#[should_panic]
+fn test_pad_circular_too_wide() {
+    let a = Array::new(1., vec![2, 3]);
+
+    pad_array(&a, &[(3, 0), (0, 0)], PadMode::Circular);
+}
+
+#[test]
+fn test_pad_gradients() {
+    let mut rng = Rng::new(0);
+    let widths = [(1, 2), (0, 0), (2, 1)];
+    for mode in [
+        PadMode::Constant(3.),
+        PadMode::Reflect,
+        PadMode::Replicate,
+        PadMode::Circular,
+    ] {
+        let input_array = Array::random_uniform(vec![3, 2, 4], -1., 1., &mut rng);
+        let input = Tensor::new_variable(input_array.clone());
+        let output = pad(&input, &widths, mode);
+        let weights = Array::random_uniform(output.shape(), -1., 1., &mut rng);
+        let loss = &output * &Tensor::new_variable(weights.clone());
+
+        assert_eq!(output.shape(), vec![6, 2, 7]);
+        assert_arrays_rel_eq!(
+            loss.grad(&input, None).unwrap(),
+            numerical_grad(
+                |x| weighted_sum(&pad_array(x, &widths, mode), &weights),
+                &input_array
+            ),
+            1e-6
+        );
+    }
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use common::{indices, numerical_grad, weighted_sum};
+use neurust::linalg::utils::are_arrays_near_equal;
+use neurust::linalg::{
+    avg_pool2d as avg_pool2d_array, global_avg_pool2d as global_avg_pool2d_array,
+    global_max_pool2d as global_max_pool2d_array, max_pool2d as max_pool2d_array, DataFormat, Rng,
+};
+use neurust::tensor::pool::{avg_pool2d, global_avg_pool2d, global_max_pool2d, max_pool2d};
+use neurust::{assert_arrays_rel_eq, Array, Tensor};
+
+type ArrayPool = Box<dyn Fn(&Array<f64>) -> Array<f64>>;
+
+#[test]
+fn test_max_pool2d_padding() {
+    let input = Array::from_vec(
+        vec![
+            -1., -2., -3., -4., -5., -6., -7., -8., -9., -10., -11., -12., -13., -14., -15., -16.,
+        ],
+        vec![1, 1, 4, 4],
+    );
+
+    assert_eq!(
+        max_pool2d_array(&input, (3, 3), (2, 2), (1, 1), DataFormat::Nchw),
+        Array::from_vec(vec![-1., -2., -5., -6.], vec![1, 1, 2, 2])
+    );
+}
+
+#[test]
+fn test_avg_pool2d_padding_excluded() {
+    let input = Array::from_vec(
+        vec![
+            1., 2., 3., 4., 5., 6., 7., 8., 9., 10., 11., 12., 13., 14., 15., 16.,
+        ],
+        vec![1, 1, 4, 4],
+    );
+
+    assert_eq!(
+        avg_pool2d_array(&input, (3, 3), (2, 2), (1, 1), DataFormat::Nchw),
+        Array::from_vec(vec![3.5, 5., 9.5, 11.], vec![1, 1, 2, 2])
+    );
+}
+
+#[test]
+fn test_pool2d_nhwc() {
+    let mut rng = Rng::new(0);
+    let input = Array::random_uniform(vec![2, 3, 5, 6], -1., 1., &mut rng);
+    let nhwc = input.permute(&[0, 2, 3, 1]);
+
+    assert_eq!(
+        max_pool2d_array(&nhwc, (2, 3), (2, 1), (1, 1), DataFormat::Nhwc),
+        max_pool2d_array(&input, (2, 3), (2, 1), (1, 1), DataFormat::Nchw).permute(&[0, 2, 3, 1])
+    );
+    assert_eq!(
+        avg_pool2d_array(&nhwc, (2, 3), (2, 1), (1, 1), DataFormat::Nhwc),
+        avg_pool2d_array(&input, (2, 3), (2, 1), (1, 1), DataFormat::Nchw).permute(&[0, 2, 3, 1])
+    );
+}
+
+#[test]
+fn test_global_pool2d() {
+    let mut rng = Rng::new(1);
+    let input = Array::random_uniform(vec![2, 3, 4, 5], -1., 1., &mut rng);
+
+    let max = global_max_pool2d_array(&input, DataFormat::Nchw);
+    let avg = global_avg_pool2d_array(&input, DataFormat::Nchw);
+
+    assert_eq!(max.get_shape(), vec![2, 3, 1, 1]);
+    for index in indices(&[2, 3]) {
+        let values: Vec<f64> = indices(&[4, 5])
+            .into_iter()
+            .map(|hw| input[vec![index[0], index[1], hw[0], hw[1]]])
+            .collect();
+        let expected_max = values.iter().cloned().fold(f64::MIN, f64::max);
+        let expected_avg = values.iter().sum::<f64>() / 20.;
+        assert_eq!(max[vec![index[0], index[1], 0, 0]], expected_max);
+        assert!((avg[vec![index[0], index[1], 0, 0]] - expected_avg).abs() < 1e-12);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_pool2d_padding_too_big() {
+    let input = Array::new(1., vec![1, 1, 4, 4]);
+
+    max_pool2d_array(&input, (2, 2), (1, 1), (2, 2), DataFormat::Nchw);
+}
+
+#[test]
+#[should_panic]
+fn test_pool2d_wrong_dims() {
+    let input = Array::new(1., vec![1, 4, 4]);
+
+    avg_pool2d_array(&input, (2, 2), (1, 1), (0, 0), DataFormat::Nchw);
+}
+
+#[test]
+fn test_max_pool2d_gradient_routes_to_argmax() {
+    let input = Tensor::new_variable(Array::from_vec(
+        vec![
+            1., 9., 2., 3., 4., 5., 6., 7., 8., 0., 1., 2., 3., 4., 5., 6.,
+        ],
+        vec![1, 1, 4, 4],
+    ));
+    let output = max_pool2d(&input, (2, 2), (2, 2), (0, 0), DataFormat::Nchw);
+    let weights = Tensor::new_variable(Array::from_vec(vec![1., 2., 3., 4.], vec![1, 1, 2, 2]));
+
+    assert_eq!(
+        (&output * &weights).grad(&input, None).unwrap(),
+        Array::from_vec(
+            vec![0., 1., 0., 0., 0., 0., 0., 2., 3., 0., 0., 0., 0., 0., 0., 4.],
+            vec![1, 1, 4, 4]
+        )
+    );
+}
+
+#[test]
+fn test_pool2d_gradients() {
+    let mut rng = Rng::new(2);
+    for data_format in [DataFormat::Nchw, DataFormat::Nhwc] {
+        let input_array = Array::random_uniform(vec![2, 5, 6, 3], -1., 1., &mut rng);
+        let input = Tensor::new_variable(input_array.clone());
+
+        let cases: Vec<(Tensor<f64>, ArrayPool)> = vec![
+            (
+                max_pool2d(&input, (3, 2), (2, 2), (1, 1), data_format),
+                Box::new(move |x| max_pool2d_array(x, (3, 2), (2, 2), (1, 1), data_format)),
+            ),
+            (
+                avg_pool2d(&input, (3, 2), (2, 1), (1, 1), data_format),
+                Box::new(move |x| avg_pool2d_array(x, (3, 2), (2, 1), (1, 1), data_format)),
+            ),
+            (
+                global_max_pool2d(&input, data_format),
+                Box::new(move |x| global_max_pool2d_array(x, data_format)),
+            ),
+            (
+                global_avg_pool2d(&input, data_format),
+                Box::new(move |x| global_avg_pool2d_array(x, data_format)),
+            ),
+        ];
+        for (output, pool) in cases {
+            let weights = Array::random_uniform(output.shape(), -1., 1., &mut rng);
+            let loss = &output * &Tensor::new_variable(weights.clone());
+
+            assert_eq!(output.eval(None), pool(&input_array));
+            assert_arrays_rel_eq!(
+                loss.grad(&input, None).unwrap(),
+                numerical_grad(|x| weighted_sum(&pool(x), &weights), &input_array),
+                1e-6
+            );
+        }
+    }
+}
@@ -0,0 +1,219 @@
+mod common;
+
+use common::{numerical_grad, weighted_sum};
+use neurust::linalg::utils::are_arrays_near_equal;
+use neurust::linalg::Rng;
+use neurust::nn::layers::{Recurrent, RecurrentCell, GRU, LSTM, RNN};
+use neurust::nn::Module;
+use neurust::tensor::math::tanh;
+use neurust::tensor::shape::select;
+use neurust::{assert_arrays_rel_eq, Array, Tensor};
+use std::collections::HashMap;
+
+#[test]
+fn test_recurrent_layers_output_shapes() {
+    let mut rng = Rng::new(0);
+    let input = Tensor::new_variable(Array::random_uniform(vec![2, 5, 3], -1., 1., &mut rng));
+    let check = |sequences: &dyn Module<f64>, last: &dyn Module<f64>| {
+        let sequences_output = sequences.forward(&input);
+        let last_output = last.forward(&input);
+
+        assert_eq!(sequences_output.shape(), vec![2, 5, 4]);
+        assert_eq!(last_output.shape(), vec![2, 4]);
+    };
+
+    check(
+        &RNN::new(3, 4, tanh, true, &mut rng),
+        &RNN::new(3, 4, tanh, false, &mut rng),
+    );
+    check(
+        &LSTM::new(3, 4, true, &mut rng),
+        &LSTM::new(3, 4, false, &mut rng),
+    );
+    check(
+        &GRU::new(3, 4, true, &mut rng),
+        &GRU::new(3, 4, false, &mut rng),
+    );
+}
+
+#[test]
+fn test_last_state_matches_last_step_of_sequence() {
+    let mut rng = Rng::new(1);
+    let input = Tensor::new_variable(Array::random_uniform(vec![2, 4, 3], -1., 1., &mut rng));
+    let lstm = LSTM::new(3, 5, true, &mut rng);
+    let (output, states) = lstm.forward_with_state(&input, None);
+
+    assert_eq!(select(&output, 1, 3).eval(None), states[0].eval(None));
+}
+
+#[test]
+fn test_rnn_computation() {
+    let rnn = RNN::new(1, 1, tanh, true, &mut Rng::new(0));
+    rnn.cell().named_parameters()[0]
+        .1
+        .assign(&Array::new(2., vec![1, 1]));
+    rnn.cell().named_parameters()[1]
+        .1
+        .assign(&Array::new(0.5, vec![1, 1]));
+    rnn.cell().named_parameters()[2]
+        .1
+        .assign(&Array::new(0.1, vec![1]));
+    let input = Tensor::new_variable(Array::from_vec(vec![1., -1.], vec![1, 2, 1]));
+
+    let h1 = (2.1f64).tanh();
+    let h2 = (-2. + 0.5 * h1 + 0.1).tanh();
+
+    assert_arrays_rel_eq!(
+        rnn.forward(&input).eval(None),
+        Array::from_vec(vec![h1, h2], vec![1, 2, 1]),
+        1e-12
+    );
+}
+
+type ForwardWithState<'a> =
+    Box<dyn Fn(&Tensor<f64>, Option<&[Tensor<f64>]>) -> (Tensor<f64>, Vec<Tensor<f64>>) + 'a>;
+
+#[test]
+fn test_initial_states_via_placeholders() {
+    let mut rng = Rng::new(2);
+    let input_array = Array::random_uniform(vec![2, 6, 3], -1., 1., &mut rng);
+    let first_chunk = Tensor::new_variable(
+        input_array
+            .s(vec![(..).into(), (..3).into(), (..).into()])
+            .to_array(),
+    );
+    let second_chunk = Tensor::new_variable(
+        input_array
+            .s(vec![(..).into(), (3..).into(), (..).into()])
+            .to_array(),
+    );
+    let input = Tensor::new_variable(input_array);
+    let gru = GRU::new(3, 4, true, &mut rng);
+    let lstm = LSTM::new(3, 4, false, &mut rng);
+    let layers: Vec<(ForwardWithState, usize)> = vec![
+        (Box::new(|x, s| gru.forward_with_state(x, s)), 1),
+        (Box::new(|x, s| lstm.forward_with_state(x, s)), 2),
+    ];
+    for (forward, state_count) in layers {
+        let placeholders: Vec<Tensor<f64>> = (0..state_count)
+            .map(|i| Tensor::new_placeholder(format!("state_{}", i), vec![2, 4]))
+            .collect();
+        let (_, states) = forward(&input, None);
+        let (_, zero_states) = forward(&input, Some(&placeholders));
+        let (_, first_chunk_states) = forward(&first_chunk, None);
+        let (_, second_chunk_states) = forward(&second_chunk, Some(&placeholders));
+
+        // zero initial states are equivalent to no initial states
+        let zeros = Array::new(0., vec![2, 4]);
+        let mut feed_dict = HashMap::new();
+        for i in 0..state_count {
+            feed_dict.insert(format!("state_{}", i), &zeros);
+        }
+        assert_eq!(zero_states[0].eval(Some(&feed_dict)), states[0].eval(None));
+
+        // processing a sequence in chunks with carried over states gives the same result
+        let carried_states: Vec<Array<f64>> = first_chunk_states
+            .iter()
+            .map(|state| state.eval(None))
+            .collect();
+        let mut feed_dict = HashMap::new();
+        for (i, state) in carried_states.iter().enumerate() {
+            feed_dict.insert(format!("state_{}", i), state);
+        }
+        for (state, chunk_state) in states.iter().zip(second_chunk_states.iter()) {
+            assert_arrays_rel_eq!(chunk_state.eval(Some(&feed_dict)), state.eval(None), 1e-12);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_recurrent_invalid_initial_states() {
+    let mut rng = Rng::new(0);
+    let lstm = LSTM::new(3, 4, false, &mut rng);
+    let input = Tensor::new_variable(Array::new(1., vec![2, 5, 3]));
+    let state = Tensor::new_variable(Array::new(0., vec![2, 4]));
+
+    lstm.forward_with_state(&input, Some(&[state]));
+}
+
+#[test]
+#[should_panic]
+fn test_recurrent_invalid_input_shape() {
+    let rnn = RNN::new(3, 4, tanh, false, &mut Rng::new(0));
+
+    rnn.forward(&Tensor::new_variable(Array::new(1., vec![2, 3])));
+}
+
+#[test]
+fn test_recurrent_parameters() {
+    let mut rng = Rng::new(0);
+    let lstm = LSTM::<f64>::new(3, 4, false, &mut rng);
+    let gru = GRU::<f64>::new(3, 4, false, &mut rng);
+    let names = |parameters: Vec<(String, &Tensor<f64>)>| {
+        parameters
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(lstm.name(), "LSTM");
+    assert_eq!(
+        &names(lstm.named_parameters())[..3],
+        ["kernel_i", "recurrent_kernel_i", "bias_i"]
+    );
+    assert_eq!(
+        lstm.named_parameters()[5].1.eval(None),
+        Array::new(1., vec![4])
+    );
+    assert_eq!(names(gru.named_parameters()).len(), 9);
+    assert_eq!(gru.named_parameters()[1].1.shape(), vec![4, 4]);
+}
+
+fn check_gradients<C: RecurrentCell<f64>>(
+    layer: &Recurrent<C>,
+    input_array: &Array<f64>,
+    rng: &mut Rng,
+) {
+    let input = Tensor::new_variable(input_array.clone());
+    let output = layer.forward(&input);
+    let weights = Array::random_uniform(output.shape(), -1., 1., rng);
+    let loss = &output * &Tensor::new_variable(weights.clone());
+    let eval = |input: &Array<f64>| {
+        let output = layer.forward(&Tensor::new_variable(input.clone()));
+        weighted_sum(&output.eval(None), &weights)
+    };
+
+    assert_arrays_rel_eq!(
+        loss.grad(&input, None).unwrap(),
+        numerical_grad(eval, input_array),
+        1e-5
+    );
+    for (_, parameter) in layer.named_parameters() {
+        let parameter_array = parameter.eval(None);
+        let eval = |value: &Array<f64>| {
+            parameter.assign(value);
+            weighted_sum(&output.eval(None), &weights)
+        };
+        let expected = numerical_grad(eval, &parameter_array);
+        parameter.assign(&parameter_array);
+
+        assert_arrays_rel_eq!(loss.grad(parameter, None).unwrap(), expected, 1e-5);
+    }
+}
+
+#[test]
+fn test_recurrent_layers_gradients() {
+    let mut rng = Rng::new(3);
+    let input_array = Array::random_uniform(vec![2, 3, 2], -1., 1., &mut rng);
+
+    check_gradients(
+        &RNN::new(2, 3, tanh, true, &mut rng),
+        &input_array,
+        &mut rng,
+    );
+    check_gradients(&LSTM::new(2, 3, false, &mut rng), &input_array, &mut rng);
+    check_gradients(&LSTM::new(2, 3, true, &mut rng), &input_array, &mut rng);
+    check_gradients(&GRU::new(2, 3, false, &mut rng), &input_array, &mut rng);
+    check_gradients(&GRU::new(2, 3, true, &mut rng), &input_array, &mut rng);
+}
@@ -0,0 +1,206 @@
+use neurust::io::{
+    load_safetensors, save_safetensors, write_safetensors, Dtype, Error, SafeTensors, TensorInfo,
+};
+use neurust::linalg::Rng;
+use neurust::Array;
+use std::convert::TryInto;
+
+// Creates safetensors data with a given JSON header and data buffer.
+fn safetensors_bytes(header: &str, data: &[u8]) -> Vec<u8> {
+    let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+    bytes.extend_from_slice(header.as_bytes());
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+#[test]
+fn test_safetensors_file_roundtrip() {
+    let path = std::env::temp_dir().join("neurust_test_safetensors_file_roundtrip.safetensors");
+    let mut rng = Rng::new(0);
+    let kernel = Array::random_uniform(vec![3, 4], -1., 1., &mut rng);
+    let bias = Array::random_uniform(vec![4], -1., 1., &mut rng);
+    save_safetensors(
+        &path,
+        &[("dense.kernel", &kernel), ("dense.bias", &bias)],
+        &[],
+    )
+    .unwrap();
+    let arrays = load_safetensors::<f64>(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        arrays,
+        vec![
+            ("dense.kernel".to_owned(), kernel),
+            ("dense.bias".to_owned(), bias)
+        ]
+    );
+}
+
+#[test]
+fn test_write_safetensors_layout() {
+    let a = Array::from_vec(vec![1f32, 2.], vec![2, 1]);
+    let b = Array::from_vec(vec![3f32], vec![1]);
+    let mut buffer = Vec::new();
+    write_safetensors(&mut buffer, &[("a", &a), ("b", &b)], &[("format", "np")]).unwrap();
+    let header_len = u64::from_le_bytes(buffer[..8].try_into().unwrap()) as usize;
+    let header = std::str::from_utf8(&buffer[8..8 + header_len]).unwrap();
+    let tensors = SafeTensors::parse(&buffer).unwrap();
+
+    assert_eq!(header_len % 8, 0);
+    assert_eq!(
+        header.trim_end(),
+        concat!(
+            r#"{"__metadata__":{"format":"np"},"#,
+            r#""a":{"dtype":"F32","shape":[2,1],"data_offsets":[0,8]},"#,
+            r#""b":{"dtype":"F32","shape":[1],"data_offsets":[8,12]}}"#
+        )
+    );
+    assert_eq!(
+        &buffer[8 + header_len..8 + header_len + 4],
+        &1f32.to_le_bytes()
+    );
+    assert_eq!(
+        tensors.info("b"),
+        Some(&TensorInfo {
+            dtype: Dtype::F32,
+            shape: vec![1],
+            data_offsets: (8, 12)
+        })
+    );
+}
+
+#[test]
+fn test_parse_external_safetensors() {
+    // arrays stored in a different order than described, with a scalar and a mixed data type
+    let header = r#"{
+        "scale": {"dtype": "F64", "shape": [], "data_offsets": [16, 24]},
+        "weights": {"dtype": "F32", "shape": [2, 2], "data_offsets": [0, 16]},
+        "__metadata__": {"format": "pt"}
+    }   "#;
+    let mut data = Vec::new();
+    for value in [1f32, -2., 3., 0.5] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(&0.25f64.to_le_bytes());
+    let bytes = safetensors_bytes(header, &data);
+    let tensors = SafeTensors::parse(&bytes).unwrap();
+
+    assert_eq!(tensors.names(), vec!["scale", "weights"]);
+    assert_eq!(
+        tensors.array::<f32>("weights").unwrap(),
+        Array::from_vec(vec![1., -2., 3., 0.5], vec![2, 2])
+    );
+    assert_eq!(
+        tensors.array::<f64>("scale").unwrap(),
+        Array::new(0.25, vec![1])
+    );
+    assert!(matches!(
+        tensors.array::<f64>("weights"),
+        Err(Error::DtypeMismatch {
+            expected: Dtype::F64,
+            found: Dtype::F32
+        })
+    ));
+    assert!(matches!(
+        tensors.array::<f32>("bias"),
+        Err(Error::Missing(_))
+    ));
+    assert!(matches!(
+        tensors.arrays::<f32>(),
+        Err(Error::DtypeMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_parse_empty_safetensors_tensor() {
+    let header = r#"{
+        "empty": {"dtype": "F32", "shape": [2, 0], "data_offsets": [0, 0]},
+        "weights": {"dtype": "F32", "shape": [1], "data_offsets": [0, 4]}
+    }"#;
+    let bytes = safetensors_bytes(header, &1f32.to_le_bytes());
+    let tensors = SafeTensors::parse(&bytes).unwrap();
+
+    assert_eq!(tensors.info("empty").unwrap().shape, vec![2, 0]);
+    assert_eq!(
+        tensors.array::<f32>("weights").unwrap(),
+        Array::new(1., vec![1])
+    );
+    assert!(matches!(
+        tensors.array::<f32>("empty"),
+        Err(Error::Format(_))
+    ));
+}
+
+#[test]
+fn test_parse_invalid_safetensors() {
+    let tensor = |dtype: &str, shape: &str, offsets: &str| {
+        format!(
+            r#""{{}}":{{"dtype":"{}","shape":{},"data_offsets":{}}}"#,
+            dtype, shape, offsets
+        )
+    };
+    let invalid_headers = [
+        // size doesn't match shape
+        format!("{{{}}}", tensor("F32", "[3]", "[0, 8]").replace("{}", "a")),
+        // unsupported data type
+        format!("{{{}}}", tensor("BF16", "[4]", "[0, 8]").replace("{}", "a")),
+        // gap between tensors
+        format!(
+            "{{{},{}}}",
+            tensor("F32", "[1]", "[0, 4]").replace("{}", "a"),
+            tensor("F32", "[0]", "[8, 8]").replace("{}", "b")
+        ),
+        // overlapping tensors
+        format!(
+            "{{{},{}}}",
+            tensor("F32", "[2]", "[0, 8]").replace("{}", "a"),
+            tensor("F32", "[1]", "[4, 8]").replace("{}", "b")
+        ),
+        // tensor exceeding data buffer
+        format!("{{{}}}", tensor("F64", "[2]", "[0, 16]").replace("{}", "a")),
+        // not a JSON object
+        "[]".to_owned(),
+        // high surrogate escape followed by a non-surrogate one
+        r#"{"\ud800\u0041":{"dtype":"F32","shape":[2],"data_offsets":[0,8]}}"#.to_owned(),
+    ];
+    for header in invalid_headers.iter() {
+        let bytes = safetensors_bytes(header, &[0; 8]);
+
+        assert!(matches!(SafeTensors::parse(&bytes), Err(Error::Format(_))));
+    }
+
+    let mut too_long_header = safetensors_bytes("{}", &[]);
+    too_long_header[0] = 100;
+    let mut huge_header = safetensors_bytes("{}", &[]);
+    huge_header[..8].copy_from_slice(&u64::MAX.to_le_bytes());
+    let deeply_nested = safetensors_bytes(&"[".repeat(200_000), &[]);
+
+    assert!(matches!(
+        SafeTensors::parse(&too_long_header),
+        Err(Error::Format(_))
+    ));
+    assert!(matches!(
+        SafeTensors::parse(&huge_header),
+        Err(Error::Format(_))
+    ));
+    assert!(matches!(
+        SafeTensors::parse(&deeply_nested),
+        Err(Error::Format(_))
+    ));
+    assert!(matches!(SafeTensors::parse(&[1, 2]), Err(Error::Format(_))));
+}
+
+#[test]
+fn test_write_safetensors_invalid_names() {
+    let a = Array::new(1., vec![1]);
+
+    assert!(matches!(
+        write_safetensors(&mut Vec::new(), &[("a", &a), ("a", &a)], &[]),
+        Err(Error::Format(_))
+    ));
+    assert!(matches!(
+        write_safetensors(&mut Vec::new(), &[("__metadata__", &a)], &[]),
+        Err(Error::Format(_))
+    ));
+}
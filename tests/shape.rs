@@ -0,0 +1,77 @@
+mod common;
+
+use common::{numerical_grad, weighted_sum};
+use neurust::linalg::utils::are_arrays_near_equal;
+use neurust::linalg::{stack as stack_arrays, Rng};
+use neurust::tensor::shape::{reshape, select, stack};
+use neurust::{assert_arrays_rel_eq, Array, Tensor};
+
+#[test]
+fn test_stack_and_select_arrays() {
+    let a = Array::from_vec(vec![1., 2., 3., 4.], vec![2, 2]);
+    let b = Array::from_vec(vec![5., 6., 7., 8.], vec![2, 2]);
+    let stacked = stack_arrays(&[&a, &b], 1);
+
+    assert_eq!(
+        stacked,
+        Array::from_vec(vec![1., 2., 5., 6., 3., 4., 7., 8.], vec![2, 2, 2])
+    );
+    assert_eq!(stacked.select(1, 0), a);
+    assert_eq!(stacked.select(1, 1), b);
+    assert_eq!(
+        stacked.select(2, 1),
+        Array::from_vec(vec![2., 6., 4., 8.], vec![2, 2])
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_stack_different_shapes() {
+    let a = Array::new(1., vec![2, 2]);
+    let b = Array::new(1., vec![2, 3]);
+
+    stack_arrays(&[&a, &b], 0);
+}
+
+#[test]
+#[should_panic]
+fn test_select_index_out_of_bounds() {
+    Array::new(1., vec![2, 2]).select(0, 2);
+}
+
+#[test]
+fn test_shape_ops_gradients() {
+    let mut rng = Rng::new(0);
+    let a_array = Array::random_uniform(vec![2, 3], -1., 1., &mut rng);
+    let b_array = Array::random_uniform(vec![2, 3], -1., 1., &mut rng);
+    let weights = Array::random_uniform(vec![3, 2], -1., 1., &mut rng);
+    let a = Tensor::new_variable(a_array.clone());
+    let b = Tensor::new_variable(b_array.clone());
+    let build = |a: &Tensor<f64>, b: &Tensor<f64>| {
+        // `a` is stacked twice to check accumulation of gradients
+        let stacked = stack(&[a, b, a], 2);
+        reshape(&select(&stacked, 0, 1), vec![3, 3]).matmul(&Tensor::new_variable(Array::from_vec(
+            vec![1., 0., 0., 2., 1., -1.],
+            vec![3, 2],
+        )))
+    };
+    let loss = &build(&a, &b) * &Tensor::new_variable(weights.clone());
+    let eval = |a: &Array<f64>, b: &Array<f64>| {
+        let output = build(
+            &Tensor::new_variable(a.clone()),
+            &Tensor::new_variable(b.clone()),
+        );
+        weighted_sum(&output.eval(None), &weights)
+    };
+
+    assert_arrays_rel_eq!(
+        loss.grad(&a, None).unwrap(),
+        numerical_grad(|x| eval(x, &b_array), &a_array),
+        1e-5
+    );
+    assert_arrays_rel_eq!(
+        loss.grad(&b, None).unwrap(),
+        numerical_grad(|x| eval(&a_array, x), &b_array),
+        1e-5
+    );
+}
@@ -1,4 +1,8 @@
+mod common;
+
+use common::{numerical_grad, weighted_sum};
 use neurust::linalg::utils::are_arrays_near_equal;
+use neurust::linalg::Rng;
 use neurust::{assert_arrays_rel_eq, Array, Tensor};
 use std::collections::HashMap;
 
@@ -147,7 +151,7 @@ test_tensor_operators!(
 
 test_tensor_operators!(
     test_sub, -, Array::new(-1., vec![2, 2, 3]),
-    Array::new(-1., vec![2, 2, 3]), Array::new(-1., vec![2, 2, 3])
+    Array::new(1., vec![2, 2, 3]), Array::new(-1., vec![2, 2, 3])
 );
 
 test_tensor_operators!(
@@ -203,6 +207,70 @@ fn test_broadcast_smaller_shape() {
     assert_eq!(add.eval(None), Array::new(4., vec![5, 1, 3, 2]));
 }
 
+#[test]
+fn test_broadcast_gradient() {
+    let a = Tensor::new_variable(Array::new(1., vec![2, 3, 2]));
+    let b = Tensor::new_variable(Array::new(3., vec![3, 1]));
+    let c = Tensor::new_variable(Array::new(2., vec![2, 2, 4]));
+
+    assert_eq!((&a + &b).grad(&b, None), Some(Array::new(4., vec![3, 1])));
+    assert_eq!((&a * &b).grad(&b, None), Some(Array::new(4., vec![3, 1])));
+    assert_eq!(
+        (&a * &b).grad(&a, None),
+        Some(Array::new(3., vec![2, 3, 2]))
+    );
+    assert_eq!(
+        c.matmul(&Tensor::new_variable(Array::new(1., vec![4, 3])))
+            .grad(&c, None),
+        Some(Array::new(3., vec![2, 2, 4]))
+    );
+}
+
+#[test]
+fn test_broadcast_matmul_gradient() {
+    let a = Tensor::new_variable(Array::new(1., vec![2, 3, 2]));
+    let b = Tensor::new_variable(Array::new(2., vec![2, 4]));
+    let matmul = a.matmul(&b);
+
+    assert_eq!(matmul.grad(&b, None), Some(Array::new(6., vec![2, 4])));
+}
+
+#[test]
+fn test_sub_div_gradients() {
+    let mut rng = Rng::new(0);
+    for shape_b in [vec![2, 3], vec![3], vec![2, 1]] {
+        let a_array = Array::random_uniform(vec![2, 3], -1., 1., &mut rng);
+        let b_array = Array::random_uniform(shape_b, 0.5, 1.5, &mut rng);
+        let weights = Array::random_uniform(vec![2, 3], -1., 1., &mut rng);
+        let a = Tensor::new_variable(a_array.clone());
+        let b = Tensor::new_variable(b_array.clone());
+        let weights_tensor = Tensor::new_variable(weights.clone());
+        let sub_loss = &(&a - &b) * &weights_tensor;
+        let div_loss = &(&a / &b) * &weights_tensor;
+
+        assert_arrays_rel_eq!(
+            sub_loss.grad(&a, None).unwrap(),
+            numerical_grad(|x| weighted_sum(&x.sub(&b_array), &weights), &a_array),
+            1e-5
+        );
+        assert_arrays_rel_eq!(
+            sub_loss.grad(&b, None).unwrap(),
+            numerical_grad(|x| weighted_sum(&a_array.sub(x), &weights), &b_array),
+            1e-5
+        );
+        assert_arrays_rel_eq!(
+            div_loss.grad(&a, None).unwrap(),
+            numerical_grad(|x| weighted_sum(&x.div(&b_array), &weights), &a_array),
+            1e-5
+        );
+        assert_arrays_rel_eq!(
+            div_loss.grad(&b, None).unwrap(),
+            numerical_grad(|x| weighted_sum(&a_array.div(x), &weights), &b_array),
+            1e-5
+        );
+    }
+}
+
 mod test_neg {
     use super::*;
 
@@ -305,3 +373,51 @@ fn test_complex_example() {
         1e-7
     );
 }
+
+#[test]
+fn test_eval_and_grads() {
+    let a = Tensor::new_variable(Array::from_vec(vec![1., 2.], vec![2, 1]));
+    let b = Tensor::new_variable(Array::from_vec(vec![3., 4.], vec![2, 1]));
+    let c = Tensor::new_variable(Array::new(1., vec![2, 1]));
+    let output = &(&a * &b) + &a;
+
+    let (value, grads) = output.eval_and_grads(&[&a, &b, &c], None);
+
+    assert_eq!(value, output.eval(None));
+    assert_eq!(grads[0], output.grad(&a, None));
+    assert_eq!(grads[1], output.grad(&b, None));
+    assert_eq!(grads[2], None);
+}
+
+#[test]
+fn test_grad_with_shared_intermediate_nodes() {
+    let x = Tensor::new_variable(Array::from_vec(vec![1., 2., 3.], vec![3]));
+    let y = &x * &x;
+    let z = &(&y * &y) + &y;
+
+    // z = x^4 + x^2
+    assert_eq!(
+        z.grad(&x, None).unwrap(),
+        Array::from_vec(vec![6., 36., 114.], vec![3])
+    );
+    assert_eq!(
+        z.grad(&y, None).unwrap(),
+        Array::from_vec(vec![3., 9., 19.], vec![3])
+    );
+}
+
+#[test]
+fn test_grad_with_deeply_shared_nodes() {
+    let x = Tensor::new_variable(Array::from_vec(vec![1., 2.], vec![2]));
+    let mut y = &x * 1.;
+    // Every node is used twice, so the gradient has to be propagated
+    // once per node rather than once per path.
+    for _ in 0..50 {
+        y = &y + &y;
+    }
+
+    assert_eq!(
+        y.grad(&x, None).unwrap(),
+        Array::new(2f64.powi(50), vec![2])
+    );
+}
@@ -0,0 +1,225 @@
+use neurust::data::transforms::{
+    BoundingBox, ColorJitter, Compose, Normalize, RandomAffine, RandomCrop, RandomHorizontalFlip,
+    RandomVerticalFlip, Resize, Transform,
+};
+use neurust::linalg::utils::are_arrays_near_equal;
+use neurust::linalg::{Interpolation, Rng};
+use neurust::{assert_arrays_rel_eq, Array};
+
+// Creates an image of zeros with ones inside a given box.
+fn box_image(channels: usize, height: usize, width: usize, bounding_box: [usize; 4]) -> Array<f64> {
+    let [x_min, y_min, x_max, y_max] = bounding_box;
+    let mut data = Vec::new();
+    for _ in 0..channels {
+        for y in 0..height {
+            for x in 0..width {
+                let inside = (x_min..x_max).contains(&x) && (y_min..y_max).contains(&y);
+                data.push(if inside { 1. } else { 0. });
+            }
+        }
+    }
+    Array::from_vec(data, vec![channels, height, width])
+}
+
+// Returns the smallest box containing non-zero pixels of the first channel.
+fn content_box(image: &Array<f64>) -> BoundingBox<f64> {
+    let shape = image.get_shape();
+    let mut content = BoundingBox::new(f64::INFINITY, f64::INFINITY, 0., 0.);
+    for y in 0..shape[1] {
+        for x in 0..shape[2] {
+            if image[vec![0, y, x]] > 0.5 {
+                content.x_min = content.x_min.min(x as f64);
+                content.y_min = content.y_min.min(y as f64);
+                content.x_max = content.x_max.max(x as f64 + 1.);
+                content.y_max = content.y_max.max(y as f64 + 1.);
+            }
+        }
+    }
+    content
+}
+
+#[test]
+fn test_random_crop() {
+    let mut rng = Rng::new(0);
+    let image = box_image(2, 8, 10, [3, 2, 6, 5]);
+    let boxes = [
+        BoundingBox::new(3., 2., 6., 5.),
+        BoundingBox::new(0., 0., 10., 8.),
+    ];
+    let crop = RandomCrop::new(6, 7);
+    for _ in 0..10 {
+        let (output, output_boxes) = crop.apply(&image, &boxes, &mut rng);
+
+        assert_eq!(output.get_shape(), vec![2, 6, 7]);
+        assert_eq!(content_box(&output), output_boxes[0]);
+        assert_eq!(output_boxes[1], BoundingBox::new(0., 0., 7., 6.));
+    }
+}
+
+#[test]
+fn test_random_crop_outside_box_is_empty() {
+    let image = Array::new(0., vec![1, 4, 4]);
+    let boxes = [BoundingBox::new(2., 0., 4., 4.)];
+    let mut rng = Rng::new(0);
+    let mut empty_count = 0;
+    for _ in 0..20 {
+        let (_, output_boxes) = RandomCrop::new(4, 2).apply(&image, &boxes, &mut rng);
+        if output_boxes[0].is_empty() {
+            empty_count += 1;
+        }
+    }
+
+    assert!(empty_count > 0 && empty_count < 20);
+}
+
+#[test]
+fn test_flips() {
+    let image = Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![1, 2, 3]);
+    let boxes = [BoundingBox::new(0., 0., 1., 2.)];
+    let mut rng = Rng::new(0);
+    let (horizontal, horizontal_boxes) =
+        RandomHorizontalFlip::new(1.).apply(&image, &boxes, &mut rng);
+    let (vertical, vertical_boxes) = RandomVerticalFlip::new(1.).apply(&image, &boxes, &mut rng);
+
+    assert_eq!(
+        horizontal,
+        Array::from_vec(vec![3., 2., 1., 6., 5., 4.], vec![1, 2, 3])
+    );
+    assert_eq!(horizontal_boxes, vec![BoundingBox::new(2., 0., 3., 2.)]);
+    assert_eq!(
+        vertical,
+        Array::from_vec(vec![4., 5., 6., 1., 2., 3.], vec![1, 2, 3])
+    );
+    assert_eq!(vertical_boxes, boxes.to_vec());
+    assert_eq!(
+        RandomHorizontalFlip::new(0.).apply(&image, &boxes, &mut rng),
+        (image, boxes.to_vec())
+    );
+}
+
+#[test]
+fn test_resize() {
+    let image = box_image(3, 4, 6, [2, 1, 4, 3]);
+    let boxes = [BoundingBox::new(2., 1., 4., 3.)];
+    let mut rng = Rng::new(0);
+    let (nearest, nearest_boxes) =
+        Resize::new(8, 3, Interpolation::Nearest).apply(&image, &boxes, &mut rng);
+    let bilinear = Resize::new(2, 3, Interpolation::Bilinear).apply_image(&image, &mut rng);
+
+    assert_eq!(nearest.get_shape(), vec![3, 8, 3]);
+    assert_eq!(nearest_boxes, vec![BoundingBox::new(1., 2., 2., 6.)]);
+    assert_eq!(content_box(&nearest), nearest_boxes[0]);
+    assert_eq!(bilinear.get_shape(), vec![3, 2, 3]);
+    assert_eq!(bilinear[vec![2, 0, 1]], 0.5);
+}
+
+#[test]
+fn test_color_jitter() {
+    let mut rng = Rng::new(0);
+    let image = Array::random_uniform(vec![3, 5, 5], 0., 1., &mut rng);
+    let jitter = ColorJitter::new(0.4, 0.4, 0.4);
+    let output = jitter.apply_image(&image, &mut Rng::new(1));
+
+    assert_arrays_rel_eq!(
+        ColorJitter::new(0., 0., 0.).apply_image(&image, &mut rng),
+        image,
+        1e-12
+    );
+    assert_ne!(output, image);
+    assert_eq!(jitter.apply_image(&image, &mut Rng::new(1)), output);
+    assert_eq!(
+        output.map(|x| if (0. ..=1.).contains(&x) { 0. } else { 1. }),
+        Array::new(0., vec![3, 5, 5])
+    );
+}
+
+#[test]
+fn test_color_jitter_keeps_grayscale_images_gray() {
+    let gray = Array::<f64>::random_uniform(vec![1, 4, 4], 0., 1., &mut Rng::new(0));
+    let rgb = &gray * &Array::new(1., vec![3, 1, 1]);
+    let output = ColorJitter::new(0.3, 0.3, 0.9).apply_image(&rgb, &mut Rng::new(2));
+
+    for y in 0..4 {
+        for x in 0..4 {
+            assert!((output[vec![0, y, x]] - output[vec![2, y, x]]).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_normalize_invalid_channels() {
+    let normalize = Normalize::new(vec![0.5, 0.5, 0.5], vec![0.2, 0.2, 0.2]);
+
+    normalize.apply_image(&Array::new(1., vec![1, 2, 2]), &mut Rng::new(0));
+}
+
+#[test]
+fn test_random_affine_identity() {
+    let image = Array::random_uniform(vec![2, 5, 6], 0., 1., &mut Rng::new(0));
+    let boxes = [BoundingBox::new(1., 1., 3., 4.)];
+    for &interpolation in [Interpolation::Nearest, Interpolation::Bilinear].iter() {
+        let affine = RandomAffine::new(0., (0., 0.), (1., 1.), interpolation, 0.);
+
+        assert_eq!(
+            affine.apply(&image, &boxes, &mut Rng::new(0)),
+            (image.clone(), boxes.to_vec())
+        );
+    }
+}
+
+#[test]
+fn test_random_affine_boxes_follow_content() {
+    let mut rng = Rng::new(3);
+    let image = box_image(1, 20, 20, [6, 8, 12, 11]);
+    let boxes = [BoundingBox::new(6., 8., 12., 11.)];
+    let affine = RandomAffine::new(30., (0.2, 0.2), (0.8, 1.2), Interpolation::Nearest, 0.);
+    for _ in 0..10 {
+        let (output, output_boxes) = affine.apply(&image, &boxes, &mut rng);
+        let content = content_box(&output);
+
+        // content of the box is contained in the transformed box, up to discretization
+        assert!(content.x_min >= output_boxes[0].x_min - 1.);
+        assert!(content.y_min >= output_boxes[0].y_min - 1.);
+        assert!(content.x_max <= output_boxes[0].x_max + 1.);
+        assert!(content.y_max <= output_boxes[0].y_max + 1.);
+    }
+}
+
+#[test]
+fn test_random_affine_fill() {
+    let image = Array::new(1., vec![1, 4, 4]);
+    let affine = RandomAffine::new(0., (0., 0.), (0.5, 0.5), Interpolation::Bilinear, -1.);
+    let output = affine.apply_image(&image, &mut Rng::new(0));
+
+    assert_eq!(output[vec![0, 0, 0]], -1.);
+    assert_eq!(output[vec![0, 1, 1]], 1.);
+}
+
+#[test]
+fn test_compose_is_reproducible() {
+    let image = Array::random_uniform(vec![3, 12, 12], 0., 1., &mut Rng::new(0));
+    let boxes = [BoundingBox::new(2., 3., 7., 9.)];
+    let mut transform = Compose::new();
+    transform.add(RandomAffine::new(
+        10.,
+        (0.1, 0.1),
+        (0.9, 1.1),
+        Interpolation::Bilinear,
+        0.,
+    ));
+    transform.add(RandomCrop::new(10, 10));
+    transform.add(RandomHorizontalFlip::new(0.5));
+    transform.add(Resize::new(5, 5, Interpolation::Bilinear));
+    transform.add(ColorJitter::new(0.2, 0.2, 0.2));
+    transform.add(Normalize::new(vec![0.5; 3], vec![0.25; 3]));
+
+    let (output, output_boxes) = transform.apply(&image, &boxes, &mut Rng::new(5));
+
+    assert_eq!(output.get_shape(), vec![3, 5, 5]);
+    assert_eq!(
+        transform.apply(&image, &boxes, &mut Rng::new(5)),
+        (output.clone(), output_boxes)
+    );
+    assert_ne!(transform.apply_image(&image, &mut Rng::new(6)), output);
+}
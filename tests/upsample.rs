@@ -0,0 +1,86 @@
+mod common;
+
+use common::{numerical_grad, weighted_sum};
+use neurust::linalg::utils::are_arrays_near_equal;
+use neurust::linalg::{upsample2d as upsample2d_array, DataFormat, Interpolation, Rng};
+use neurust::tensor::upsample::upsample2d;
+use neurust::{assert_arrays_rel_eq, Array, Tensor};
+
+#[test]
+fn test_upsample2d_nearest() {
+    let input = Array::from_vec(vec![1., 2., 3., 4., 5., 6.], vec![1, 2, 1, 3]);
+
+    assert_eq!(
+        upsample2d_array(&input, (2, 1), Interpolation::Nearest, DataFormat::Nchw),
+        Array::from_vec(
+            vec![1., 2., 3., 1., 2., 3., 4., 5., 6., 4., 5., 6.],
+            vec![1, 2, 2, 3]
+        )
+    );
+}
+
+#[test]
+fn test_upsample2d_bilinear() {
+    let input = Array::from_vec(vec![0., 4., 8., 12.], vec![1, 1, 2, 2]);
+
+    assert_arrays_rel_eq!(
+        upsample2d_array(&input, (2, 2), Interpolation::Bilinear, DataFormat::Nchw),
+        Array::from_vec(
+            vec![0., 1., 3., 4., 2., 3., 5., 6., 6., 7., 9., 10., 8., 9., 11., 12.],
+            vec![1, 1, 4, 4]
+        ),
+        1e-12
+    );
+}
+
+#[test]
+fn test_upsample2d_nhwc() {
+    let mut rng = Rng::new(0);
+    let input = Array::random_uniform(vec![2, 3, 2, 4], -1., 1., &mut rng);
+
+    for mode in [Interpolation::Nearest, Interpolation::Bilinear] {
+        assert_arrays_rel_eq!(
+            upsample2d_array(
+                &input.permute(&[0, 2, 3, 1]),
+                (3, 2),
+                mode,
+                DataFormat::Nhwc
+            ),
+            upsample2d_array(&input, (3, 2), mode, DataFormat::Nchw).permute(&[0, 2, 3, 1]),
+            1e-12
+        );
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_upsample2d_zero_scale() {
+    let input = Array::new(1., vec![1, 1, 2, 2]);
+
+    upsample2d_array(&input, (0, 2), Interpolation::Nearest, DataFormat::Nchw);
+}
+
+#[test]
+fn test_upsample2d_gradients() {
+    let mut rng = Rng::new(1);
+    for (mode, data_format) in [
+        (Interpolation::Nearest, DataFormat::Nchw),
+        (Interpolation::Bilinear, DataFormat::Nchw),
+        (Interpolation::Bilinear, DataFormat::Nhwc),
+    ] {
+        let input_array = Array::random_uniform(vec![2, 3, 3, 2], -1., 1., &mut rng);
+        let input = Tensor::new_variable(input_array.clone());
+        let output = upsample2d(&input, (2, 3), mode, data_format);
+        let weights = Array::random_uniform(output.shape(), -1., 1., &mut rng);
+        let loss = &output * &Tensor::new_variable(weights.clone());
+
+        assert_arrays_rel_eq!(
+            loss.grad(&input, None).unwrap(),
+            numerical_grad(
+                |x| weighted_sum(&upsample2d_array(x, (2, 3), mode, data_format), &weights),
+                &input_array
+            ),
+            1e-6
+        );
+    }
+}